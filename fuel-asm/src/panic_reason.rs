@@ -84,6 +84,8 @@ pub enum PanicReason {
     ArithmeticError = 0x23,
     /// The contract instruction is not allowed in predicates.
     ContractInstructionNotAllowed = 0x24,
+    /// The instruction is denied by the opcode policy of the interpreter.
+    InstructionNotAllowed = 0x25,
 }
 
 impl fmt::Display for PanicReason {
@@ -140,6 +142,7 @@ impl From<u8> for PanicReason {
             0x22 => MessageDataTooLong,
             0x23 => ArithmeticError,
             0x24 => ContractInstructionNotAllowed,
+            0x25 => InstructionNotAllowed,
             _ => UnknownPanicReason,
         }
     }
@@ -23,7 +23,7 @@ use alloc::{
 /// merkle tree, where the storage key is `Bytes32` and the value is the
 /// [`Buffer`](crate::sparse::Buffer) (raw presentation of the
 /// [`Node`](crate::sparse::Node)).
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct NodesTable;
 
 impl Mappable for NodesTable {
@@ -36,7 +36,7 @@ impl Mappable for NodesTable {
 type Storage = StorageMap<NodesTable>;
 type SparseMerkleTree = sparse::MerkleTree<NodesTable, Storage>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct MerkleTree {
     tree: SparseMerkleTree,
 }
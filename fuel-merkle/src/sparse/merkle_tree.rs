@@ -114,6 +114,16 @@ pub struct MerkleTree<TableType, StorageType> {
     phantom_table: PhantomData<TableType>,
 }
 
+impl<TableType, StorageType: Clone> Clone for MerkleTree<TableType, StorageType> {
+    fn clone(&self) -> Self {
+        Self {
+            root_node: self.root_node.clone(),
+            storage: self.storage.clone(),
+            phantom_table: Default::default(),
+        }
+    }
+}
+
 impl<TableType, StorageType> MerkleTree<TableType, StorageType> {
     pub const fn empty_root() -> &'static Bytes32 {
         empty_sum()
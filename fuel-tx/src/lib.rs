@@ -58,7 +58,9 @@ pub use builder::{
 
 #[cfg(feature = "alloc")]
 pub use receipt::{
+    PanicContext,
     Receipt,
+    ReceiptRepr,
     ScriptExecutionResult,
 };
 
@@ -33,11 +33,13 @@ use fuel_types::{
 #[cfg(feature = "std")]
 mod receipt_std;
 
+mod panic_context;
 mod receipt_repr;
 mod script_result;
 mod sizes;
 
-use receipt_repr::ReceiptRepr;
+pub use panic_context::PanicContext;
+pub use receipt_repr::ReceiptRepr;
 
 use crate::{
     input::message::compute_message_id,
@@ -90,6 +92,9 @@ pub enum Receipt {
         is: Word,
         #[derivative(PartialEq = "ignore", Hash = "ignore")]
         contract_id: Option<ContractId>,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        #[cfg_attr(feature = "serde", serde(default))]
+        context: PanicContext,
     },
 
     Revert {
@@ -254,6 +259,7 @@ impl Receipt {
             pc,
             is,
             contract_id: None,
+            context: PanicContext::None,
         }
     }
 
@@ -268,6 +274,21 @@ impl Receipt {
         self
     }
 
+    pub fn with_panic_context(mut self, panic_context: PanicContext) -> Self {
+        if let Receipt::Panic {
+            ref mut contract_id,
+            ref mut context,
+            ..
+        } = self
+        {
+            if let PanicContext::ContractId(id) = panic_context {
+                *contract_id = Some(id);
+            }
+            *context = panic_context;
+        }
+        self
+    }
+
     pub const fn revert(id: ContractId, ra: Word, pc: Word, is: Word) -> Self {
         Self::Revert { id, ra, pc, is }
     }
@@ -720,6 +741,13 @@ impl Receipt {
         }
     }
 
+    pub const fn panic_context(&self) -> Option<&PanicContext> {
+        match self {
+            Self::Panic { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
     fn variant_len_without_data(variant: ReceiptRepr) -> usize {
         match variant {
             ReceiptRepr::Call => CallSizesLayout::LEN,
@@ -757,7 +785,14 @@ impl SizedBytes for Receipt {
 
 #[cfg(test)]
 mod tests {
-    use crate::Receipt;
+    use crate::{
+        PanicContext,
+        Receipt,
+    };
+    use fuel_asm::{
+        PanicInstruction,
+        PanicReason,
+    };
     use fuel_types::ContractId;
 
     // TODO: Rewrite the test cases when `Receipt` will have its struct for
@@ -856,4 +891,22 @@ mod tests {
     fn receipt_to(#[case] receipt: Receipt, #[case] expected_to: Option<ContractId>) {
         assert_eq!(receipt.to(), expected_to.as_ref());
     }
+
+    #[test]
+    fn panic_context_is_metadata() {
+        let reason = PanicInstruction::error(PanicReason::ContractNotInInputs, 0);
+        let receipt = Receipt::panic(Default::default(), reason, 0, 0);
+        let id = ContractId::from([1; 32]);
+        let with_context = receipt
+            .clone()
+            .with_panic_context(PanicContext::ContractId(id));
+
+        assert_eq!(receipt.panic_context(), Some(&PanicContext::None));
+        assert_eq!(
+            with_context.panic_context(),
+            Some(&PanicContext::ContractId(id))
+        );
+        assert_eq!(with_context.contract_id(), Some(&id));
+        assert_eq!(receipt, with_context);
+    }
 }
@@ -0,0 +1,40 @@
+use fuel_types::{
+    AssetId,
+    ContractId,
+    Word,
+};
+
+/// Additional information about the reason of a panic.
+///
+/// It is metadata of [`crate::Receipt::Panic`], and isn't part of its binary
+/// representation.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PanicContext {
+    /// No additional information.
+    #[default]
+    None,
+    /// Contract missing from the inputs.
+    ContractId(ContractId),
+    /// Asset whose balance is insufficient.
+    AssetId(AssetId),
+    /// Asset whose balance is insufficient, with the amount required by the
+    /// instruction and the available one.
+    Balance {
+        asset_id: AssetId,
+        expected: Word,
+        actual: Word,
+    },
+    /// Memory range that couldn't be accessed.
+    MemoryRange { address: Word, size: Word },
+    /// Value expected by the instruction, and the actual one: the gas cost and
+    /// the remaining gas, the maximum and actual message data length, or the
+    /// stack pointer and the stack start.
+    Values { expected: Word, actual: Word },
+}
+
+impl PanicContext {
+    pub const fn is_none(&self) -> bool {
+        matches!(self, Self::None)
+    }
+}
@@ -39,6 +39,7 @@ macro_rules! enum_from {
 
 enum_from! {
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub enum ReceiptRepr {
         Call = 0x00,
         Return = 0x01,
@@ -8,13 +8,14 @@ use crate::{
     interpreter::{
         InitialBalances,
         Interpreter,
+        Memory,
     },
 };
 use derivative::Derivative;
+use std::borrow::Cow;
 
 use fuel_tx::ScriptExecutionResult;
 use fuel_types::{
-    fmt_truncated_hex,
     ContractId,
     Word,
 };
@@ -26,8 +27,7 @@ pub struct Backtrace {
     call_stack: Vec<CallFrame>,
     contract: ContractId,
     registers: [Word; VM_REGISTER_COUNT],
-    #[derivative(Debug(format_with = "fmt_truncated_hex::<16>"))]
-    memory: Vec<u8>,
+    memory: Memory,
     result: ScriptExecutionResult,
     initial_balances: InitialBalances,
 }
@@ -42,7 +42,7 @@ impl Backtrace {
     ) -> Self {
         let call_stack = vm.call_stack().to_owned();
         let contract = vm.internal_contract_or_default();
-        let memory = vm.memory().to_owned();
+        let memory = vm.paged_memory().clone();
         let initial_balances = vm.initial_balances().clone();
         let mut registers = [0; VM_REGISTER_COUNT];
 
@@ -73,9 +73,18 @@ impl Backtrace {
         &self.registers
     }
 
-    /// Memory of the VM when the error occurred.
-    pub fn memory(&self) -> &[u8] {
-        self.memory.as_slice()
+    /// Memory of the VM when the error occurred, as `MEM_SIZE` contiguous
+    /// bytes.
+    ///
+    /// See [`Interpreter::memory`] for the cost of the conversion.
+    pub fn memory(&self) -> Cow<'_, [u8]> {
+        self.memory.read(0..MEM_SIZE)
+    }
+
+    /// Memory of the VM when the error occurred, backed by lazily allocated
+    /// pages.
+    pub const fn paged_memory(&self) -> &Memory {
+        &self.memory
     }
 
     /// [`ScriptExecutionResult`] of the error that caused this backtrace.
@@ -112,7 +121,7 @@ impl Backtrace {
             call_stack,
             contract,
             registers,
-            memory,
+            memory.to_vec(),
             result,
             initial_balances,
         )
@@ -0,0 +1,12 @@
+//! Debug Adapter Protocol server for the FuelVM, over stdio.
+
+use std::io;
+
+use fuel_vm::dap::DapServer;
+
+fn main() -> io::Result<()> {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+
+    DapServer::new(stdin.lock(), stdout.lock()).run()
+}
@@ -0,0 +1,478 @@
+//! Optimistic parallel execution of the transactions of a block.
+//!
+//! Every transaction is first executed speculatively over the same storage
+//! snapshot, on its own thread pool slot, recording the keys it accesses and
+//! buffering its writes in a [`StorageTransaction`]. The buffered writes are
+//! then applied in the block order. A transaction that accessed a key written
+//! by a previously applied transaction observed a stale state, so it is
+//! executed again over the updated storage.
+//!
+//! Like in [`MemoryClient`](crate::memory_client::MemoryClient), the storage
+//! changes of reverted transactions are discarded. The resulting storage and
+//! receipts are the same as executing the transactions one after another
+//! with [`BlockExecutor::execute_sequential`].
+
+use std::{
+    borrow::Cow,
+    num::NonZeroUsize,
+    sync::{
+        atomic::{
+            AtomicUsize,
+            Ordering,
+        },
+        Mutex,
+    },
+    thread,
+};
+
+use fuel_storage::{
+    Mappable,
+    MerkleRoot,
+    MerkleRootStorage,
+    StorageInspect,
+    StorageMutate,
+    StorageRead,
+    StorageSize,
+};
+use fuel_tx::{
+    ConsensusParameters,
+    Create,
+    Script,
+    Transaction,
+};
+use fuel_types::{
+    Address,
+    BlockHeight,
+    Bytes32,
+    ContractId,
+    Word,
+};
+
+use crate::{
+    checked_transaction::{
+        Checked,
+        IntoChecked,
+    },
+    error::InterpreterError,
+    gas::GasCosts,
+    interpreter::{
+        CheckedMetadata,
+        ExecutableTransaction,
+        Interpreter,
+    },
+    state::StateTransition,
+    storage::{
+        ChangeSet,
+        ContractsAssetsStorage,
+        InterpreterStorage,
+        ReadWriteRecorder,
+        ReadWriteSet,
+        StorageTransaction,
+    },
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Transaction executed by the [`BlockExecutor`].
+#[derive(Debug, Clone)]
+#[allow(missing_docs)]
+pub enum BlockTransaction {
+    Script(Checked<Script>),
+    Create(Checked<Create>),
+}
+
+impl From<Checked<Script>> for BlockTransaction {
+    fn from(checked: Checked<Script>) -> Self {
+        Self::Script(checked)
+    }
+}
+
+impl From<Checked<Create>> for BlockTransaction {
+    fn from(checked: Checked<Create>) -> Self {
+        Self::Create(checked)
+    }
+}
+
+/// Result of the execution of a transaction of the block.
+pub type BlockTransactionResult = Result<StateTransition<Transaction>, InterpreterError>;
+
+/// Outcome of the execution of a block.
+#[derive(Debug)]
+pub struct BlockExecution<S> {
+    storage: S,
+    results: Vec<BlockTransactionResult>,
+    reexecuted: Vec<usize>,
+}
+
+impl<S> BlockExecution<S> {
+    /// Storage with the changes of the non-reverted transactions applied.
+    pub const fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// Results of the transactions, in the block order.
+    pub fn results(&self) -> &[BlockTransactionResult] {
+        self.results.as_slice()
+    }
+
+    /// Indexes of the transactions whose speculative execution conflicted
+    /// with a previous transaction, and were executed again.
+    pub fn reexecuted(&self) -> &[usize] {
+        self.reexecuted.as_slice()
+    }
+
+    /// Convert this instance into the storage and the transaction results.
+    pub fn into_inner(self) -> (S, Vec<BlockTransactionResult>) {
+        (self.storage, self.results)
+    }
+}
+
+/// Executes the transactions of a block, in parallel where they don't
+/// access the same storage keys.
+#[derive(Debug, Clone)]
+pub struct BlockExecutor {
+    params: ConsensusParameters,
+    gas_costs: GasCosts,
+    threads: NonZeroUsize,
+}
+
+impl BlockExecutor {
+    /// Create a new executor, using as many threads as the available
+    /// parallelism.
+    pub fn new(params: ConsensusParameters, gas_costs: GasCosts) -> Self {
+        let threads =
+            thread::available_parallelism().unwrap_or(NonZeroUsize::new(1).unwrap());
+
+        Self {
+            params,
+            gas_costs,
+            threads,
+        }
+    }
+
+    /// Set the number of threads used for the speculative executions.
+    pub fn with_threads(&mut self, threads: NonZeroUsize) -> &mut Self {
+        self.threads = threads;
+        self
+    }
+
+    /// Execute the transactions speculatively in parallel over `storage`, and
+    /// commit them in order, executing again the ones that conflict.
+    ///
+    /// Returns an error if the storage fails while applying the changes.
+    pub fn execute<S>(
+        &self,
+        storage: S,
+        transactions: Vec<BlockTransaction>,
+    ) -> Result<BlockExecution<S>, InterpreterError>
+    where
+        S: InterpreterStorage + Sync,
+    {
+        let speculative = self.execute_speculatively(&storage, &transactions);
+
+        let mut storage = storage;
+        let mut written = ReadWriteSet::default();
+        let mut results = Vec::with_capacity(transactions.len());
+        let mut reexecuted = vec![];
+
+        for (index, (transaction, speculated)) in
+            transactions.into_iter().zip(speculative).enumerate()
+        {
+            let speculated = if speculated.executed.accesses.conflicts_with(&written) {
+                reexecuted.push(index);
+                self.speculate(&storage, transaction)
+            } else {
+                speculated
+            };
+
+            let Speculated { executed, writes } = speculated;
+            if let Some(writes) = writes {
+                writes
+                    .apply(&mut storage)
+                    .map_err(InterpreterError::from_io)?;
+                written.extend_writes(&executed.accesses);
+            }
+            results.push(executed.result);
+        }
+
+        Ok(BlockExecution {
+            storage,
+            results,
+            reexecuted,
+        })
+    }
+
+    /// Execute the transactions one after another directly over `storage`.
+    ///
+    /// This is the reference the parallel execution is equivalent to.
+    pub fn execute_sequential<S>(
+        &self,
+        storage: S,
+        transactions: Vec<BlockTransaction>,
+    ) -> Result<BlockExecution<S>, InterpreterError>
+    where
+        S: InterpreterStorage,
+    {
+        let mut storage = storage;
+        let mut results = Vec::with_capacity(transactions.len());
+
+        for transaction in transactions {
+            let mut transaction_storage = StorageTransaction::new(&mut storage);
+            let executed =
+                self.execute_transaction(&mut transaction_storage, transaction);
+            if executed.committed {
+                transaction_storage
+                    .commit()
+                    .map_err(InterpreterError::from_io)?;
+            }
+            results.push(executed.result);
+        }
+
+        Ok(BlockExecution {
+            storage,
+            results,
+            reexecuted: vec![],
+        })
+    }
+
+    fn execute_speculatively<S>(
+        &self,
+        storage: &S,
+        transactions: &[BlockTransaction],
+    ) -> Vec<Speculated>
+    where
+        S: InterpreterStorage + Sync,
+    {
+        let next = AtomicUsize::new(0);
+        let speculated = Mutex::new(Vec::with_capacity(transactions.len()));
+        let threads = self.threads.get().min(transactions.len());
+
+        thread::scope(|scope| {
+            for _ in 0..threads {
+                scope.spawn(|| loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(transaction) = transactions.get(index) else {
+                        break
+                    };
+                    let result = self.speculate(storage, transaction.clone());
+                    speculated
+                        .lock()
+                        .expect("Executions never panic while holding the lock")
+                        .push((index, result));
+                });
+            }
+        });
+
+        let mut speculated = speculated
+            .into_inner()
+            .expect("Executions never panic while holding the lock");
+        speculated.sort_by_key(|(index, _)| *index);
+        speculated.into_iter().map(|(_, result)| result).collect()
+    }
+
+    /// Execute `transaction` over a read-only `storage`, buffering its changes.
+    fn speculate<S>(&self, storage: &S, transaction: BlockTransaction) -> Speculated
+    where
+        S: InterpreterStorage,
+    {
+        let mut transaction_storage = StorageTransaction::new(Snapshot(storage));
+        let executed = self.execute_transaction(&mut transaction_storage, transaction);
+        let writes = executed.committed.then(|| transaction_storage.changes());
+
+        Speculated { executed, writes }
+    }
+
+    /// Execute `transaction` over `storage`, recording the accessed keys.
+    fn execute_transaction<S>(
+        &self,
+        storage: S,
+        transaction: BlockTransaction,
+    ) -> Executed
+    where
+        S: InterpreterStorage,
+    {
+        match transaction {
+            BlockTransaction::Script(checked) => self.transact(storage, checked),
+            BlockTransaction::Create(checked) => self.transact(storage, checked),
+        }
+    }
+
+    fn transact<S, Tx>(&self, storage: S, checked: Checked<Tx>) -> Executed
+    where
+        S: InterpreterStorage,
+        Tx: ExecutableTransaction + Into<Transaction>,
+        <Tx as IntoChecked>::Metadata: CheckedMetadata,
+    {
+        let mut vm = Interpreter::with_storage(
+            ReadWriteRecorder::new(storage),
+            self.params,
+            self.gas_costs.clone(),
+        );
+
+        let result = vm
+            .transact(checked)
+            .map(StateTransition::from)
+            .map(|state| {
+                let accesses = state.read_write_set().cloned();
+                let (state, tx, receipts) = state.into_inner();
+                StateTransition::new(state, tx.into(), receipts)
+                    .with_read_write_set(accesses)
+            });
+        let accesses = vm.read_write_set().cloned().unwrap_or_default();
+        let committed = matches!(&result, Ok(state) if !state.should_revert());
+
+        Executed {
+            result,
+            accesses,
+            committed,
+        }
+    }
+}
+
+/// Transaction executed over some storage state.
+struct Executed {
+    result: BlockTransactionResult,
+    accesses: ReadWriteSet,
+    /// Whether the changes of the transaction are kept, i.e. it wasn't
+    /// reverted.
+    committed: bool,
+}
+
+/// Transaction executed over a snapshot of the storage.
+struct Speculated {
+    executed: Executed,
+    /// Changes to apply, or `None` if the transaction was reverted.
+    writes: Option<ChangeSet>,
+}
+
+/// Read-only view of the storage shared by the speculative executions.
+///
+/// The executions write to a [`StorageTransaction`] over the view that is
+/// never committed, so the writes never reach it.
+struct Snapshot<'a, S>(&'a S);
+
+impl<Type: Mappable, S> StorageInspect<Type> for Snapshot<'_, S>
+where
+    S: StorageInspect<Type>,
+{
+    type Error = S::Error;
+
+    fn get(
+        &self,
+        key: &Type::Key,
+    ) -> Result<Option<Cow<'_, Type::OwnedValue>>, Self::Error> {
+        self.0.get(key)
+    }
+
+    fn contains_key(&self, key: &Type::Key) -> Result<bool, Self::Error> {
+        self.0.contains_key(key)
+    }
+}
+
+impl<Type: Mappable, S> StorageMutate<Type> for Snapshot<'_, S>
+where
+    S: StorageInspect<Type>,
+{
+    fn insert(
+        &mut self,
+        _key: &Type::Key,
+        _value: &Type::Value,
+    ) -> Result<Option<Type::OwnedValue>, Self::Error> {
+        unreachable!("The snapshot is only written through a storage transaction")
+    }
+
+    fn remove(
+        &mut self,
+        _key: &Type::Key,
+    ) -> Result<Option<Type::OwnedValue>, Self::Error> {
+        unreachable!("The snapshot is only written through a storage transaction")
+    }
+}
+
+impl<Type: Mappable, S> StorageSize<Type> for Snapshot<'_, S>
+where
+    S: StorageSize<Type>,
+{
+    fn size_of_value(&self, key: &Type::Key) -> Result<Option<usize>, Self::Error> {
+        self.0.size_of_value(key)
+    }
+}
+
+impl<Type: Mappable, S> StorageRead<Type> for Snapshot<'_, S>
+where
+    S: StorageRead<Type>,
+{
+    fn read(
+        &self,
+        key: &Type::Key,
+        buf: &mut [u8],
+    ) -> Result<Option<usize>, Self::Error> {
+        self.0.read(key, buf)
+    }
+
+    fn read_alloc(&self, key: &Type::Key) -> Result<Option<Vec<u8>>, Self::Error> {
+        self.0.read_alloc(key)
+    }
+}
+
+impl<Key, Type: Mappable, S> MerkleRootStorage<Key, Type> for Snapshot<'_, S>
+where
+    S: MerkleRootStorage<Key, Type>,
+{
+    fn root(&self, key: &Key) -> Result<MerkleRoot, Self::Error> {
+        self.0.root(key)
+    }
+}
+
+impl<S: ContractsAssetsStorage> ContractsAssetsStorage for Snapshot<'_, S> {}
+
+impl<S> InterpreterStorage for Snapshot<'_, S>
+where
+    S: InterpreterStorage,
+{
+    type DataError = S::DataError;
+
+    fn block_height(&self) -> Result<BlockHeight, Self::DataError> {
+        self.0.block_height()
+    }
+
+    fn timestamp(&self, height: BlockHeight) -> Result<Word, Self::DataError> {
+        self.0.timestamp(height)
+    }
+
+    fn block_hash(&self, block_height: BlockHeight) -> Result<Bytes32, Self::DataError> {
+        self.0.block_hash(block_height)
+    }
+
+    fn coinbase(&self) -> Result<Address, Self::DataError> {
+        self.0.coinbase()
+    }
+
+    fn merkle_contract_state_range(
+        &self,
+        id: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> Result<Vec<Option<Cow<'_, Bytes32>>>, Self::DataError> {
+        self.0.merkle_contract_state_range(id, start_key, range)
+    }
+
+    fn merkle_contract_state_insert_range(
+        &mut self,
+        _contract: &ContractId,
+        _start_key: &Bytes32,
+        _values: &[Bytes32],
+    ) -> Result<Option<()>, Self::DataError> {
+        unreachable!("The snapshot is only written through a storage transaction")
+    }
+
+    fn merkle_contract_state_remove_range(
+        &mut self,
+        _contract: &ContractId,
+        _start_key: &Bytes32,
+        _range: Word,
+    ) -> Result<Option<()>, Self::DataError> {
+        unreachable!("The snapshot is only written through a storage transaction")
+    }
+}
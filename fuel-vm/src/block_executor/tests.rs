@@ -0,0 +1,219 @@
+use std::num::NonZeroUsize;
+
+use fuel_asm::{
+    op,
+    Instruction,
+    RegId,
+};
+use fuel_tx::{
+    Output,
+    TransactionBuilder,
+};
+use fuel_types::{
+    Bytes32,
+    Immediate18,
+};
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+use super::*;
+use crate::{
+    prelude::*,
+    script_with_data_offset,
+    storage::ContractsState,
+};
+
+/// Increments the state slot `0` and logs its new value.
+fn counter() -> Vec<Instruction> {
+    vec![
+        op::movi(0x10, Bytes32::LEN as Immediate18),
+        op::aloc(0x10),
+        op::srw(0x11, 0x12, RegId::HP),
+        op::addi(0x11, 0x11, 1),
+        op::sww(RegId::HP, 0x12, 0x11),
+        op::log(0x11, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ]
+}
+
+/// Script calling `contract_id`, then reverting if `revert` is set.
+fn call(
+    test_context: &mut TestBuilder,
+    contract_id: ContractId,
+    revert: bool,
+) -> BlockTransaction {
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset as Immediate18),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            if revert {
+                op::rvrt(RegId::ONE)
+            } else {
+                op::ret(RegId::ONE)
+            },
+        ],
+        test_context.tx_offset()
+    );
+    let script_data: Vec<u8> = contract_id
+        .iter()
+        .copied()
+        .chain((0 as Word).to_be_bytes())
+        .chain((0 as Word).to_be_bytes())
+        .collect();
+
+    test_context
+        .start_script(script, script_data)
+        .gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build()
+        .into()
+}
+
+/// Transaction deploying `program`, and the id of the deployed contract.
+fn create(
+    test_context: &mut TestBuilder,
+    program: Vec<Instruction>,
+) -> (BlockTransaction, ContractId) {
+    let salt: Salt = StdRng::seed_from_u64(42u64).gen();
+    let program: Vec<u8> = program.into_iter().collect();
+    let contract = Contract::from(program.as_slice());
+    let state_root = Contract::default_state_root();
+    let contract_id = contract.id(&salt, &contract.root(), &state_root);
+
+    let tx = TransactionBuilder::create(program.into(), salt, vec![])
+        .gas_limit(1_000_000)
+        .add_random_fee_input()
+        .add_output(Output::contract_created(contract_id, state_root))
+        .finalize()
+        .into_checked(
+            test_context.get_block_height(),
+            test_context.get_params(),
+            &GasCosts::default(),
+        )
+        .expect("failed to check tx");
+
+    (tx.into(), contract_id)
+}
+
+fn assert_same_execution(
+    parallel: &BlockExecution<MemoryStorage>,
+    sequential: &BlockExecution<MemoryStorage>,
+    contracts: &[ContractId],
+) {
+    assert_eq!(parallel.results(), sequential.results());
+    assert_eq!(
+        parallel.storage().all_contract_state().collect::<Vec<_>>(),
+        sequential
+            .storage()
+            .all_contract_state()
+            .collect::<Vec<_>>()
+    );
+    for contract in contracts {
+        assert_eq!(
+            parallel.storage().storage_contract(contract).unwrap(),
+            sequential.storage().storage_contract(contract).unwrap()
+        );
+        assert_eq!(
+            parallel.storage().storage::<ContractsState>().root(contract),
+            sequential.storage().storage::<ContractsState>().root(contract)
+        );
+    }
+}
+
+fn logged(result: &BlockTransactionResult) -> Vec<Word> {
+    result
+        .as_ref()
+        .expect("Expected a state transition")
+        .receipts()
+        .iter()
+        .filter_map(|receipt| match receipt {
+            Receipt::Log { ra, .. } => Some(*ra),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn block_executor_matches_sequential_execution() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let first = test_context
+        .setup_contract(counter(), None, None)
+        .contract_id;
+    let second = test_context
+        .setup_contract(counter(), None, None)
+        .contract_id;
+    let (deploy, deployed) = create(&mut test_context, counter());
+
+    let transactions = vec![
+        call(&mut test_context, first, false),
+        call(&mut test_context, second, false),
+        call(&mut test_context, first, true),
+        call(&mut test_context, first, false),
+        deploy,
+        call(&mut test_context, deployed, false),
+        call(&mut test_context, second, false),
+    ];
+
+    let mut executor =
+        BlockExecutor::new(*test_context.get_params(), GasCosts::default());
+    executor.with_threads(NonZeroUsize::new(4).unwrap());
+
+    let storage = test_context.get_storage().clone();
+    let parallel = executor
+        .execute(storage.clone(), transactions.clone())
+        .expect("Expected block execution");
+    let sequential = executor
+        .execute_sequential(storage, transactions)
+        .expect("Expected block execution");
+
+    assert_same_execution(&parallel, &sequential, &[first, second, deployed]);
+
+    // The reverted call doesn't increment the counter for the next one
+    let logs: Vec<_> = parallel.results().iter().map(logged).collect();
+    assert_eq!(
+        logs,
+        vec![vec![1], vec![1], vec![2], vec![2], vec![], vec![1], vec![2]]
+    );
+
+    // Only the calls observing a previous committed increment run again.
+    // The reverted call conflicts with the first one but doesn't commit.
+    assert_eq!(parallel.reexecuted(), &[2, 3, 5, 6]);
+}
+
+#[test]
+fn block_executor_without_conflicts_runs_once() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let contracts: Vec<_> = (0..8)
+        .map(|_| {
+            test_context
+                .setup_contract(counter(), None, None)
+                .contract_id
+        })
+        .collect();
+    let transactions: Vec<_> = contracts
+        .iter()
+        .map(|contract| call(&mut test_context, *contract, false))
+        .collect();
+
+    let executor = BlockExecutor::new(*test_context.get_params(), GasCosts::default());
+    let storage = test_context.get_storage().clone();
+    let parallel = executor
+        .execute(storage.clone(), transactions.clone())
+        .expect("Expected block execution");
+    let sequential = executor
+        .execute_sequential(storage, transactions)
+        .expect("Expected block execution");
+
+    assert_same_execution(&parallel, &sequential, &contracts);
+    assert!(parallel.reexecuted().is_empty());
+    assert!(parallel
+        .results()
+        .iter()
+        .all(|result| logged(result) == vec![1]));
+}
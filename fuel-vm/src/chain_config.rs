@@ -0,0 +1,319 @@
+//! Chain configuration to start a local VM environment.
+//!
+//! A [`ChainConfig`] bundles the consensus parameters, the gas schedule and
+//! the genesis state. With the `json` feature it is loaded from a JSON file,
+//! where the omitted fields take their default value.
+
+#[cfg(test)]
+mod tests;
+
+use std::collections::BTreeSet;
+
+use fuel_tx::{
+    ConsensusParameters,
+    Contract,
+    Input,
+    Script,
+    StorageSlot,
+    UtxoId,
+};
+use fuel_types::{
+    Address,
+    AssetId,
+    BlockHeight,
+    ContractId,
+    Nonce,
+    Salt,
+    Word,
+};
+
+use crate::{
+    consts::MEM_SIZE,
+    gas::GasCostsValues,
+    memory_client::MemoryClient,
+    storage::{
+        ContractsAssetsStorage,
+        InterpreterStorage,
+        MemoryStorage,
+    },
+    transactor::Transactor,
+};
+
+/// Inconsistency of a [`ChainConfig`].
+#[derive(Debug, thiserror::Error)]
+pub enum ChainConfigError {
+    /// A predicate may use more gas than the whole transaction.
+    #[error("`max_gas_per_predicate` is greater than `max_gas_per_tx`")]
+    PredicateGasExceedsTxGas,
+    /// The fees can't be computed with a zero factor.
+    #[error("`gas_price_factor` is zero")]
+    ZeroGasPriceFactor,
+    /// The inputs of a transaction don't fit in the VM memory.
+    #[error("`max_inputs` doesn't fit the transaction in the VM memory")]
+    TxOffsetExceedsMemory,
+    /// The bytecode of a contract is larger than `contract_max_size`.
+    #[error("Contract {0} is larger than `contract_max_size`")]
+    ContractTooLarge(ContractId),
+    /// A contract has more storage slots than `max_storage_slots`.
+    #[error("Contract {0} has more storage slots than `max_storage_slots`")]
+    TooManyStorageSlots(ContractId),
+    /// Two contracts have the same id.
+    #[error("Contract {0} is deployed twice")]
+    DuplicateContract(ContractId),
+    /// Two coins have the same UTXO id.
+    #[error("Coin {0:?} is defined twice")]
+    DuplicateCoin(UtxoId),
+    /// Two messages have the same nonce.
+    #[error("Message {0} is defined twice")]
+    DuplicateMessage(Nonce),
+    /// The data of a message is longer than `max_message_data_length`.
+    #[error("Message {0} has more data than `max_message_data_length`")]
+    MessageDataTooLong(Nonce),
+    /// The configuration file is invalid.
+    #[cfg(feature = "json")]
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+/// Contract deployed at genesis.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ContractConfig {
+    /// Bytecode of the contract.
+    pub code: Contract,
+    /// Salt of the contract id.
+    pub salt: Salt,
+    /// Initial storage slots.
+    pub storage_slots: Vec<StorageSlot>,
+    /// Initial asset balances.
+    pub balances: Vec<(AssetId, Word)>,
+}
+
+impl ContractConfig {
+    /// Id of the contract, as if it was deployed by a `Create` transaction.
+    pub fn id(&self) -> ContractId {
+        let state_root = Contract::initial_state_root(self.storage_slots.iter());
+        self.code.id(&self.salt, &self.code.root(), &state_root)
+    }
+}
+
+/// Coin owned at genesis.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct CoinConfig {
+    /// UTXO id of the coin.
+    pub utxo_id: UtxoId,
+    /// Owner of the coin.
+    pub owner: Address,
+    /// Amount of the coin.
+    pub amount: Word,
+    /// Asset of the coin.
+    pub asset_id: AssetId,
+}
+
+impl CoinConfig {
+    /// Signed input spending the coin.
+    pub fn input(&self, witness_index: u8) -> Input {
+        Input::coin_signed(
+            self.utxo_id,
+            self.owner,
+            self.amount,
+            self.asset_id,
+            Default::default(),
+            witness_index,
+            Default::default(),
+        )
+    }
+}
+
+/// Message received at genesis.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct MessageConfig {
+    /// Sender of the message.
+    pub sender: Address,
+    /// Recipient of the message.
+    pub recipient: Address,
+    /// Nonce of the message.
+    pub nonce: Nonce,
+    /// Amount of base asset carried by the message.
+    pub amount: Word,
+    /// Data of the message.
+    pub data: Vec<u8>,
+}
+
+impl MessageConfig {
+    /// Signed input spending the message.
+    pub fn input(&self, witness_index: u8) -> Input {
+        if self.data.is_empty() {
+            Input::message_coin_signed(
+                self.sender,
+                self.recipient,
+                self.amount,
+                self.nonce,
+                witness_index,
+            )
+        } else {
+            Input::message_data_signed(
+                self.sender,
+                self.recipient,
+                self.amount,
+                self.nonce,
+                witness_index,
+                self.data.clone(),
+            )
+        }
+    }
+}
+
+/// Consensus parameters, gas schedule and genesis state of a chain.
+///
+/// The VM doesn't keep track of the UTXOs, so the coins and messages are only
+/// listed here, to build the inputs of the transactions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ChainConfig {
+    /// Consensus parameters of the chain.
+    pub consensus_parameters: ConsensusParameters,
+    /// Gas schedule of the instructions.
+    pub gas_costs: GasCostsValues,
+    /// Height of the first block.
+    pub block_height: BlockHeight,
+    /// Timestamp of the first block.
+    pub block_timestamp: Word,
+    /// Coinbase of the blocks.
+    pub coinbase: Address,
+    /// Contracts deployed at genesis.
+    pub contracts: Vec<ContractConfig>,
+    /// Coins owned at genesis.
+    pub coins: Vec<CoinConfig>,
+    /// Messages received at genesis.
+    pub messages: Vec<MessageConfig>,
+}
+
+impl Default for ChainConfig {
+    fn default() -> Self {
+        let storage = MemoryStorage::default();
+        let block_height = storage.block_height().expect("Infallible");
+
+        Self {
+            consensus_parameters: ConsensusParameters::DEFAULT,
+            gas_costs: GasCostsValues::default(),
+            block_height,
+            block_timestamp: storage.timestamp(block_height).expect("Infallible"),
+            coinbase: storage.coinbase().expect("Infallible"),
+            contracts: vec![],
+            coins: vec![],
+            messages: vec![],
+        }
+    }
+}
+
+impl ChainConfig {
+    /// Load and validate a configuration from JSON.
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &str) -> Result<Self, ChainConfigError> {
+        let config: Self = serde_json::from_str(json)?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check that the parameters are consistent with each other and with the
+    /// genesis state.
+    pub fn validate(&self) -> Result<(), ChainConfigError> {
+        let params = &self.consensus_parameters;
+
+        if params.max_gas_per_predicate > params.max_gas_per_tx {
+            return Err(ChainConfigError::PredicateGasExceedsTxGas)
+        }
+        if params.gas_price_factor == 0 {
+            return Err(ChainConfigError::ZeroGasPriceFactor)
+        }
+        if params.max_inputs > MEM_SIZE as u64 || params.tx_offset() >= MEM_SIZE {
+            return Err(ChainConfigError::TxOffsetExceedsMemory)
+        }
+
+        let mut contracts = BTreeSet::new();
+        for contract in &self.contracts {
+            let id = contract.id();
+            if contract.code.as_ref().len() as u64 > params.contract_max_size {
+                return Err(ChainConfigError::ContractTooLarge(id))
+            }
+            if contract.storage_slots.len() as u64 > params.max_storage_slots {
+                return Err(ChainConfigError::TooManyStorageSlots(id))
+            }
+            if !contracts.insert(id) {
+                return Err(ChainConfigError::DuplicateContract(id))
+            }
+        }
+
+        let mut coins = BTreeSet::new();
+        for coin in &self.coins {
+            if !coins.insert(coin.utxo_id) {
+                return Err(ChainConfigError::DuplicateCoin(coin.utxo_id))
+            }
+        }
+
+        let mut messages = BTreeSet::new();
+        for message in &self.messages {
+            if message.data.len() as u64 > params.max_message_data_length {
+                return Err(ChainConfigError::MessageDataTooLong(message.nonce))
+            }
+            if !messages.insert(message.nonce) {
+                return Err(ChainConfigError::DuplicateMessage(message.nonce))
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate the configuration and build the genesis storage.
+    pub fn storage(&self) -> Result<MemoryStorage, ChainConfigError> {
+        self.validate()?;
+
+        let mut storage = MemoryStorage::new(self.block_height, self.coinbase);
+        storage.set_block_timestamp(self.block_timestamp);
+        for contract in &self.contracts {
+            let id = contract.id();
+            storage
+                .deploy_contract_with_id(
+                    &contract.salt,
+                    &contract.storage_slots,
+                    &contract.code,
+                    &contract.code.root(),
+                    &id,
+                )
+                .expect("Infallible");
+            for (asset, balance) in &contract.balances {
+                storage
+                    .merkle_contract_asset_id_balance_insert(&id, asset, *balance)
+                    .expect("Infallible");
+            }
+        }
+        storage.commit();
+        storage.persist();
+
+        Ok(storage)
+    }
+
+    /// Validate the configuration and build a transactor over the genesis
+    /// storage.
+    pub fn transactor(
+        &self,
+    ) -> Result<Transactor<MemoryStorage, Script>, ChainConfigError> {
+        Ok(Transactor::new(
+            self.storage()?,
+            self.consensus_parameters,
+            self.gas_costs.clone().into(),
+        ))
+    }
+
+    /// Validate the configuration and build a client over the genesis storage.
+    pub fn memory_client(&self) -> Result<MemoryClient, ChainConfigError> {
+        self.transactor().map(MemoryClient::from_txtor)
+    }
+}
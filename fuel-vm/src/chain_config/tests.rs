@@ -0,0 +1,160 @@
+use fuel_asm::op;
+use fuel_tx::{
+    ConsensusParameters,
+    Contract,
+    Finalizable,
+    Receipt,
+    StorageSlot,
+    TransactionBuilder,
+};
+use fuel_types::{
+    AssetId,
+    BlockHeight,
+    Bytes32,
+    Nonce,
+    Salt,
+};
+
+use crate::{
+    chain_config::{
+        ChainConfig,
+        ChainConfigError,
+        ContractConfig,
+        MessageConfig,
+    },
+    checked_transaction::IntoChecked,
+    gas::GasCosts,
+    storage::{
+        ContractsAssetsStorage,
+        InterpreterStorage,
+    },
+};
+
+fn contract() -> ContractConfig {
+    ContractConfig {
+        code: Contract::from(op::ret(0x01).to_bytes().to_vec()),
+        salt: Salt::from([1; 32]),
+        storage_slots: vec![StorageSlot::new([2; 32].into(), [3; 32].into())],
+        balances: vec![(AssetId::zeroed(), 100)],
+    }
+}
+
+#[test]
+fn json_config_builds_the_genesis_state() {
+    let json = r#"{
+        "consensus_parameters": { "max_gas_per_tx": 1000000, "max_gas_per_predicate": 1000 },
+        "block_height": 10,
+        "block_timestamp": 4611686018427387914
+    }"#;
+    let mut config = ChainConfig::from_json(json).unwrap();
+    assert_eq!(config.consensus_parameters.max_gas_per_tx, 1_000_000);
+    assert_eq!(
+        config.consensus_parameters.max_inputs,
+        ConsensusParameters::DEFAULT.max_inputs
+    );
+    config.contracts.push(contract());
+
+    let id = contract().id();
+    let client = config.memory_client().unwrap();
+    let storage = client.as_ref();
+    assert_eq!(storage.block_height().unwrap(), BlockHeight::new(10));
+    assert_eq!(
+        storage.timestamp(BlockHeight::new(11)).unwrap(),
+        4611686018427387924
+    );
+    assert!(storage.storage_contract_exists(&id).unwrap());
+    assert_eq!(
+        storage
+            .merkle_contract_state(&id, &[2; 32].into())
+            .unwrap()
+            .map(|value| value.into_owned()),
+        Some(Bytes32::from([3; 32]))
+    );
+    assert_eq!(
+        storage
+            .merkle_contract_asset_id_balance(&id, &AssetId::zeroed())
+            .unwrap(),
+        Some(100)
+    );
+}
+
+#[test]
+fn transactor_runs_scripts_with_the_config_parameters() {
+    let config = ChainConfig::default();
+    let mut client = config.memory_client().unwrap();
+
+    let tx = TransactionBuilder::script(op::ret(0x01).to_bytes().to_vec(), vec![])
+        .gas_limit(1_000_000)
+        .add_random_fee_input()
+        .finalize()
+        .into_checked(
+            config.block_height,
+            &config.consensus_parameters,
+            &GasCosts::default(),
+        )
+        .unwrap();
+    let receipts = client.transact(tx);
+
+    assert!(matches!(
+        receipts.last(),
+        Some(Receipt::ScriptResult { .. })
+    ));
+}
+
+#[test]
+fn inconsistent_configs_are_rejected() {
+    let mut config = ChainConfig::default();
+    config.consensus_parameters.max_gas_per_predicate =
+        config.consensus_parameters.max_gas_per_tx + 1;
+    assert!(matches!(
+        config.validate(),
+        Err(ChainConfigError::PredicateGasExceedsTxGas)
+    ));
+
+    let mut config = ChainConfig::default();
+    config.consensus_parameters.gas_price_factor = 0;
+    assert!(matches!(
+        config.memory_client(),
+        Err(ChainConfigError::ZeroGasPriceFactor)
+    ));
+
+    let mut config = ChainConfig::default();
+    config.consensus_parameters.max_inputs = u64::MAX;
+    assert!(matches!(
+        config.validate(),
+        Err(ChainConfigError::TxOffsetExceedsMemory)
+    ));
+
+    let config = ChainConfig {
+        contracts: vec![contract(), contract()],
+        ..Default::default()
+    };
+    assert!(matches!(
+        config.validate(),
+        Err(ChainConfigError::DuplicateContract(id)) if id == contract().id()
+    ));
+
+    let mut config = ChainConfig {
+        contracts: vec![contract()],
+        ..Default::default()
+    };
+    config.consensus_parameters.max_storage_slots = 0;
+    assert!(matches!(
+        config.validate(),
+        Err(ChainConfigError::TooManyStorageSlots(_))
+    ));
+
+    let mut config = ChainConfig {
+        messages: vec![MessageConfig {
+            nonce: Nonce::from([1; 32]),
+            data: vec![0; 2],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    config.consensus_parameters.max_message_data_length = 1;
+    assert!(matches!(
+        config.validate(),
+        Err(ChainConfigError::MessageDataTooLong(_))
+    ));
+}
@@ -35,7 +35,10 @@ use crate::{
         AvailableBalances,
     },
     error::PredicateVerificationFailed,
-    gas::GasCosts,
+    gas::{
+        GasCosts,
+        Schedule,
+    },
     interpreter::{
         CheckedMetadata as CheckedMetadataAccessTrait,
         InitialBalances,
@@ -197,6 +200,20 @@ pub trait IntoChecked: FormatValidityChecks + Sized {
             .check_predicates(params, gas_costs)
     }
 
+    /// Returns transaction that passed all `Checks`, with the gas costs and
+    /// consensus parameters of `schedule` at `block_height`.
+    fn into_checked_with_schedule(
+        self,
+        block_height: BlockHeight,
+        schedule: &Schedule,
+    ) -> Result<Checked<Self>, CheckError>
+    where
+        Checked<Self>: CheckPredicates,
+    {
+        let entry = schedule.at(block_height);
+        self.into_checked(block_height, entry.params(), entry.gas_costs())
+    }
+
     /// Returns transaction that passed only `Checks::Basic`.
     fn into_checked_basic(
         self,
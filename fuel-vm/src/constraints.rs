@@ -1,14 +1,17 @@
 //! Types to help constrain inputs to functions to only what is used.
-use std::ops::{
-    Deref,
-    DerefMut,
+use std::{
+    fmt,
+    ops::{
+        Deref,
+        DerefMut,
+    },
 };
 
 use fuel_asm::Word;
 use fuel_types::ContractId;
 
 use crate::{
-    consts::MEM_SIZE,
+    interpreter::Memory,
     prelude::{
         MemoryRange,
         RuntimeError,
@@ -39,12 +42,12 @@ impl<T> CheckedMemValue<T> {
     }
 
     /// Try to read a value of type `T` from memory.
-    pub fn try_from(self, memory: &[u8; MEM_SIZE]) -> Result<T, RuntimeError>
+    pub fn try_from(self, memory: &Memory) -> Result<T, RuntimeError>
     where
         T: for<'a> TryFrom<&'a [u8]>,
         RuntimeError: for<'a> From<<T as TryFrom<&'a [u8]>>::Error>,
     {
-        Ok(T::try_from(&memory[self.0.usizes()])?)
+        Ok(T::try_from(&memory.read(self.0.usizes()))?)
     }
 
     /// The start of the range.
@@ -59,12 +62,12 @@ impl<T> CheckedMemValue<T> {
 
     #[cfg(test)]
     /// Inspect a value of type `T` from memory.
-    pub fn inspect(self, memory: &[u8; MEM_SIZE]) -> T
+    pub fn inspect(self, memory: &Memory) -> T
     where
         T: std::io::Write + Default,
     {
         let mut t = T::default();
-        t.write_all(&memory[self.0.usizes()]).unwrap();
+        t.write_all(&memory.read(self.0.usizes())).unwrap();
         t
     }
 }
@@ -75,16 +78,14 @@ impl<const LEN: usize> CheckedMemConstLen<LEN> {
         Ok(Self(MemoryRange::new_const::<_, LEN>(address)?))
     }
 
-    /// Get the memory slice for this range.
-    pub fn read(self, memory: &[u8; MEM_SIZE]) -> &[u8; LEN] {
-        (&memory[self.0.usizes()]).try_into().expect(
-            "This is always correct as the address and LEN are checked on construction.",
-        )
+    /// Get a copy of the memory bytes of this range.
+    pub fn read(self, memory: &Memory) -> [u8; LEN] {
+        memory.read_bytes(self.0.start)
     }
 
     /// Get the mutable memory slice for this range.
-    pub fn write(self, memory: &mut [u8; MEM_SIZE]) -> &mut [u8; LEN] {
-        (&mut memory[self.0.usizes()]).try_into().expect(
+    pub fn write(self, memory: &mut Memory) -> &mut [u8; LEN] {
+        memory.write(self.0.usizes()).try_into().expect(
             "This is always correct as the address and LEN are checked on construction.",
         )
     }
@@ -99,6 +100,99 @@ pub struct InstructionLocation {
     pub offset: u64,
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for InstructionLocation {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        if let Some(ctx) = self.context {
+            serializer.serialize_str(&format!("{}:{}", ctx, self.offset))
+        } else {
+            serializer.serialize_str(&format!("{}", self.offset))
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+struct InstructionLocationVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Visitor<'de> for InstructionLocationVisitor {
+    type Value = InstructionLocation;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("A valid instruction location")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        use std::str::FromStr;
+
+        Ok(if let Some((l, r)) = value.split_once(':') {
+            let context = Some(ContractId::from_str(l).map_err(|_| {
+                serde::de::Error::custom("Invalid ContractId in InstructionLocation")
+            })?);
+            let offset = r.parse().map_err(serde::de::Error::custom)?;
+            InstructionLocation { context, offset }
+        } else {
+            let offset = value.parse().map_err(serde::de::Error::custom)?;
+            InstructionLocation {
+                context: None,
+                offset,
+            }
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for InstructionLocation {
+    fn deserialize<D>(deserializer: D) -> Result<InstructionLocation, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(InstructionLocationVisitor)
+    }
+}
+
+impl InstructionLocation {
+    /// New location from context and offset
+    pub const fn new(context: Option<ContractId>, offset: u64) -> Self {
+        Self { context, offset }
+    }
+
+    /// Context, i.e. current contract
+    pub const fn context(&self) -> Option<ContractId> {
+        self.context
+    }
+
+    /// Offset from the IS register
+    pub const fn offset(&self) -> u64 {
+        self.offset
+    }
+}
+
+impl fmt::Display for InstructionLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Location({}, offset={})",
+            self.context
+                .map(|contract_id| format!(
+                    "contract_id={}",
+                    contract_id
+                        .iter()
+                        .map(|b| format!("{b:02x?}"))
+                        .collect::<String>()
+                ),)
+                .unwrap_or_else(|| "script".to_string()),
+            self.offset
+        )
+    }
+}
+
 impl<const LEN: usize> Deref for CheckedMemConstLen<LEN> {
     type Target = MemoryRange;
 
@@ -35,9 +35,15 @@ pub const VM_MAX_RAM: u64 = 1024 * 1024 * FUEL_MAX_MEMORY_SIZE;
 /// Size of the VM memory, in bytes.
 pub const MEM_SIZE: usize = VM_MAX_RAM as usize;
 
+/// Size of the pages in which the VM memory is lazily allocated, in bytes.
+pub const MEM_PAGE_SIZE: usize = 16 * 1024;
+
 /// Maximum memory access size, in bytes.
 pub const MEM_MAX_ACCESS_SIZE: u64 = VM_MAX_RAM;
 
+/// Default maximum size of the debugger step history, in bytes.
+pub const DEBUG_HISTORY_MAX_BYTES: usize = MEM_SIZE;
+
 /// Tighter of the two bounds for VM_MAX_RAM and usize::MAX
 pub const MIN_VM_MAX_RAM_USIZE_MAX: u64 = if VM_MAX_RAM < usize::MAX as u64 {
     VM_MAX_RAM
@@ -0,0 +1,780 @@
+//! Debug Adapter Protocol server for the VM debugger
+//!
+//! [`DapServer`] speaks the [Debug Adapter Protocol](https://microsoft.github.io/debug-adapter-protocol/)
+//! over a pair of byte streams, usually the stdio of the `fuel-vm-dap` binary, to
+//! debug scripts and contracts from an editor.
+//!
+//! The program is provided by the arguments of the `launch` request:
+//! - `program`: path to a file with the script bytecode, or `bytecode`: the script
+//!   bytecode as a hex string.
+//! - `scriptData`: the script data as a hex string, empty by default.
+//! - `contracts`: contracts deployed before the execution, each of them provided as an
+//!   object with either a `program` or a `bytecode` field. They are added to the inputs
+//!   of the script.
+//! - `gasLimit`: gas limit of the script.
+//! - `stopOnEntry`: break on the first instruction of the script.
+//!
+//! The sources are the disassembled bytecode of the script and of the contracts, with
+//! one instruction per line, so line `n` of a source is the instruction at offset
+//! `n - 1`. Registers, call frames, memory and receipts are exposed as scopes of the
+//! stack frames.
+
+use std::{
+    collections::HashMap,
+    fs,
+    io::{
+        self,
+        BufRead,
+        Write,
+    },
+    ops::Range,
+};
+
+use fuel_asm::{
+    Instruction,
+    RegId,
+};
+use fuel_crypto::SecretKey;
+use fuel_tx::{
+    Contract,
+    Finalizable,
+    Input,
+    Output,
+    Script,
+    TransactionBuilder,
+    TxPointer,
+    UtxoId,
+};
+use fuel_types::{
+    Bytes32,
+    ContractId,
+    Salt,
+    Word,
+};
+use serde_json::{
+    json,
+    Value,
+};
+
+use crate::{
+    call::CallFrame,
+    checked_transaction::IntoChecked,
+    consts::*,
+    error::InterpreterError,
+    interpreter::Interpreter,
+    state::{
+        Breakpoint,
+        DebugEval,
+        ProgramState,
+    },
+    storage::{
+        InterpreterStorage,
+        MemoryStorage,
+    },
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Identifier of the single thread of the VM.
+const THREAD_ID: u64 = 1;
+
+/// Gas limit of the script if the launch request doesn't provide one.
+const DEFAULT_GAS_LIMIT: Word = 1_000_000;
+
+/// Amount of bytes displayed by each variable of the memory scope.
+const MEMORY_ROW_SIZE: usize = 32;
+
+/// Variables reference of the current registers.
+const REGISTERS_REFERENCE: u64 = 1;
+/// Variables reference of the memory.
+const MEMORY_REFERENCE: u64 = 2;
+/// Variables reference of the receipts.
+const RECEIPTS_REFERENCE: u64 = 3;
+/// Base variables reference of the registers saved in the call frames.
+const FRAME_REGISTERS_REFERENCE: u64 = 1000;
+/// Base variables reference of the call frames.
+const CALL_FRAME_REFERENCE: u64 = 2000;
+
+/// Names of the reserved registers, by index.
+const REGISTER_NAMES: [&str; 16] = [
+    "$zero", "$one", "$of", "$pc", "$ssp", "$sp", "$fp", "$hp", "$err", "$ggas", "$cgas",
+    "$bal", "$is", "$ret", "$retl", "$flag",
+];
+
+/// A program to debug, provided by the `launch` request.
+struct Session {
+    /// Bytecode of the script.
+    script: Vec<u8>,
+    /// Deployed contracts with their bytecode.
+    contracts: Vec<(ContractId, Vec<u8>)>,
+    stop_on_entry: bool,
+    /// Script transaction, taken once the execution starts.
+    tx: Option<Script>,
+}
+
+/// Debug Adapter Protocol server.
+///
+/// Required features:
+/// - `dap`
+pub struct DapServer<R, W> {
+    reader: R,
+    writer: W,
+    seq: u64,
+    vm: Interpreter<MemoryStorage, Script>,
+    session: Option<Session>,
+    breakpoints: HashMap<ContractId, Vec<Breakpoint>>,
+    stopped: bool,
+}
+
+impl<R, W> DapServer<R, W>
+where
+    R: BufRead,
+    W: Write,
+{
+    /// Create a new server reading the requests from `reader` and writing the
+    /// responses and events to `writer`.
+    pub fn new(reader: R, writer: W) -> Self {
+        Self {
+            reader,
+            writer,
+            seq: 0,
+            vm: Interpreter::with_memory_storage(),
+            session: None,
+            breakpoints: HashMap::new(),
+            stopped: false,
+        }
+    }
+
+    /// Serve the requests until the client disconnects or the input is closed.
+    pub fn run(mut self) -> io::Result<()> {
+        while let Some(request) = self.read_message()? {
+            if !self.handle(&request)? {
+                break
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Handle a request, returning `false` once the session is over.
+    fn handle(&mut self, request: &Value) -> io::Result<bool> {
+        let command = request["command"].as_str().unwrap_or_default();
+        let arguments = &request["arguments"];
+
+        let result = match command {
+            "initialize" => Ok(json!({
+                "supportsConfigurationDoneRequest": true,
+            })),
+            "launch" => self.launch(arguments),
+            "setBreakpoints" => self.set_breakpoints(arguments),
+            "setExceptionBreakpoints" | "configurationDone" | "disconnect" => {
+                Ok(Value::Null)
+            }
+            "threads" => Ok(json!({
+                "threads": [{ "id": THREAD_ID, "name": "main" }],
+            })),
+            "stackTrace" => self.stack_trace(),
+            "scopes" => self.scopes(arguments),
+            "variables" => self.variables(arguments),
+            "source" => self.source(arguments),
+            "continue" => self
+                .ensure_stopped()
+                .map(|_| json!({ "allThreadsContinued": true })),
+            "next" | "stepIn" => self.ensure_stopped().map(|_| Value::Null),
+            _ => Err(format!("Unsupported request `{command}`")),
+        };
+        let success = result.is_ok();
+        self.respond(request, result)?;
+
+        if !success {
+            return Ok(true)
+        }
+
+        match command {
+            "launch" => self.send_event("initialized", Value::Null)?,
+            "configurationDone" => self.start()?,
+            "continue" => self.resume(false)?,
+            "next" | "stepIn" => self.resume(true)?,
+            "disconnect" => return Ok(false),
+            _ => (),
+        }
+
+        Ok(true)
+    }
+
+    fn launch(&mut self, arguments: &Value) -> Result<Value, String> {
+        if self.session.is_some() {
+            return Err("The program is already launched".into())
+        }
+
+        let script = program_bytes(arguments)?;
+        let script_data = match arguments["scriptData"].as_str() {
+            Some(data) => decode_hex(data)?,
+            None => vec![],
+        };
+        let contracts = match arguments["contracts"].as_array() {
+            Some(contracts) => contracts
+                .iter()
+                .map(program_bytes)
+                .collect::<Result<Vec<_>, _>>()?,
+            None => vec![],
+        };
+
+        let fee_secret = SecretKey::try_from(Bytes32::new([1; 32]))
+            .expect("The fee secret key is valid");
+        let mut builder = TransactionBuilder::script(script.clone(), script_data);
+        builder
+            .gas_limit(arguments["gasLimit"].as_u64().unwrap_or(DEFAULT_GAS_LIMIT))
+            .add_unsigned_coin_input(
+                fee_secret,
+                Default::default(),
+                0,
+                Default::default(),
+                Default::default(),
+                Default::default(),
+            );
+
+        let mut deployed = Vec::with_capacity(contracts.len());
+        for code in contracts {
+            let contract = Contract::from(code.as_slice());
+            let salt = Salt::zeroed();
+            let root = contract.root();
+            let state_root = Contract::default_state_root();
+            let id = contract.id(&salt, &root, &state_root);
+
+            self.vm
+                .as_mut()
+                .deploy_contract_with_id(&salt, &[], &contract, &root, &id)
+                .map_err(|e| e.to_string())?;
+
+            let index = builder.inputs().len() as u8;
+            builder
+                .add_input(Input::contract(
+                    UtxoId::new(Bytes32::new(*id), 0),
+                    Bytes32::zeroed(),
+                    Bytes32::zeroed(),
+                    TxPointer::default(),
+                    id,
+                ))
+                .add_output(Output::contract(
+                    index,
+                    Bytes32::zeroed(),
+                    Bytes32::zeroed(),
+                ));
+            deployed.push((id, code));
+        }
+
+        self.session = Some(Session {
+            script,
+            contracts: deployed,
+            stop_on_entry: arguments["stopOnEntry"].as_bool().unwrap_or(false),
+            tx: Some(builder.finalize()),
+        });
+
+        Ok(Value::Null)
+    }
+
+    fn set_breakpoints(&mut self, arguments: &Value) -> Result<Value, String> {
+        let source = &arguments["source"];
+        let contract = match source["sourceReference"].as_u64() {
+            Some(reference) => self
+                .source_contract(reference)
+                .ok_or_else(|| format!("Unknown source reference {reference}"))?,
+            None => source["name"]
+                .as_str()
+                .and_then(|name| name.parse().ok())
+                .unwrap_or_default(),
+        };
+
+        for breakpoint in self.breakpoints.remove(&contract).unwrap_or_default() {
+            self.vm.remove_breakpoint(&breakpoint);
+        }
+
+        let lines: Vec<Word> = arguments["breakpoints"]
+            .as_array()
+            .map(|breakpoints| {
+                breakpoints
+                    .iter()
+                    .filter_map(|b| b["line"].as_u64())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let breakpoints: Vec<_> = lines
+            .iter()
+            .map(|line| Breakpoint::new(contract, line.saturating_sub(1)))
+            .collect();
+        for breakpoint in &breakpoints {
+            self.vm.set_breakpoint(*breakpoint);
+        }
+        self.breakpoints.insert(contract, breakpoints);
+
+        Ok(json!({
+            "breakpoints": lines
+                .iter()
+                .map(|line| json!({ "verified": true, "line": line }))
+                .collect::<Vec<_>>(),
+        }))
+    }
+
+    fn stack_trace(&self) -> Result<Value, String> {
+        self.ensure_stopped()?;
+
+        let frames = self.vm.call_stack();
+        let mut stack = vec![self.stack_frame(
+            0,
+            frames.last().map(CallFrame::to),
+            self.vm.registers(),
+        )];
+
+        for (index, frame) in frames.iter().enumerate().rev() {
+            let caller = index.checked_sub(1).map(|caller| frames[caller].to());
+            stack.push(self.stack_frame(index as u64 + 1, caller, frame.registers()));
+        }
+
+        Ok(json!({
+            "totalFrames": stack.len(),
+            "stackFrames": stack,
+        }))
+    }
+
+    /// Stack frame `id`, executing in `contract` with `registers`.
+    fn stack_frame(
+        &self,
+        id: u64,
+        contract: Option<&ContractId>,
+        registers: &[Word],
+    ) -> Value {
+        let offset = registers[RegId::PC].saturating_sub(registers[RegId::IS])
+            / Instruction::SIZE as Word;
+        let name = match contract {
+            Some(contract) => format!("{contract:#x}"),
+            None => "script".into(),
+        };
+
+        json!({
+            "id": id,
+            "name": name,
+            "source": self.source_descriptor(contract),
+            "line": offset + 1,
+            "column": 1,
+        })
+    }
+
+    fn scopes(&self, arguments: &Value) -> Result<Value, String> {
+        self.ensure_stopped()?;
+
+        let frames = self.vm.call_stack().len() as u64;
+        let frame = arguments["frameId"].as_u64().unwrap_or_default();
+
+        let scopes = match frame {
+            0 => {
+                let mut scopes = vec![scope("Registers", REGISTERS_REFERENCE, None)];
+                if frames > 0 {
+                    scopes.push(scope(
+                        "Call frame",
+                        CALL_FRAME_REFERENCE + frames - 1,
+                        None,
+                    ));
+                }
+                scopes.push(scope(
+                    "Memory",
+                    MEMORY_REFERENCE,
+                    Some(self.memory_rows().len()),
+                ));
+                scopes.push(scope(
+                    "Receipts",
+                    RECEIPTS_REFERENCE,
+                    Some(self.vm.receipts().len()),
+                ));
+                scopes
+            }
+            frame if frame <= frames => {
+                let index = frame - 1;
+                let mut scopes =
+                    vec![scope("Registers", FRAME_REGISTERS_REFERENCE + index, None)];
+                if index > 0 {
+                    scopes.push(scope(
+                        "Call frame",
+                        CALL_FRAME_REFERENCE + index - 1,
+                        None,
+                    ));
+                }
+                scopes
+            }
+            _ => return Err(format!("Unknown frame {frame}")),
+        };
+
+        Ok(json!({ "scopes": scopes }))
+    }
+
+    fn variables(&self, arguments: &Value) -> Result<Value, String> {
+        self.ensure_stopped()?;
+
+        let reference = arguments["variablesReference"].as_u64().unwrap_or_default();
+        let frames = self.vm.call_stack();
+        let frame = |base: u64| {
+            reference
+                .checked_sub(base)
+                .and_then(|index| frames.get(index as usize))
+        };
+
+        let variables = match reference {
+            REGISTERS_REFERENCE => register_variables(self.vm.registers()),
+            MEMORY_REFERENCE => {
+                let rows = self.memory_rows();
+                let start = arguments["start"].as_u64().unwrap_or_default() as usize;
+                let count = arguments["count"]
+                    .as_u64()
+                    .map_or(rows.len(), |c| c as usize);
+
+                rows.into_iter()
+                    .skip(start)
+                    .take(count)
+                    .map(|row| {
+                        variable(
+                            format!("{:#010x}", row.start),
+                            encode_hex(&self.vm.paged_memory().read(row)),
+                        )
+                    })
+                    .collect()
+            }
+            RECEIPTS_REFERENCE => self
+                .vm
+                .receipts()
+                .iter()
+                .enumerate()
+                .map(|(index, receipt)| {
+                    let receipt = serde_json::to_string(receipt)
+                        .unwrap_or_else(|_| format!("{receipt:?}"));
+                    variable(index.to_string(), receipt)
+                })
+                .collect(),
+            _ => {
+                if let Some(frame) = frame(CALL_FRAME_REFERENCE) {
+                    call_frame_variables(frame)
+                } else if let Some(frame) = frame(FRAME_REGISTERS_REFERENCE) {
+                    register_variables(frame.registers())
+                } else {
+                    return Err(format!("Unknown variables reference {reference}"))
+                }
+            }
+        };
+
+        Ok(json!({ "variables": variables }))
+    }
+
+    fn source(&self, arguments: &Value) -> Result<Value, String> {
+        let reference = arguments["sourceReference"]
+            .as_u64()
+            .or_else(|| arguments["source"]["sourceReference"].as_u64())
+            .unwrap_or_default();
+
+        let session = self.session.as_ref().ok_or("The program isn't launched")?;
+        let bytecode = match reference {
+            1 => &session.script,
+            reference => reference
+                .checked_sub(2)
+                .and_then(|index| session.contracts.get(index as usize))
+                .map(|(_, code)| code)
+                .ok_or_else(|| format!("Unknown source reference {reference}"))?,
+        };
+
+        Ok(json!({ "content": disassemble(bytecode) }))
+    }
+
+    /// Start the execution of the launched program.
+    fn start(&mut self) -> io::Result<()> {
+        let (tx, stop_on_entry) = match self.session.as_mut() {
+            Some(session) => (session.tx.take(), session.stop_on_entry),
+            None => return Ok(()),
+        };
+        let tx = match tx {
+            Some(tx) => tx,
+            None => return Ok(()),
+        };
+
+        let height = self.vm.as_ref().block_height().unwrap_or_default();
+        let result = tx
+            .into_checked(height, self.vm.params(), self.vm.gas_costs())
+            .map_err(InterpreterError::from)
+            .and_then(|tx| {
+                self.vm.set_single_stepping(stop_on_entry);
+                let state = self.vm.transact(tx).map(ProgramState::from);
+                self.vm.set_single_stepping(false);
+                state
+            });
+
+        self.report(result, if stop_on_entry { "entry" } else { "breakpoint" })
+    }
+
+    /// Resume the execution, stopping after the next instruction if `step` is set.
+    fn resume(&mut self, step: bool) -> io::Result<()> {
+        self.vm.set_single_stepping(step);
+        let result = self.vm.resume();
+        self.vm.set_single_stepping(false);
+
+        self.report(result, if step { "step" } else { "breakpoint" })
+    }
+
+    /// Notify the client of the outcome of an execution.
+    fn report(
+        &mut self,
+        result: Result<ProgramState, InterpreterError>,
+        reason: &str,
+    ) -> io::Result<()> {
+        let state = match result {
+            Ok(state) => state,
+            Err(e) => {
+                self.stopped = false;
+                self.send_event(
+                    "output",
+                    json!({ "category": "stderr", "output": format!("{e}\n") }),
+                )?;
+                self.send_event("exited", json!({ "exitCode": 1 }))?;
+                return self.send_event("terminated", Value::Null)
+            }
+        };
+
+        let exit_code = match state {
+            ProgramState::RunProgram(debug) | ProgramState::VerifyPredicate(debug) => {
+                self.stopped = true;
+                let reason = match debug {
+                    DebugEval::Watchpoint { .. } => "data breakpoint",
+                    _ => reason,
+                };
+                return self.send_event(
+                    "stopped",
+                    json!({
+                        "reason": reason,
+                        "threadId": THREAD_ID,
+                        "allThreadsStopped": true,
+                    }),
+                )
+            }
+            ProgramState::Return(_) | ProgramState::ReturnData(_) => 0,
+            ProgramState::Revert(_) => 1,
+        };
+
+        self.stopped = false;
+        self.send_event(
+            "output",
+            json!({ "category": "console", "output": format!("{state:?}\n") }),
+        )?;
+        self.send_event("exited", json!({ "exitCode": exit_code }))?;
+        self.send_event("terminated", Value::Null)
+    }
+
+    fn ensure_stopped(&self) -> Result<(), String> {
+        if self.stopped {
+            Ok(())
+        } else {
+            Err("The program isn't stopped".into())
+        }
+    }
+
+    /// Contract of the source with the reference, the default id being the script.
+    fn source_contract(&self, reference: u64) -> Option<ContractId> {
+        let session = self.session.as_ref()?;
+
+        match reference {
+            1 => Some(ContractId::default()),
+            reference => reference
+                .checked_sub(2)
+                .and_then(|index| session.contracts.get(index as usize))
+                .map(|(id, _)| *id),
+        }
+    }
+
+    /// Source of the code of the contract, or of the script if `None`.
+    fn source_descriptor(&self, contract: Option<&ContractId>) -> Value {
+        let contracts = self
+            .session
+            .as_ref()
+            .map(|session| session.contracts.as_slice())
+            .unwrap_or_default();
+
+        match contract {
+            None => json!({ "name": "script", "sourceReference": 1 }),
+            Some(contract) => {
+                let name = format!("{contract:#x}");
+                match contracts.iter().position(|(id, _)| id == contract) {
+                    Some(index) => {
+                        json!({ "name": name, "sourceReference": index + 2 })
+                    }
+                    None => json!({ "name": name }),
+                }
+            }
+        }
+    }
+
+    /// Ranges of the memory scope: the stack followed by the heap.
+    fn memory_rows(&self) -> Vec<Range<usize>> {
+        let registers = self.vm.registers();
+        let sp = registers[RegId::SP] as usize;
+        let hp = (registers[RegId::HP] as usize).min(MEM_SIZE);
+
+        let rows = |range: Range<usize>| {
+            range
+                .clone()
+                .step_by(MEMORY_ROW_SIZE)
+                .map(move |start| start..(start + MEMORY_ROW_SIZE).min(range.end))
+        };
+
+        rows(0..sp.min(hp)).chain(rows(hp..MEM_SIZE)).collect()
+    }
+
+    fn respond(
+        &mut self,
+        request: &Value,
+        result: Result<Value, String>,
+    ) -> io::Result<()> {
+        let mut response = json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "command": request["command"],
+            "success": result.is_ok(),
+        });
+
+        match result {
+            Ok(Value::Null) => (),
+            Ok(body) => response["body"] = body,
+            Err(message) => response["message"] = message.into(),
+        }
+
+        self.send(response)
+    }
+
+    fn send_event(&mut self, event: &str, body: Value) -> io::Result<()> {
+        let mut message = json!({ "type": "event", "event": event });
+        if !body.is_null() {
+            message["body"] = body;
+        }
+
+        self.send(message)
+    }
+
+    /// Write a message with its `Content-Length` header.
+    fn send(&mut self, mut message: Value) -> io::Result<()> {
+        self.seq += 1;
+        message["seq"] = self.seq.into();
+
+        let body = serde_json::to_vec(&message)?;
+        write!(self.writer, "Content-Length: {}\r\n\r\n", body.len())?;
+        self.writer.write_all(&body)?;
+        self.writer.flush()
+    }
+
+    /// Read the next message, or `None` if the input is closed.
+    fn read_message(&mut self) -> io::Result<Option<Value>> {
+        let mut length = None;
+
+        loop {
+            let mut line = String::new();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Ok(None)
+            }
+
+            let line = line.trim_end();
+            if line.is_empty() {
+                if length.is_some() {
+                    break
+                }
+            } else if let Some(value) = line.strip_prefix("Content-Length:") {
+                length = Some(value.trim().parse::<usize>().map_err(|e| {
+                    io::Error::new(io::ErrorKind::InvalidData, e.to_string())
+                })?);
+            }
+        }
+
+        let mut body = vec![0; length.unwrap_or_default()];
+        self.reader.read_exact(&mut body)?;
+
+        serde_json::from_slice(&body).map(Some).map_err(Into::into)
+    }
+}
+
+fn scope(name: &str, reference: u64, indexed: Option<usize>) -> Value {
+    let mut scope = json!({
+        "name": name,
+        "variablesReference": reference,
+        "expensive": false,
+    });
+    if let Some(indexed) = indexed {
+        scope["indexedVariables"] = indexed.into();
+    }
+
+    scope
+}
+
+fn variable(name: String, value: String) -> Value {
+    json!({ "name": name, "value": value, "variablesReference": 0 })
+}
+
+fn register_variables(registers: &[Word]) -> Vec<Value> {
+    registers
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let name = REGISTER_NAMES
+                .get(index)
+                .map(|name| name.to_string())
+                .unwrap_or_else(|| format!("$r{index}"));
+            variable(name, value.to_string())
+        })
+        .collect()
+}
+
+fn call_frame_variables(frame: &CallFrame) -> Vec<Value> {
+    vec![
+        variable("to".into(), format!("{:#x}", frame.to())),
+        variable("asset_id".into(), format!("{:#x}", frame.asset_id())),
+        variable("code_size".into(), frame.code_size().to_string()),
+        variable("a".into(), frame.a().to_string()),
+        variable("b".into(), frame.b().to_string()),
+    ]
+}
+
+/// Bytecode of the program described by the `program` or `bytecode` field.
+fn program_bytes(arguments: &Value) -> Result<Vec<u8>, String> {
+    if let Some(path) = arguments["program"].as_str() {
+        fs::read(path).map_err(|e| format!("Failed to read `{path}`: {e}"))
+    } else if let Some(bytecode) = arguments["bytecode"].as_str() {
+        decode_hex(bytecode)
+    } else {
+        Err("Missing `program` or `bytecode`".into())
+    }
+}
+
+/// One instruction per line.
+fn disassemble(bytecode: &[u8]) -> String {
+    bytecode
+        .chunks(Instruction::SIZE)
+        .map(|chunk| match <[u8; Instruction::SIZE]>::try_from(chunk) {
+            Ok(bytes) => match Instruction::try_from(bytes) {
+                Ok(instruction) => format!("{instruction:?}\n"),
+                Err(_) => format!("<invalid {}>\n", encode_hex(chunk)),
+            },
+            Err(_) => format!("<data {}>\n", encode_hex(chunk)),
+        })
+        .collect()
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn decode_hex(hex: &str) -> Result<Vec<u8>, String> {
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    hex.as_bytes()
+        .chunks(2)
+        .map(|byte| {
+            core::str::from_utf8(byte)
+                .ok()
+                .filter(|byte| byte.len() == 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                .ok_or_else(|| format!("Invalid hex string `{hex}`"))
+        })
+        .collect()
+}
@@ -0,0 +1,377 @@
+use super::*;
+
+use crate::{
+    prelude::*,
+    script_with_data_offset,
+};
+use fuel_asm::op;
+use serde_json::json;
+
+/// Frame the requests as the client would, numbering them in order.
+fn requests(requests: &[(&str, Value)]) -> Vec<u8> {
+    requests
+        .iter()
+        .enumerate()
+        .flat_map(|(seq, (command, arguments))| {
+            let body = json!({
+                "seq": seq + 1,
+                "type": "request",
+                "command": command,
+                "arguments": arguments,
+            })
+            .to_string();
+
+            format!("Content-Length: {}\r\n\r\n{body}", body.len()).into_bytes()
+        })
+        .collect()
+}
+
+/// Run the scripted session, returning the messages sent by the server.
+fn run_session(session: &[(&str, Value)]) -> Vec<Value> {
+    let input = requests(session);
+    let mut output = vec![];
+
+    DapServer::new(input.as_slice(), &mut output)
+        .run()
+        .expect("Failed to run the session");
+
+    let mut reader = output.as_slice();
+    let mut messages = vec![];
+    while let Some(message) = DapServer::new(&mut reader, io::sink())
+        .read_message()
+        .expect("Invalid message")
+    {
+        messages.push(message);
+    }
+
+    messages
+}
+
+fn bytecode(program: Vec<Instruction>) -> String {
+    encode_hex(&program.into_iter().collect::<Vec<u8>>())
+}
+
+/// Responses to the requests with the command, in order.
+fn responses<'a>(messages: &'a [Value], command: &str) -> Vec<&'a Value> {
+    messages
+        .iter()
+        .filter(|m| m["type"] == "response" && m["command"] == command)
+        .collect()
+}
+
+/// Events with the name, in order.
+fn events<'a>(messages: &'a [Value], event: &str) -> Vec<&'a Value> {
+    messages
+        .iter()
+        .filter(|m| m["type"] == "event" && m["event"] == event)
+        .collect()
+}
+
+/// Value of the variable with the name, in a `variables` response.
+fn variable_value<'a>(response: &'a Value, name: &str) -> &'a str {
+    response["body"]["variables"]
+        .as_array()
+        .expect("Expected variables")
+        .iter()
+        .find(|v| v["name"] == name)
+        .and_then(|v| v["value"].as_str())
+        .expect("Expected variable")
+}
+
+#[test]
+fn dap_breakpoints_and_variables() {
+    let script = bytecode(vec![
+        op::movi(0x10, 5),
+        op::movi(0x11, 7),
+        op::add(0x12, 0x10, 0x11),
+        op::log(0x12, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::ret(0x12),
+    ]);
+
+    let messages = run_session(&[
+        ("initialize", json!({ "adapterID": "fuel-vm" })),
+        ("launch", json!({ "bytecode": script })),
+        (
+            "setBreakpoints",
+            json!({
+                "source": { "name": "script", "sourceReference": 1 },
+                "breakpoints": [{ "line": 3 }, { "line": 5 }],
+            }),
+        ),
+        ("configurationDone", json!({})),
+        ("threads", json!({})),
+        ("stackTrace", json!({ "threadId": THREAD_ID })),
+        ("scopes", json!({ "frameId": 0 })),
+        (
+            "variables",
+            json!({ "variablesReference": REGISTERS_REFERENCE }),
+        ),
+        ("continue", json!({ "threadId": THREAD_ID })),
+        (
+            "variables",
+            json!({ "variablesReference": REGISTERS_REFERENCE }),
+        ),
+        (
+            "variables",
+            json!({ "variablesReference": RECEIPTS_REFERENCE }),
+        ),
+        ("continue", json!({ "threadId": THREAD_ID })),
+        ("disconnect", json!({})),
+    ]);
+
+    let failed: Vec<_> = messages
+        .iter()
+        .filter(|m| m["type"] == "response" && m["success"] != true)
+        .collect();
+    assert!(failed.is_empty(), "{failed:?}");
+    assert_eq!(events(&messages, "initialized").len(), 1);
+
+    let breakpoints = &responses(&messages, "setBreakpoints")[0]["body"]["breakpoints"];
+    assert_eq!(breakpoints[0]["verified"], true);
+    assert_eq!(breakpoints[1]["line"], 5);
+
+    let stopped = events(&messages, "stopped");
+    assert_eq!(stopped.len(), 2);
+    assert_eq!(stopped[0]["body"]["reason"], "breakpoint");
+    assert_eq!(stopped[0]["body"]["threadId"], THREAD_ID);
+
+    let frames = &responses(&messages, "stackTrace")[0]["body"]["stackFrames"];
+    assert_eq!(frames.as_array().map(Vec::len), Some(1));
+    assert_eq!(frames[0]["name"], "script");
+    assert_eq!(frames[0]["line"], 3);
+    assert_eq!(frames[0]["source"]["sourceReference"], 1);
+
+    let scopes = &responses(&messages, "scopes")[0]["body"]["scopes"];
+    let names: Vec<_> = scopes
+        .as_array()
+        .expect("Expected scopes")
+        .iter()
+        .map(|s| s["name"].as_str().unwrap_or_default())
+        .collect();
+    assert_eq!(names, vec!["Registers", "Memory", "Receipts"]);
+
+    let variables = responses(&messages, "variables");
+    assert_eq!(variable_value(variables[0], "$r16"), "5");
+    assert_eq!(variable_value(variables[0], "$r17"), "7");
+    assert_eq!(variable_value(variables[0], "$r18"), "0");
+    assert_eq!(variable_value(variables[0], "$pc"), {
+        let params = ConsensusParameters::default();
+        let is = params.tx_offset()
+            + <Script as fuel_tx::field::Script>::script_offset_static();
+        (is + 2 * Instruction::SIZE).to_string()
+    });
+    assert_eq!(variable_value(variables[1], "$r18"), "12");
+
+    let receipts = variables[2]["body"]["variables"]
+        .as_array()
+        .expect("Expected receipts");
+    let logged = receipts.iter().any(|receipt| {
+        let receipt: Receipt =
+            serde_json::from_str(receipt["value"].as_str().expect("Expected receipt"))
+                .expect("Invalid receipt");
+        matches!(receipt, Receipt::Log { ra: 12, .. })
+    });
+    assert!(logged);
+
+    let exited = events(&messages, "exited");
+    assert_eq!(exited.len(), 1);
+    assert_eq!(exited[0]["body"]["exitCode"], 0);
+    assert_eq!(events(&messages, "terminated").len(), 1);
+
+    let seqs: Vec<_> = messages.iter().filter_map(|m| m["seq"].as_u64()).collect();
+    assert_eq!(seqs, (1..=messages.len() as u64).collect::<Vec<_>>());
+}
+
+#[test]
+fn dap_single_stepping() {
+    let script = bytecode(vec![
+        op::movi(0x10, 1),
+        op::movi(0x11, 2),
+        op::movi(0x12, 3),
+        op::ret(RegId::ONE),
+    ]);
+
+    let messages = run_session(&[
+        ("initialize", json!({})),
+        ("launch", json!({ "bytecode": script, "stopOnEntry": true })),
+        ("configurationDone", json!({})),
+        ("stackTrace", json!({ "threadId": THREAD_ID })),
+        ("next", json!({ "threadId": THREAD_ID })),
+        ("stackTrace", json!({ "threadId": THREAD_ID })),
+        ("stepIn", json!({ "threadId": THREAD_ID })),
+        ("stackTrace", json!({ "threadId": THREAD_ID })),
+        ("continue", json!({ "threadId": THREAD_ID })),
+        ("disconnect", json!({})),
+    ]);
+
+    let reasons: Vec<_> = events(&messages, "stopped")
+        .iter()
+        .map(|e| e["body"]["reason"].clone())
+        .collect();
+    assert_eq!(reasons, vec!["entry", "step", "step"]);
+
+    let lines: Vec<_> = responses(&messages, "stackTrace")
+        .iter()
+        .map(|r| r["body"]["stackFrames"][0]["line"].clone())
+        .collect();
+    assert_eq!(lines, vec![1, 2, 3]);
+
+    // Stepping doesn't leave the single-stepping mode enabled
+    assert_eq!(events(&messages, "exited")[0]["body"]["exitCode"], 0);
+}
+
+#[test]
+fn dap_contract_call_frames() {
+    let contract = [op::movi(0x10, 42), op::ret(0x10)];
+    let contract_code: Vec<u8> = contract.iter().copied().collect();
+    let contract_id = {
+        let contract = Contract::from(contract_code.as_slice());
+        contract.id(
+            &Salt::zeroed(),
+            &contract.root(),
+            &Contract::default_state_root(),
+        )
+    };
+
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset as Immediate18),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        ConsensusParameters::default().tx_offset()
+    );
+    let script_data: Vec<u8> = contract_id
+        .iter()
+        .copied()
+        .chain((0 as Word).to_be_bytes())
+        .chain((0 as Word).to_be_bytes())
+        .collect();
+
+    let messages = run_session(&[
+        ("initialize", json!({})),
+        (
+            "launch",
+            json!({
+                "bytecode": bytecode(script),
+                "scriptData": encode_hex(&script_data),
+                "contracts": [{ "bytecode": encode_hex(&contract_code) }],
+            }),
+        ),
+        (
+            "setBreakpoints",
+            json!({
+                "source": { "sourceReference": 2 },
+                "breakpoints": [{ "line": 2 }],
+            }),
+        ),
+        ("configurationDone", json!({})),
+        ("stackTrace", json!({ "threadId": THREAD_ID })),
+        ("scopes", json!({ "frameId": 0 })),
+        ("scopes", json!({ "frameId": 1 })),
+        (
+            "variables",
+            json!({ "variablesReference": CALL_FRAME_REFERENCE }),
+        ),
+        (
+            "variables",
+            json!({ "variablesReference": FRAME_REGISTERS_REFERENCE }),
+        ),
+        (
+            "variables",
+            json!({ "variablesReference": REGISTERS_REFERENCE }),
+        ),
+        ("source", json!({ "sourceReference": 2 })),
+        ("continue", json!({ "threadId": THREAD_ID })),
+        ("disconnect", json!({})),
+    ]);
+
+    let stopped = events(&messages, "stopped");
+    assert_eq!(stopped.len(), 1);
+
+    let frames = &responses(&messages, "stackTrace")[0]["body"]["stackFrames"];
+    assert_eq!(frames[0]["name"], format!("{contract_id:#x}"));
+    assert_eq!(frames[0]["line"], 2);
+    assert_eq!(frames[0]["source"]["sourceReference"], 2);
+    assert_eq!(frames[1]["name"], "script");
+    assert_eq!(frames[1]["line"], 2);
+
+    let scopes = responses(&messages, "scopes");
+    assert_eq!(scopes[0]["body"]["scopes"][1]["name"], "Call frame");
+    assert_eq!(scopes[1]["body"]["scopes"][0]["name"], "Registers");
+    assert_eq!(
+        scopes[1]["body"]["scopes"].as_array().map(Vec::len),
+        Some(1)
+    );
+
+    let variables = responses(&messages, "variables");
+    assert_eq!(
+        variable_value(variables[0], "to"),
+        format!("{contract_id:#x}")
+    );
+    // The caller registers are saved at the `CALL` instruction
+    assert_eq!(variable_value(variables[1], "$r16"), {
+        let (_, data_offset) = script_with_data_offset!(
+            data_offset,
+            vec![
+                op::movi(0x10, data_offset as Immediate18),
+                op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+                op::ret(RegId::ONE),
+            ],
+            ConsensusParameters::default().tx_offset()
+        );
+        data_offset.to_string()
+    });
+    assert_eq!(variable_value(variables[2], "$r16"), "42");
+
+    let source = responses(&messages, "source")[0]["body"]["content"]
+        .as_str()
+        .expect("Expected source")
+        .to_owned();
+    let lines: Vec<_> = source.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("MOVI"));
+    assert!(lines[1].starts_with("RET"));
+
+    assert_eq!(events(&messages, "exited")[0]["body"]["exitCode"], 0);
+}
+
+#[test]
+fn dap_rejects_invalid_requests() {
+    let messages = run_session(&[
+        ("stackTrace", json!({ "threadId": THREAD_ID })),
+        ("launch", json!({})),
+        ("launch", json!({ "bytecode": "0xzz" })),
+        ("stepOut", json!({ "threadId": THREAD_ID })),
+        (
+            "launch",
+            json!({ "bytecode": bytecode(vec![op::rvrt(RegId::ONE)]) }),
+        ),
+        ("configurationDone", json!({})),
+        ("continue", json!({ "threadId": THREAD_ID })),
+        ("disconnect", json!({})),
+    ]);
+
+    let success: Vec<_> = messages
+        .iter()
+        .filter(|m| m["type"] == "response")
+        .map(|m| (m["command"].clone(), m["success"].clone()))
+        .collect();
+    assert_eq!(
+        success,
+        vec![
+            (json!("stackTrace"), json!(false)),
+            (json!("launch"), json!(false)),
+            (json!("launch"), json!(false)),
+            (json!("stepOut"), json!(false)),
+            (json!("launch"), json!(true)),
+            (json!("configurationDone"), json!(true)),
+            (json!("continue"), json!(false)),
+            (json!("disconnect"), json!(true)),
+        ]
+    );
+
+    // The reverted script exits with a failure
+    assert_eq!(events(&messages, "exited")[0]["body"]["exitCode"], 1);
+}
@@ -37,6 +37,13 @@ pub enum InterpreterError {
     /// state transitions.
     #[error("Execution error")]
     NoTransactionInitialized,
+    /// The script transaction doesn't succeed even with the maximal gas limit,
+    /// so no gas limit can be estimated for it.
+    #[error("The transaction fails with the maximal gas limit")]
+    GasEstimationFailed,
+    /// There is no yielded execution to resume.
+    #[error("No yielded execution to resume")]
+    NotYielded,
     /// I/O and OS related errors.
     #[error("Unrecoverable error: {0}")]
     Io(#[from] io::Error),
@@ -116,6 +123,8 @@ impl PartialEq for InterpreterError {
             (Self::CheckError(s), Self::CheckError(o)) => s == o,
             (Self::PredicateFailure, Self::PredicateFailure) => true,
             (Self::NoTransactionInitialized, Self::NoTransactionInitialized) => true,
+            (Self::GasEstimationFailed, Self::GasEstimationFailed) => true,
+            (Self::NotYielded, Self::NotYielded) => true,
             (Self::Io(s), Self::Io(o)) => s.kind() == o.kind(),
 
             #[cfg(feature = "debug")]
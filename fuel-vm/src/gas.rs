@@ -15,6 +15,12 @@ use fuel_types::Word;
 /// show what version of `fuel-core` was used
 /// to generate the costs.
 mod default_gas_costs;
+mod schedule;
+
+pub use schedule::{
+    Schedule,
+    ScheduleEntry,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 /// Gas unit cost that embeds a unit price and operations count.
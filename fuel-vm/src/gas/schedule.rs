@@ -0,0 +1,121 @@
+//! Gas costs and consensus parameters activated at block heights.
+
+use fuel_tx::ConsensusParameters;
+use fuel_types::BlockHeight;
+
+use super::{
+    GasCosts,
+    GasCostsValues,
+};
+
+/// Gas costs and consensus parameters in force from a block height.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleEntry {
+    activation: BlockHeight,
+    gas_costs: GasCosts,
+    params: ConsensusParameters,
+}
+
+impl ScheduleEntry {
+    /// First block height of the entry.
+    pub const fn activation(&self) -> BlockHeight {
+        self.activation
+    }
+
+    /// Gas costs of the entry.
+    pub const fn gas_costs(&self) -> &GasCosts {
+        &self.gas_costs
+    }
+
+    /// Consensus parameters of the entry.
+    pub const fn params(&self) -> &ConsensusParameters {
+        &self.params
+    }
+}
+
+/// Versioned gas costs and consensus parameters of a chain.
+///
+/// Each entry is in force from its activation height until the next one. The
+/// first entry is activated at genesis, so every block height resolves to an
+/// entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    /// Ordered by activation height, never empty.
+    entries: Vec<ScheduleEntry>,
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Self::new(GasCostsValues::default(), ConsensusParameters::DEFAULT)
+    }
+}
+
+impl Schedule {
+    /// Schedule with the given gas costs and parameters from genesis.
+    pub fn new(gas_costs: GasCostsValues, params: ConsensusParameters) -> Self {
+        Self {
+            entries: vec![ScheduleEntry {
+                activation: 0.into(),
+                gas_costs: gas_costs.into(),
+                params,
+            }],
+        }
+    }
+
+    /// Activate the gas costs and parameters from `activation`, replacing the
+    /// entry activated at the same height.
+    pub fn activate(
+        &mut self,
+        activation: BlockHeight,
+        gas_costs: GasCostsValues,
+        params: ConsensusParameters,
+    ) -> &mut Self {
+        let entry = ScheduleEntry {
+            activation,
+            gas_costs: gas_costs.into(),
+            params,
+        };
+        match self
+            .entries
+            .binary_search_by_key(&activation, ScheduleEntry::activation)
+        {
+            Ok(i) => self.entries[i] = entry,
+            Err(i) => self.entries.insert(i, entry),
+        }
+        self
+    }
+
+    /// Entry in force at `block_height`.
+    pub fn at(&self, block_height: BlockHeight) -> &ScheduleEntry {
+        let next = self
+            .entries
+            .partition_point(|entry| entry.activation <= block_height);
+        &self.entries[next.saturating_sub(1)]
+    }
+
+    /// Entries, ordered by activation height.
+    pub fn entries(&self) -> &[ScheduleEntry] {
+        &self.entries
+    }
+}
+
+impl From<(GasCostsValues, ConsensusParameters)> for Schedule {
+    fn from((gas_costs, params): (GasCostsValues, ConsensusParameters)) -> Self {
+        Self::new(gas_costs, params)
+    }
+}
+
+impl FromIterator<(BlockHeight, GasCostsValues, ConsensusParameters)> for Schedule {
+    /// Schedule of the given entries, on top of the default gas costs and
+    /// parameters at genesis.
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (BlockHeight, GasCostsValues, ConsensusParameters)>,
+    {
+        let mut schedule = Self::default();
+        for (activation, gas_costs, params) in iter {
+            schedule.activate(activation, gas_costs, params);
+        }
+        schedule
+    }
+}
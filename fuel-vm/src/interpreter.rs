@@ -5,10 +5,19 @@ use crate::{
     constraints::reg_key::*,
     consts::*,
     context::Context,
-    gas::GasCosts,
-    state::Debugger,
+    gas::{
+        GasCosts,
+        Schedule,
+    },
+    policy::OpcodePolicy,
+    state::{
+        Debugger,
+        StateDiff,
+    },
+    storage::ReadWriteSet,
 };
 use std::{
+    borrow::Cow,
     io,
     io::Read,
     mem,
@@ -27,6 +36,7 @@ use fuel_tx::{
     Create,
     Executable,
     Output,
+    PanicContext,
     Receipt,
     Script,
     Transaction,
@@ -40,6 +50,7 @@ use fuel_types::{
         SizedBytes,
     },
     AssetId,
+    BlockHeight,
     ContractId,
     Word,
 };
@@ -51,27 +62,56 @@ mod constructors;
 mod contract;
 mod crypto;
 pub mod diff;
+mod ecal;
 mod executors;
 mod flow;
 mod gas;
 mod initialization;
+mod inspect;
 mod internal;
 mod log;
 mod memory;
 mod metadata;
+mod panic_context;
 mod post_execution;
 mod receipts;
 
 #[cfg(feature = "debug")]
 mod debug;
 
+#[cfg(feature = "debug")]
+mod suspend;
+
+#[cfg(feature = "trace")]
+mod trace;
+
+#[cfg(any(feature = "debug", feature = "trace"))]
+mod state_slots;
+
 use crate::profiler::Profiler;
 
-#[cfg(feature = "profile-gas")]
-use crate::profiler::InstructionLocation;
+#[cfg(feature = "trace")]
+use crate::tracer::Tracer;
+
+#[cfg(any(feature = "profile-gas", feature = "trace"))]
+use crate::constraints::InstructionLocation;
 
 pub use balances::RuntimeBalances;
-pub use memory::MemoryRange;
+pub use ecal::{
+    EcalContext,
+    EcalHandler,
+};
+pub use executors::GasLimitEstimate;
+pub use inspect::FrameInfo;
+pub use memory::{
+    Memory,
+    MemoryRange,
+    WriteCheckpoint,
+    WriteRecord,
+};
+pub use receipts::ReceiptSink;
+#[cfg(feature = "debug")]
+pub use suspend::SuspendError;
 
 use crate::checked_transaction::{
     CreateCheckedMetadata,
@@ -83,7 +123,7 @@ use crate::checked_transaction::{
 };
 
 use self::{
-    memory::Memory,
+    ecal::EcalSlot,
     receipts::ReceiptsCtx,
 };
 
@@ -98,7 +138,7 @@ use self::{
 #[derive(Debug, Clone)]
 pub struct Interpreter<S, Tx = ()> {
     registers: [Word; VM_REGISTER_COUNT],
-    memory: Memory<MEM_SIZE>,
+    memory: Memory,
     frames: Vec<CallFrame>,
     receipts: ReceiptsCtx,
     tx: Tx,
@@ -108,29 +148,43 @@ pub struct Interpreter<S, Tx = ()> {
     context: Context,
     balances: RuntimeBalances,
     gas_costs: GasCosts,
+    opcode_policy: OpcodePolicy,
+    /// Handler of the `ECAL` instruction, disabled by default.
+    ecal: EcalSlot,
     profiler: Profiler,
+    #[cfg(feature = "trace")]
+    tracer: Tracer,
+    /// Storage keys accessed by the last transaction, if the storage records
+    /// them.
+    read_write_set: Option<ReadWriteSet>,
+    /// Changes made by the last transaction, if the storage records them.
+    state_diff: Option<StateDiff>,
     params: ConsensusParameters,
+    /// Gas costs and parameters by block height. If set, they replace
+    /// `gas_costs` and `params` before each transaction.
+    schedule: Option<Schedule>,
+    /// Whether the script yielded after exhausting its instruction budget, see
+    /// [`Interpreter::transact_bounded`].
+    yielded: bool,
     /// `PanicContext` after the latest execution. It is consumed by
     /// `append_panic_receipt` and is `PanicContext::None` after consumption.
     panic_context: PanicContext,
 }
 
-/// Sometimes it is possible to add some additional context information
-/// regarding panic reasons to simplify debugging.
-// TODO: Move this enum into `fuel-tx` and use it inside of the `Receipt::Panic` as meta
-//  information. Maybe better to have `Vec<PanicContext>` to provide more information.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub(crate) enum PanicContext {
-    /// No additional information.
-    None,
-    /// `ContractId` retrieved during instruction execution.
-    ContractId(ContractId),
-}
-
 impl<S, Tx> Interpreter<S, Tx> {
-    /// Returns the current state of the VM memory
-    pub fn memory(&self) -> &[u8] {
-        self.memory.as_slice()
+    /// Returns the current state of the VM memory, as `MEM_SIZE` contiguous bytes
+    ///
+    /// The memory is allocated in pages, so it is usually copied, with the
+    /// unallocated pages reading as zero. Use [`Self::paged_memory`] to read
+    /// parts of it without copying the whole address space.
+    pub fn memory(&self) -> Cow<'_, [u8]> {
+        self.memory.read(0..MEM_SIZE)
+    }
+
+    /// Returns the current state of the VM memory, backed by lazily allocated
+    /// pages
+    pub const fn paged_memory(&self) -> &Memory {
+        &self.memory
     }
 
     /// Returns the current state of the registers
@@ -138,7 +192,8 @@ impl<S, Tx> Interpreter<S, Tx> {
         &self.registers
     }
 
-    pub(crate) fn call_stack(&self) -> &[CallFrame] {
+    /// Call frames of the current execution, outermost first
+    pub fn call_stack(&self) -> &[CallFrame] {
         self.frames.as_slice()
     }
 
@@ -167,11 +222,48 @@ impl<S, Tx> Interpreter<S, Tx> {
         &self.gas_costs
     }
 
+    /// Opcodes denied on top of the consensus rules.
+    pub const fn opcode_policy(&self) -> &OpcodePolicy {
+        &self.opcode_policy
+    }
+
+    /// Gas costs and consensus parameters by block height, if the interpreter
+    /// follows a schedule.
+    pub const fn schedule(&self) -> Option<&Schedule> {
+        self.schedule.as_ref()
+    }
+
+    /// Switch to the gas costs and parameters of the schedule in force at
+    /// `block_height`, if any.
+    pub(crate) fn apply_schedule(&mut self, block_height: BlockHeight) {
+        if let Some(schedule) = &self.schedule {
+            let entry = schedule.at(block_height);
+            self.params = *entry.params();
+            self.gas_costs = entry.gas_costs().clone();
+        }
+    }
+
     /// Receipts generated by a transaction execution.
     pub fn receipts(&self) -> &[Receipt] {
         self.receipts.as_ref().as_slice()
     }
 
+    /// Storage keys accessed by the last transaction.
+    ///
+    /// Only available if the storage records them, e.g. with
+    /// [`ReadWriteRecorder`](crate::storage::ReadWriteRecorder).
+    pub fn read_write_set(&self) -> Option<&ReadWriteSet> {
+        self.read_write_set.as_ref()
+    }
+
+    /// Changes made by the last transaction.
+    ///
+    /// Only available if the storage records them, e.g. with
+    /// [`ChangeRecorder`](crate::storage::ChangeRecorder).
+    pub fn state_diff(&self) -> Option<&StateDiff> {
+        self.state_diff.as_ref()
+    }
+
     pub(crate) fn contract_id(&self) -> Option<ContractId> {
         self.frames.last().map(|frame| *frame.to())
     }
@@ -195,7 +287,7 @@ pub(crate) fn is_unsafe_math(flag: Reg<FLAG>) -> bool {
     flags(flag).contains(Flags::UNSAFEMATH)
 }
 
-#[cfg(feature = "profile-gas")]
+#[cfg(any(feature = "profile-gas", feature = "trace"))]
 fn current_location(
     current_contract: Option<ContractId>,
     pc: crate::constraints::reg_key::Reg<{ crate::constraints::reg_key::PC }>,
@@ -204,6 +296,28 @@ fn current_location(
     InstructionLocation::new(current_contract, *pc - *is)
 }
 
+/// Locations of the call instructions in `frames`, followed by `location`.
+#[cfg(feature = "profile-gas")]
+fn current_call_stack(
+    frames: &[CallFrame],
+    location: InstructionLocation,
+) -> Vec<InstructionLocation> {
+    let mut caller = None;
+    let mut stack: Vec<_> = frames
+        .iter()
+        .map(|frame| {
+            let registers = frame.registers();
+            let offset = registers[fuel_asm::RegId::PC]
+                .saturating_sub(registers[fuel_asm::RegId::IS]);
+            let call = InstructionLocation::new(caller, offset);
+            caller = Some(*frame.to());
+            call
+        })
+        .collect();
+    stack.push(location);
+    stack
+}
+
 impl<S, Tx> AsRef<S> for Interpreter<S, Tx> {
     fn as_ref(&self) -> &S {
         &self.storage
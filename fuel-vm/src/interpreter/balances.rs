@@ -24,7 +24,10 @@ use std::{
     ops::Index,
 };
 
-use super::MemoryRange;
+use super::{
+    Memory,
+    MemoryRange,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(crate) struct Balance {
@@ -107,6 +110,23 @@ impl RuntimeBalances {
             .map(|state| Self { state })
     }
 
+    /// Balances with their location in the VM memory.
+    #[cfg(feature = "debug")]
+    pub(crate) fn iter_with_offsets(&self) -> impl Iterator<Item = (&AssetId, &Balance)> {
+        self.state.iter()
+    }
+
+    /// Restore balances previously located in the VM memory.
+    #[cfg(feature = "debug")]
+    pub(crate) fn from_offsets<T>(iter: T) -> Self
+    where
+        T: IntoIterator<Item = (AssetId, Balance)>,
+    {
+        Self {
+            state: iter.into_iter().collect(),
+        }
+    }
+
     /// Fetch the balance of a given Id, if set.
     pub fn balance(&self, asset: &AssetId) -> Option<Word> {
         self.state.get(asset).map(Balance::value)
@@ -114,7 +134,7 @@ impl RuntimeBalances {
 
     fn set_memory_balance_inner(
         balance: &Balance,
-        memory: &mut [u8; MEM_SIZE],
+        memory: &mut Memory,
     ) -> Result<Word, RuntimeError> {
         let value = balance.value();
         let offset = balance.offset();
@@ -137,7 +157,7 @@ impl RuntimeBalances {
     /// ordered, as in the protocol.
     pub fn checked_balance_add(
         &mut self,
-        memory: &mut [u8; MEM_SIZE],
+        memory: &mut Memory,
         asset: &AssetId,
         value: Word,
     ) -> Option<Word> {
@@ -152,7 +172,7 @@ impl RuntimeBalances {
     /// appropriate offset
     pub fn checked_balance_sub(
         &mut self,
-        memory: &mut [u8; MEM_SIZE],
+        memory: &mut Memory,
         asset: &AssetId,
         value: Word,
     ) -> Option<Word> {
@@ -179,8 +199,11 @@ impl RuntimeBalances {
             let value = balance.value();
             let ofs = balance.offset();
 
-            vm.memory[ofs..ofs + AssetId::LEN].copy_from_slice(asset.as_ref());
-            vm.memory[ofs + AssetId::LEN..ofs + AssetId::LEN + WORD_SIZE]
+            vm.memory
+                .write(ofs..ofs + AssetId::LEN)
+                .copy_from_slice(asset.as_ref());
+            vm.memory
+                .write(ofs + AssetId::LEN..ofs + AssetId::LEN + WORD_SIZE)
                 .copy_from_slice(&value.to_be_bytes());
         });
 
@@ -247,7 +270,7 @@ fn writes_to_memory_correctly() {
         .expect("failed to generate balances")
         .to_vm(&mut interpreter);
 
-    let memory = interpreter.memory();
+    let memory = interpreter.paged_memory();
     assets_sorted
         .iter()
         .fold(VM_MEMORY_BALANCES_OFFSET, |ofs, (asset, value)| {
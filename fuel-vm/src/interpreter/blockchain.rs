@@ -19,12 +19,18 @@ use super::{
         AppendReceipt,
     },
     memory::{
+        ownership_panic,
         try_mem_write,
         try_zeroize,
         OwnershipRegisters,
     },
+    panic_context::{
+        memory_panic,
+        panic_with_context,
+    },
     ExecutableTransaction,
     Interpreter,
+    Memory,
     MemoryRange,
     RuntimeBalances,
 };
@@ -70,6 +76,7 @@ use fuel_storage::{
 };
 use fuel_tx::{
     ContractIdExt,
+    PanicContext,
     Receipt,
 };
 use fuel_types::{
@@ -252,8 +259,7 @@ where
         b: Word,
     ) -> Result<(), RuntimeError> {
         let current_contract =
-            current_contract(&self.context, self.registers.fp(), self.memory.as_ref())?
-                .copied();
+            current_contract(&self.context, self.registers.fp(), &self.memory)?;
         let (
             SystemRegisters {
                 cgas, ggas, pc, is, ..
@@ -271,6 +277,7 @@ where
                 &mut self.panic_context,
             ),
             current_contract,
+            frames: &self.frames,
             cgas,
             ggas,
             pc,
@@ -285,7 +292,7 @@ where
         rb: RegisterId,
         c: Word,
     ) -> Result<(), RuntimeError> {
-        let contract_id = self.internal_contract().copied();
+        let contract_id = self.internal_contract();
         let (SystemRegisters { pc, .. }, mut w) = split_registers(&mut self.registers);
         let result = &mut w[WriteRegKey::try_from(rb)?];
 
@@ -340,7 +347,7 @@ where
         d: Word,
     ) -> Result<(), RuntimeError> {
         let owner = self.ownership_registers();
-        let contract_id = self.internal_contract().copied();
+        let contract_id = self.internal_contract();
         let (SystemRegisters { pc, .. }, mut w) = split_registers(&mut self.registers);
         let result = &mut w[WriteRegKey::try_from(rb)?];
 
@@ -390,7 +397,7 @@ where
         c: Word,
         d: Word,
     ) -> Result<(), RuntimeError> {
-        let contract_id = self.internal_contract().copied();
+        let contract_id = self.internal_contract();
         let (SystemRegisters { pc, .. }, mut w) = split_registers(&mut self.registers);
         let result = &mut w[WriteRegKey::try_from(rb)?];
 
@@ -401,7 +408,7 @@ where
             ..
         } = self;
 
-        state_write_qword(&contract_id?, storage, memory.as_mut(), pc, result, input)
+        state_write_qword(&contract_id?, storage, memory, pc, result, input)
     }
 
     pub(crate) fn timestamp(
@@ -445,7 +452,7 @@ where
 
 struct LoadContractCodeCtx<'vm, S, I> {
     contract_max_size: u64,
-    memory: &'vm mut [u8; MEM_SIZE],
+    memory: &'vm mut Memory,
     input_contracts: InputContracts<'vm, I>,
     storage: &'vm S,
     ssp: RegMut<'vm, SSP>,
@@ -478,7 +485,13 @@ impl<'vm, S, I> LoadContractCodeCtx<'vm, S, I> {
         let fp = *self.fp as usize;
 
         if ssp != sp {
-            return Err(PanicReason::ExpectedUnallocatedStack.into())
+            return Err(panic_with_context(
+                PanicReason::ExpectedUnallocatedStack,
+                PanicContext::Values {
+                    expected: ssp,
+                    actual: sp,
+                },
+            ))
         }
 
         let contract_id = a as usize;
@@ -490,30 +503,36 @@ impl<'vm, S, I> LoadContractCodeCtx<'vm, S, I> {
         let memory_offset_end = checked_add_usize(memory_offset, length)?;
 
         // Validate arguments
+        if contract_id_end as Word > VM_MAX_RAM {
+            return Err(memory_panic(
+                PanicReason::MemoryOverflow,
+                a,
+                ContractId::LEN as Word,
+            ))
+        }
         if memory_offset_end >= *self.hp as usize
-            || contract_id_end as Word > VM_MAX_RAM
             || length > MEM_MAX_ACCESS_SIZE as usize
             || length > self.contract_max_size as usize
         {
-            return Err(PanicReason::MemoryOverflow.into())
+            return Err(memory_panic(
+                PanicReason::MemoryOverflow,
+                ssp,
+                length as Word,
+            ))
         }
 
         // Clear memory
-        self.memory[memory_offset..memory_offset_end].fill(0);
+        self.memory.write(memory_offset..memory_offset_end).fill(0);
 
         // Fetch the contract id
-        let contract_id: &[u8; ContractId::LEN] = &self.memory
-            [contract_id..contract_id_end]
-            .try_into()
-            .expect("This can't fail, because we checked the bounds above.");
-
         // Safety: Memory bounds are checked and consistent
-        let contract_id = ContractId::from_bytes_ref(contract_id);
+        let contract_id =
+            ContractId::from(self.memory.read_bytes::<{ ContractId::LEN }>(contract_id));
 
-        self.input_contracts.check(contract_id)?;
+        self.input_contracts.check(&contract_id)?;
 
         // fetch the storage contract
-        let contract = super::contract::contract(self.storage, contract_id)?;
+        let contract = super::contract::contract(self.storage, &contract_id)?;
         let contract = contract.as_ref().as_ref();
 
         if contract_offset > contract.len() {
@@ -525,13 +544,19 @@ impl<'vm, S, I> LoadContractCodeCtx<'vm, S, I> {
 
         let code = &contract[..len];
 
-        let memory = self
-            .memory
-            .get_mut(memory_offset..arith::checked_add_usize(memory_offset, len)?)
-            .ok_or(PanicReason::MemoryOverflow)?;
+        let memory_end = arith::checked_add_usize(memory_offset, len)?;
+        if memory_end > MEM_SIZE {
+            return Err(memory_panic(
+                PanicReason::MemoryOverflow,
+                memory_offset as Word,
+                len as Word,
+            ))
+        }
 
         // perform the code copy
-        memory.copy_from_slice(code);
+        self.memory
+            .write(memory_offset..memory_end)
+            .copy_from_slice(code);
 
         self.sp
             //TODO this is looser than the compare against [RegId::HP,RegId::SSP+length]
@@ -547,19 +572,20 @@ impl<'vm, S, I> LoadContractCodeCtx<'vm, S, I> {
             let fp_code_size = add_usize(fp, CallFrame::code_size_offset());
             let fp_code_size_end = add_usize(fp_code_size, WORD_SIZE);
 
-            if fp_code_size_end > self.memory.len() {
-                Err(PanicReason::MemoryOverflow)?;
+            if fp_code_size_end > MEM_SIZE {
+                Err(memory_panic(
+                    PanicReason::MemoryOverflow,
+                    fp_code_size as Word,
+                    WORD_SIZE as Word,
+                ))?;
             }
 
-            let length = Word::from_be_bytes(
-                self.memory[fp_code_size..fp_code_size_end]
-                    .try_into()
-                    .expect("`fp_code_size_end` is `WORD_SIZE`"),
-            )
-            .checked_add(length as Word)
-            .ok_or(PanicReason::MemoryOverflow)?;
+            let length = Word::from_be_bytes(self.memory.read_bytes(fp_code_size))
+                .checked_add(length as Word)
+                .ok_or(PanicReason::MemoryOverflow)?;
 
-            self.memory[fp_code_size..fp_code_size_end]
+            self.memory
+                .write(fp_code_size..fp_code_size_end)
                 .copy_from_slice(&length.to_be_bytes());
         }
 
@@ -586,21 +612,28 @@ where
         let sub_id_range = CheckedMemConstLen::<{ Bytes32::LEN }>::new(b)?;
         let memory = &*self.append.memory;
 
-        let sub_id = Bytes32::from_bytes_ref(sub_id_range.read(memory));
+        let sub_id = Bytes32::from(sub_id_range.read(memory));
 
-        let contract_id = ContractId::from_bytes_ref(range.read(memory));
-        let asset_id = contract_id.asset_id(sub_id);
+        let contract_id = ContractId::from(range.read(memory));
+        let asset_id = contract_id.asset_id(&sub_id);
 
-        let balance = balance(self.storage, contract_id, &asset_id)?;
-        let balance = balance
-            .checked_sub(a)
-            .ok_or(PanicReason::NotEnoughBalance)?;
+        let balance = balance(self.storage, &contract_id, &asset_id)?;
+        let balance = balance.checked_sub(a).ok_or_else(|| {
+            panic_with_context(
+                PanicReason::NotEnoughBalance,
+                PanicContext::Balance {
+                    asset_id,
+                    expected: a,
+                    actual: balance,
+                },
+            )
+        })?;
 
         self.storage
-            .merkle_contract_asset_id_balance_insert(contract_id, &asset_id, balance)
+            .merkle_contract_asset_id_balance_insert(&contract_id, &asset_id, balance)
             .map_err(RuntimeError::from_io)?;
 
-        let receipt = Receipt::burn(*sub_id, *contract_id, a, *self.pc, *self.is);
+        let receipt = Receipt::burn(sub_id, contract_id, a, *self.pc, *self.is);
 
         append_receipt(self.append, receipt);
 
@@ -627,19 +660,19 @@ where
         let sub_id_range = CheckedMemConstLen::<{ Bytes32::LEN }>::new(b)?;
         let memory = &*self.append.memory;
 
-        let sub_id = Bytes32::from_bytes_ref(sub_id_range.read(memory));
+        let sub_id = Bytes32::from(sub_id_range.read(memory));
 
-        let contract_id = ContractId::from_bytes_ref(range.read(memory));
-        let asset_id = contract_id.asset_id(sub_id);
+        let contract_id = ContractId::from(range.read(memory));
+        let asset_id = contract_id.asset_id(&sub_id);
 
-        let balance = balance(self.storage, contract_id, &asset_id)?;
+        let balance = balance(self.storage, &contract_id, &asset_id)?;
         let balance = checked_add_word(balance, a)?;
 
         self.storage
-            .merkle_contract_asset_id_balance_insert(contract_id, &asset_id, balance)
+            .merkle_contract_asset_id_balance_insert(&contract_id, &asset_id, balance)
             .map_err(RuntimeError::from_io)?;
 
-        let receipt = Receipt::mint(*sub_id, *contract_id, a, *self.pc, *self.is);
+        let receipt = Receipt::mint(sub_id, contract_id, a, *self.pc, *self.is);
 
         append_receipt(self.append, receipt);
 
@@ -648,7 +681,7 @@ where
 }
 
 struct CodeCopyCtx<'vm, S, I> {
-    memory: &'vm mut [u8; MEM_SIZE],
+    memory: &'vm mut Memory,
     input_contracts: InputContracts<'vm, I>,
     storage: &'vm S,
     owner: OwnershipRegisters,
@@ -670,21 +703,21 @@ impl<'vm, S, I> CodeCopyCtx<'vm, S, I> {
         let contract = CheckedMemConstLen::<{ ContractId::LEN }>::new(b)?;
         let cd = checked_add_word(c, d)?;
 
-        if d > MEM_MAX_ACCESS_SIZE
-            || a > checked_sub_word(VM_MAX_RAM, d)?
-            || cd > VM_MAX_RAM
-        {
+        if d > MEM_MAX_ACCESS_SIZE || a > checked_sub_word(VM_MAX_RAM, d)? {
+            return Err(memory_panic(PanicReason::MemoryOverflow, a, d))
+        }
+        if cd > VM_MAX_RAM {
             return Err(PanicReason::MemoryOverflow.into())
         }
 
         let (a, c, d) = (a as usize, c as usize, d as usize);
         let cd = cd as usize;
 
-        let contract = ContractId::from_bytes_ref(contract.read(self.memory));
+        let contract = ContractId::from(contract.read(self.memory));
 
-        self.input_contracts.check(contract)?;
+        self.input_contracts.check(&contract)?;
 
-        let contract = super::contract::contract(self.storage, contract)?.into_owned();
+        let contract = super::contract::contract(self.storage, &contract)?.into_owned();
 
         if contract.as_ref().len() < d {
             try_zeroize(a, d, self.owner, self.memory)?;
@@ -698,7 +731,7 @@ impl<'vm, S, I> CodeCopyCtx<'vm, S, I> {
 
 pub(crate) fn block_hash<S: InterpreterStorage>(
     storage: &S,
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     owner: OwnershipRegisters,
     pc: RegMut<PC>,
     a: Word,
@@ -730,7 +763,7 @@ pub(crate) fn block_height(
 
 pub(crate) fn coinbase<S: InterpreterStorage>(
     storage: &S,
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     owner: OwnershipRegisters,
     pc: RegMut<PC>,
     a: Word,
@@ -741,7 +774,7 @@ pub(crate) fn coinbase<S: InterpreterStorage>(
 }
 
 struct CodeRootCtx<'vm, S, I> {
-    memory: &'vm mut [u8; MEM_SIZE],
+    memory: &'vm mut Memory,
     input_contracts: InputContracts<'vm, I>,
     storage: &'vm S,
     owner: OwnershipRegisters,
@@ -757,16 +790,20 @@ impl<'vm, S, I: Iterator<Item = &'vm ContractId>> CodeRootCtx<'vm, S, I> {
         let contract_id = CheckedMemConstLen::<{ ContractId::LEN }>::new(b)?;
 
         if ax > VM_MAX_RAM {
-            return Err(PanicReason::MemoryOverflow.into())
+            return Err(memory_panic(
+                PanicReason::MemoryOverflow,
+                a,
+                Bytes32::LEN as Word,
+            ))
         }
 
-        let contract_id = ContractId::from_bytes_ref(contract_id.read(self.memory));
+        let contract_id = ContractId::from(contract_id.read(self.memory));
 
-        self.input_contracts.check(contract_id)?;
+        self.input_contracts.check(&contract_id)?;
 
         let (_, root) = self
             .storage
-            .storage_contract_root(contract_id)
+            .storage_contract_root(&contract_id)
             .transpose()
             .ok_or(PanicReason::ContractNotFound)?
             .map_err(RuntimeError::from_io)?
@@ -780,11 +817,12 @@ impl<'vm, S, I: Iterator<Item = &'vm ContractId>> CodeRootCtx<'vm, S, I> {
 
 struct CodeSizeCtx<'vm, S, I> {
     storage: &'vm S,
-    memory: &'vm mut [u8; MEM_SIZE],
+    memory: &'vm mut Memory,
     gas_cost: DependentCost,
     profiler: &'vm mut Profiler,
     input_contracts: InputContracts<'vm, I>,
     current_contract: Option<ContractId>,
+    frames: &'vm [CallFrame],
     cgas: RegMut<'vm, CGAS>,
     ggas: RegMut<'vm, GGAS>,
     pc: RegMut<'vm, PC>,
@@ -803,15 +841,16 @@ impl<'vm, S, I: Iterator<Item = &'vm ContractId>> CodeSizeCtx<'vm, S, I> {
     {
         let contract_id = CheckedMemConstLen::<{ ContractId::LEN }>::new(b)?;
 
-        let contract_id = ContractId::from_bytes_ref(contract_id.read(self.memory));
+        let contract_id = ContractId::from(contract_id.read(self.memory));
 
-        self.input_contracts.check(contract_id)?;
+        self.input_contracts.check(&contract_id)?;
 
-        let len = contract_size(self.storage, contract_id)?;
+        let len = contract_size(self.storage, &contract_id)?;
         let profiler = ProfileGas {
             pc: self.pc.as_ref(),
             is: self.is,
             current_contract: self.current_contract,
+            frames: self.frames,
             profiler: self.profiler,
         };
         dependent_gas_charge(self.cgas, self.ggas, profiler, self.gas_cost, len)?;
@@ -823,7 +862,7 @@ impl<'vm, S, I: Iterator<Item = &'vm ContractId>> CodeSizeCtx<'vm, S, I> {
 
 pub(crate) struct StateWordCtx<'vm, S> {
     pub storage: &'vm mut S,
-    pub memory: &'vm [u8; MEM_SIZE],
+    pub memory: &'vm Memory,
     pub context: &'vm Context,
     pub fp: Reg<'vm, FP>,
     pub pc: RegMut<'vm, PC>,
@@ -845,10 +884,10 @@ pub(crate) fn state_read_word<S: InterpreterStorage>(
 
     let contract = internal_contract(context, fp, memory)?;
 
-    let key = Bytes32::from_bytes_ref(key.read(memory));
+    let key = Bytes32::from(key.read(memory));
 
     let value = storage
-        .merkle_contract_state(contract, key)
+        .merkle_contract_state(&contract, &key)
         .map_err(RuntimeError::from_io)?
         .map(|state| bytes::from_array(state.as_ref().borrow()))
         .map(Word::from_be_bytes);
@@ -876,15 +915,15 @@ pub(crate) fn state_write_word<S: InterpreterStorage>(
     let contract = internal_contract_bounds(context, fp)?;
 
     // Safety: Memory bounds logically verified by the interpreter
-    let contract = ContractId::from_bytes_ref(contract.read(memory));
-    let key = Bytes32::from_bytes_ref(key.read(memory));
+    let contract = ContractId::from(contract.read(memory));
+    let key = Bytes32::from(key.read(memory));
 
     let mut value = Bytes32::default();
 
     value[..WORD_SIZE].copy_from_slice(&c.to_be_bytes());
 
     let result = storage
-        .merkle_contract_state_insert(contract, key, &value)
+        .merkle_contract_state_insert(&contract, &key, &value)
         .map_err(RuntimeError::from_io)?;
 
     *exists = result.is_some() as Word;
@@ -916,7 +955,7 @@ where
     <S as StorageInspect<ContractsAssets>>::Error: Into<std::io::Error>,
 {
     max_message_data_length: u64,
-    memory: &'vm mut [u8; MEM_SIZE],
+    memory: &'vm mut Memory,
     tx_offset: usize,
     receipts: &'vm mut ReceiptsCtx,
     tx: &'vm mut Tx,
@@ -949,11 +988,21 @@ where
         )?;
 
         if self.msg_data_len > MEM_MAX_ACCESS_SIZE {
-            return Err(RuntimeError::Recoverable(PanicReason::MemoryOverflow))
+            return Err(memory_panic(
+                PanicReason::MemoryOverflow,
+                self.msg_data_ptr,
+                self.msg_data_len,
+            ))
         }
 
         if self.msg_data_len > self.max_message_data_length {
-            return Err(RuntimeError::Recoverable(PanicReason::MessageDataTooLong))
+            return Err(panic_with_context(
+                PanicReason::MessageDataTooLong,
+                PanicContext::Values {
+                    expected: self.max_message_data_length,
+                    actual: self.msg_data_len,
+                },
+            ))
         }
 
         let msg_data_range = MemoryRange::new(self.msg_data_ptr, self.msg_data_len)?;
@@ -980,12 +1029,12 @@ where
         let sender = CheckedMemConstLen::<{ Address::LEN }>::new(*self.fp)?;
         let txid = tx_id(self.memory);
         let msg_data = msg_data_range.read(self.memory).to_vec();
-        let sender = Address::from_bytes_ref(sender.read(self.memory));
+        let sender = Address::from(sender.read(self.memory));
 
         let receipt = Receipt::message_out(
-            txid,
+            &txid,
             self.receipts.len() as Word,
-            *sender,
+            sender,
             recipient,
             self.amount_coins_to_send,
             msg_data,
@@ -1028,7 +1077,7 @@ impl StateReadQWord {
             (Bytes32::LEN as Word).saturating_mul(num_slots),
         )?;
         if !ownership_registers.has_ownership_range(&mem_range) {
-            return Err(PanicReason::MemoryOwnership.into())
+            return Err(ownership_panic(&mem_range))
         }
         if ownership_registers.context.is_external() {
             return Err(PanicReason::ExpectedInternalContext.into())
@@ -1040,7 +1089,11 @@ impl StateReadQWord {
         let origin_key_memory_range =
             CheckedMemConstLen::<{ Bytes32::LEN }>::new(origin_key_memory_address)?;
         if dest_end > VM_MAX_RAM {
-            return Err(PanicReason::MemoryOverflow.into())
+            return Err(memory_panic(
+                PanicReason::MemoryOverflow,
+                destination_memory_address,
+                dest_end.wrapping_sub(destination_memory_address),
+            ))
         }
         Ok(Self {
             destination_address_memory_range: (destination_memory_address as usize)
@@ -1054,16 +1107,16 @@ impl StateReadQWord {
 fn state_read_qword(
     contract_id: &ContractId,
     storage: &impl InterpreterStorage,
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     pc: RegMut<PC>,
     result_register: &mut Word,
     input: StateReadQWord,
 ) -> Result<(), RuntimeError> {
-    let origin_key = Bytes32::from_bytes_ref(input.origin_key_memory_range.read(memory));
+    let origin_key = Bytes32::from(input.origin_key_memory_range.read(memory));
 
     let mut all_set = true;
     let result: Vec<u8> = storage
-        .merkle_contract_state_range(contract_id, origin_key, input.num_slots)
+        .merkle_contract_state_range(contract_id, &origin_key, input.num_slots)
         .map_err(RuntimeError::from_io)?
         .into_iter()
         .flat_map(|bytes| match bytes {
@@ -1077,7 +1130,9 @@ fn state_read_qword(
 
     *result_register = all_set as Word;
 
-    memory[input.destination_address_memory_range].copy_from_slice(&result);
+    memory
+        .write(input.destination_address_memory_range)
+        .copy_from_slice(&result);
 
     inc_pc(pc)?;
 
@@ -1108,7 +1163,11 @@ impl StateWriteQWord {
                 starting_storage_key_memory_address,
             )?;
         if source_end > VM_MAX_RAM {
-            return Err(PanicReason::MemoryOverflow.into())
+            return Err(memory_panic(
+                PanicReason::MemoryOverflow,
+                source_memory_address,
+                source_end.wrapping_sub(source_memory_address),
+            ))
         }
         Ok(Self {
             source_address_memory_range: (source_memory_address as usize)
@@ -1121,21 +1180,22 @@ impl StateWriteQWord {
 fn state_write_qword(
     contract_id: &ContractId,
     storage: &mut impl InterpreterStorage,
-    memory: &[u8; MEM_SIZE],
+    memory: &Memory,
     pc: RegMut<PC>,
     result_register: &mut Word,
     input: StateWriteQWord,
 ) -> Result<(), RuntimeError> {
     let destination_key =
-        Bytes32::from_bytes_ref(input.starting_storage_key_memory_range.read(memory));
+        Bytes32::from(input.starting_storage_key_memory_range.read(memory));
 
-    let values: Vec<_> = memory[input.source_address_memory_range]
+    let values: Vec<_> = memory
+        .read(input.source_address_memory_range)
         .chunks_exact(Bytes32::LEN)
         .flat_map(|chunk| Some(Bytes32::from(<[u8; 32]>::try_from(chunk).ok()?)))
         .collect();
 
     let any_none = storage
-        .merkle_contract_state_insert_range(contract_id, destination_key, &values)
+        .merkle_contract_state_insert_range(contract_id, &destination_key, &values)
         .map_err(RuntimeError::from_io)?
         .is_some();
     *result_register = any_none as Word;
@@ -1171,16 +1231,15 @@ impl StateClearQWord {
 fn state_clear_qword(
     contract_id: &ContractId,
     storage: &mut impl InterpreterStorage,
-    memory: &[u8; MEM_SIZE],
+    memory: &Memory,
     pc: RegMut<PC>,
     result_register: &mut Word,
     input: StateClearQWord,
 ) -> Result<(), RuntimeError> {
-    let start_key =
-        Bytes32::from_bytes_ref(input.start_storage_key_memory_range.read(memory));
+    let start_key = Bytes32::from(input.start_storage_key_memory_range.read(memory));
 
     let all_previously_set = storage
-        .merkle_contract_state_remove_range(contract_id, start_key, input.num_slots)
+        .merkle_contract_state_remove_range(contract_id, &start_key, input.num_slots)
         .map_err(RuntimeError::from_io)?
         .is_some();
 
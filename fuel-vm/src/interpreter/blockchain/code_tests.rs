@@ -11,7 +11,7 @@ use fuel_tx::Contract;
 #[test]
 fn test_load_contract() -> Result<(), RuntimeError> {
     let mut storage = MemoryStorage::new(Default::default(), Default::default());
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let mut pc = 4;
     let hp = 2000;
     let mut ssp = 1000;
@@ -53,7 +53,7 @@ fn test_load_contract() -> Result<(), RuntimeError> {
 #[test]
 fn test_code_copy() -> Result<(), RuntimeError> {
     let mut storage = MemoryStorage::new(Default::default(), Default::default());
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let mut pc = 4;
 
     let contract_id = ContractId::from([4u8; 32]);
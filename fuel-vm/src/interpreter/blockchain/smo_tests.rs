@@ -199,7 +199,7 @@ fn test_smo(
 ) -> Result<Output, RuntimeError> {
     let asset = AssetId::zeroed();
 
-    let mut memory: Memory<MEM_SIZE> = vec![0; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![0; MEM_SIZE].try_into().unwrap();
     for (offset, bytes) in mem {
         memory[offset..offset + bytes.len()].copy_from_slice(bytes.as_slice());
     }
@@ -8,8 +8,18 @@ use super::{
 use crate::{
     consts::*,
     context::Context,
-    gas::GasCosts,
-    interpreter::PanicContext,
+    gas::{
+        GasCosts,
+        Schedule,
+    },
+    interpreter::{
+        EcalHandler,
+        EcalSlot,
+        Memory,
+        PanicContext,
+        ReceiptSink,
+    },
+    policy::OpcodePolicy,
     state::Debugger,
     storage::MemoryStorage,
 };
@@ -37,9 +47,7 @@ where
     ) -> Self {
         Self {
             registers: [0; VM_REGISTER_COUNT],
-            memory: vec![0; MEM_SIZE]
-                .try_into()
-                .expect("Failed to allocate memory"),
+            memory: Memory::new(),
             frames: vec![],
             receipts: Default::default(),
             tx: Default::default(),
@@ -49,18 +57,63 @@ where
             context: Context::default(),
             balances: RuntimeBalances::default(),
             gas_costs,
+            opcode_policy: OpcodePolicy::default(),
+            ecal: EcalSlot::default(),
             profiler: Profiler::default(),
+            #[cfg(feature = "trace")]
+            tracer: Default::default(),
+            read_write_set: None,
+            state_diff: None,
             params,
+            schedule: None,
+            yielded: false,
             panic_context: PanicContext::None,
         }
     }
 
+    /// Create a new interpreter instance whose gas costs and consensus
+    /// parameters follow `schedule`.
+    ///
+    /// They are resolved from the block height of the storage before each
+    /// transaction and deployment.
+    pub fn with_schedule(storage: S, schedule: Schedule) -> Self {
+        let genesis = schedule.at(0.into());
+        let mut interpreter =
+            Self::with_storage(storage, *genesis.params(), genesis.gas_costs().clone());
+        interpreter.schedule = Some(schedule);
+        interpreter
+    }
+
     /// Set the consensus parameters for the interpreter
     pub fn with_params(&mut self, params: ConsensusParameters) -> &mut Self {
         self.params = params;
         self
     }
 
+    /// Set the opcodes denied on top of the consensus rules
+    pub fn with_opcode_policy(&mut self, policy: OpcodePolicy) -> &mut Self {
+        self.opcode_policy = policy;
+        self
+    }
+
+    /// Set the handler of the `ECAL` instruction.
+    pub fn with_ecal_handler<H>(&mut self, handler: H) -> &mut Self
+    where
+        H: EcalHandler + Send + Sync + 'static,
+    {
+        self.ecal = EcalSlot::new(handler);
+        self
+    }
+
+    /// Set the sink notified of the receipts while they are appended
+    pub fn with_receipt_sink<R>(&mut self, sink: R) -> &mut Self
+    where
+        R: ReceiptSink + Send + Sync + 'static,
+    {
+        self.receipts.set_sink(Box::new(sink));
+        self
+    }
+
     /// Sets a profiler for the VM
     #[cfg(feature = "profile-any")]
     pub fn with_profiler<P>(&mut self, receiver: P) -> &mut Self
@@ -7,8 +7,13 @@ use super::{
         set_variable_output,
         AppendReceipt,
     },
+    panic_context::{
+        memory_panic,
+        panic_with_context,
+    },
     ExecutableTransaction,
     Interpreter,
+    Memory,
     RuntimeBalances,
 };
 use crate::{
@@ -153,7 +158,7 @@ where
 
 struct ContractBalanceCtx<'vm, S, I> {
     storage: &'vm S,
-    memory: &'vm mut [u8; MEM_SIZE],
+    memory: &'vm mut Memory,
     pc: RegMut<'vm, PC>,
     input_contracts: InputContracts<'vm, I>,
 }
@@ -173,12 +178,12 @@ impl<'vm, S, I> ContractBalanceCtx<'vm, S, I> {
         let asset_id = CheckedMemConstLen::<{ AssetId::LEN }>::new(b)?;
         let contract = CheckedMemConstLen::<{ ContractId::LEN }>::new(c)?;
 
-        let asset_id = AssetId::from_bytes_ref(asset_id.read(self.memory));
-        let contract = ContractId::from_bytes_ref(contract.read(self.memory));
+        let asset_id = AssetId::from(asset_id.read(self.memory));
+        let contract = ContractId::from(contract.read(self.memory));
 
-        self.input_contracts.check(contract)?;
+        self.input_contracts.check(&contract)?;
 
-        let balance = balance(self.storage, contract, asset_id)?;
+        let balance = balance(self.storage, &contract, &asset_id)?;
 
         *result = balance;
 
@@ -187,7 +192,7 @@ impl<'vm, S, I> ContractBalanceCtx<'vm, S, I> {
 }
 struct TransferCtx<'vm, S, Tx> {
     storage: &'vm mut S,
-    memory: &'vm mut [u8; MEM_SIZE],
+    memory: &'vm mut Memory,
     context: &'vm Context,
     balances: &'vm mut RuntimeBalances,
     receipts: &'vm mut ReceiptsCtx,
@@ -221,27 +226,42 @@ impl<'vm, S, Tx> TransferCtx<'vm, S, Tx> {
 
         // if above usize::MAX then it cannot be safely cast to usize,
         // check the tighter bound between VM_MAX_RAM and usize::MAX
-        if ax > MIN_VM_MAX_RAM_USIZE_MAX || cx > MIN_VM_MAX_RAM_USIZE_MAX {
-            return Err(PanicReason::MemoryOverflow.into())
+        if ax > MIN_VM_MAX_RAM_USIZE_MAX {
+            return Err(memory_panic(
+                PanicReason::MemoryOverflow,
+                a,
+                ContractId::LEN as Word,
+            ))
+        }
+        if cx > MIN_VM_MAX_RAM_USIZE_MAX {
+            return Err(memory_panic(
+                PanicReason::MemoryOverflow,
+                c,
+                AssetId::LEN as Word,
+            ))
         }
 
         let amount = b;
-        let destination = ContractId::try_from(&self.memory[a as usize..ax as usize])
-            .expect("Unreachable! Checked memory range");
-        let asset_id = AssetId::try_from(&self.memory[c as usize..cx as usize])
+        let destination =
+            ContractId::try_from(&*self.memory.read(a as usize..ax as usize))
+                .expect("Unreachable! Checked memory range");
+        let asset_id = AssetId::try_from(&*self.memory.read(c as usize..cx as usize))
             .expect("Unreachable! Checked memory range");
 
         InputContracts::new(self.tx.input_contracts(), panic_context)
             .check(&destination)?;
 
         if amount == 0 {
-            return Err(PanicReason::NotEnoughBalance.into())
+            return Err(panic_with_context(
+                PanicReason::NotEnoughBalance,
+                PanicContext::AssetId(asset_id),
+            ))
         }
 
         let internal_context = match internal_contract(self.context, self.fp, self.memory)
         {
             // optimistically attempt to load the internal contract id
-            Ok(source_contract) => Some(*source_contract),
+            Ok(source_contract) => Some(source_contract),
             // revert to external context if no internal contract is set
             Err(RuntimeError::Recoverable(PanicReason::ExpectedInternalContext)) => None,
             // bubble up any other kind of errors
@@ -302,21 +322,32 @@ impl<'vm, S, Tx> TransferCtx<'vm, S, Tx> {
 
         // if above usize::MAX then it cannot be safely cast to usize,
         // check the tighter bound between VM_MAX_RAM and usize::MAX
-        if ax > MIN_VM_MAX_RAM_USIZE_MAX || dx > MIN_VM_MAX_RAM_USIZE_MAX {
-            return Err(PanicReason::MemoryOverflow.into())
+        if ax > MIN_VM_MAX_RAM_USIZE_MAX {
+            return Err(memory_panic(
+                PanicReason::MemoryOverflow,
+                a,
+                Address::LEN as Word,
+            ))
+        }
+        if dx > MIN_VM_MAX_RAM_USIZE_MAX {
+            return Err(memory_panic(
+                PanicReason::MemoryOverflow,
+                d,
+                AssetId::LEN as Word,
+            ))
         }
 
         let out_idx = b as usize;
-        let to = Address::try_from(&self.memory[a as usize..ax as usize])
+        let to = Address::try_from(&*self.memory.read(a as usize..ax as usize))
             .expect("Unreachable! Checked memory range");
-        let asset_id = AssetId::try_from(&self.memory[d as usize..dx as usize])
+        let asset_id = AssetId::try_from(&*self.memory.read(d as usize..dx as usize))
             .expect("Unreachable! Checked memory range");
         let amount = c;
 
         let internal_context = match internal_contract(self.context, self.fp, self.memory)
         {
             // optimistically attempt to load the internal contract id
-            Ok(source_contract) => Some(*source_contract),
+            Ok(source_contract) => Some(source_contract),
             // revert to external context if no internal contract is set
             Err(RuntimeError::Recoverable(PanicReason::ExpectedInternalContext)) => None,
             // bubble up any other kind of errors
@@ -421,9 +452,16 @@ where
     <S as StorageInspect<ContractsAssets>>::Error: Into<std::io::Error>,
 {
     let balance = balance(storage, contract, asset_id)?;
-    let balance = balance
-        .checked_sub(amount)
-        .ok_or(PanicReason::NotEnoughBalance)?;
+    let balance = balance.checked_sub(amount).ok_or_else(|| {
+        panic_with_context(
+            PanicReason::NotEnoughBalance,
+            PanicContext::Balance {
+                asset_id: *asset_id,
+                expected: amount,
+                actual: balance,
+            },
+        )
+    })?;
     storage
         .merkle_contract_asset_id_balance_insert(contract, asset_id, balance)
         .map_err(RuntimeError::from_io)?;
@@ -16,7 +16,7 @@ use test_case::test_case;
 
 #[test_case(0, 32 => Ok(()); "Can read contract balance")]
 fn test_contract_balance(b: Word, c: Word) -> Result<(), RuntimeError> {
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     memory[b as usize..(b as usize + AssetId::LEN)]
         .copy_from_slice(&[2u8; AssetId::LEN][..]);
     memory[c as usize..(c as usize + ContractId::LEN)]
@@ -54,7 +54,7 @@ fn test_contract_balance(b: Word, c: Word) -> Result<(), RuntimeError> {
 
 #[test_case(true, 0, 50, 32 => Ok(()); "Can transfer from external balance")]
 fn test_transfer(external: bool, a: Word, b: Word, c: Word) -> Result<(), RuntimeError> {
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     memory[a as usize..(a as usize + ContractId::LEN)]
         .copy_from_slice(&[3u8; ContractId::LEN][..]);
     memory[c as usize..(c as usize + AssetId::LEN)]
@@ -127,7 +127,7 @@ fn test_transfer_output(
     c: Word,
     d: Word,
 ) -> Result<(), RuntimeError> {
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     memory[a as usize..(a as usize + Address::LEN)]
         .copy_from_slice(&[3u8; Address::LEN][..]);
     memory[d as usize..(d as usize + AssetId::LEN)]
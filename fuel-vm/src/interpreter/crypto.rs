@@ -10,8 +10,10 @@ use super::{
         try_zeroize,
         OwnershipRegisters,
     },
+    panic_context::memory_panic,
     ExecutableTransaction,
     Interpreter,
+    Memory,
 };
 use crate::{
     constraints::reg_key::*,
@@ -97,7 +99,7 @@ where
 }
 
 pub(crate) fn secp256k1_recover(
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     owner: OwnershipRegisters,
     err: RegMut<ERR>,
     pc: RegMut<PC>,
@@ -126,7 +128,7 @@ pub(crate) fn secp256k1_recover(
 }
 
 pub(crate) fn secp256r1_recover(
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     owner: OwnershipRegisters,
     err: RegMut<ERR>,
     pc: RegMut<PC>,
@@ -153,7 +155,7 @@ pub(crate) fn secp256r1_recover(
 }
 
 pub(crate) fn ed25519_verify(
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     err: RegMut<ERR>,
     pc: RegMut<PC>,
     a: Word,
@@ -175,7 +177,7 @@ pub(crate) fn ed25519_verify(
 }
 
 pub(crate) fn keccak256(
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     owner: OwnershipRegisters,
     pc: RegMut<PC>,
     a: Word,
@@ -189,18 +191,22 @@ pub(crate) fn keccak256(
 
     let bc = checked_add_word(b, c)?;
 
-    if a > checked_sub_word(VM_MAX_RAM, Bytes32::LEN as Word)?
-        || c > MEM_MAX_ACCESS_SIZE
-        || bc > MIN_VM_MAX_RAM_USIZE_MAX
-    {
-        return Err(PanicReason::MemoryOverflow.into())
+    if a > checked_sub_word(VM_MAX_RAM, Bytes32::LEN as Word)? {
+        return Err(memory_panic(
+            PanicReason::MemoryOverflow,
+            a,
+            Bytes32::LEN as Word,
+        ))
+    }
+    if c > MEM_MAX_ACCESS_SIZE || bc > MIN_VM_MAX_RAM_USIZE_MAX {
+        return Err(memory_panic(PanicReason::MemoryOverflow, b, c))
     }
 
     let (a, b, bc) = (a as usize, b as usize, bc as usize);
 
     let mut h = Keccak256::new();
 
-    h.update(&memory[b..bc]);
+    h.update(memory.read(b..bc));
 
     try_mem_write(a, h.finalize().as_slice(), owner, memory)?;
 
@@ -208,7 +214,7 @@ pub(crate) fn keccak256(
 }
 
 pub(crate) fn sha256(
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     owner: OwnershipRegisters,
     pc: RegMut<PC>,
     a: Word,
@@ -217,16 +223,20 @@ pub(crate) fn sha256(
 ) -> Result<(), RuntimeError> {
     let bc = checked_add_word(b, c)?;
 
-    if a > checked_sub_word(VM_MAX_RAM, Bytes32::LEN as Word)?
-        || c > MEM_MAX_ACCESS_SIZE
-        || bc > MIN_VM_MAX_RAM_USIZE_MAX
-    {
-        return Err(PanicReason::MemoryOverflow.into())
+    if a > checked_sub_word(VM_MAX_RAM, Bytes32::LEN as Word)? {
+        return Err(memory_panic(
+            PanicReason::MemoryOverflow,
+            a,
+            Bytes32::LEN as Word,
+        ))
+    }
+    if c > MEM_MAX_ACCESS_SIZE || bc > MIN_VM_MAX_RAM_USIZE_MAX {
+        return Err(memory_panic(PanicReason::MemoryOverflow, b, c))
     }
 
     let (a, b, bc) = (a as usize, b as usize, bc as usize);
 
-    try_mem_write(a, Hasher::hash(&memory[b..bc]).as_ref(), owner, memory)?;
+    try_mem_write(a, Hasher::hash(memory.read(b..bc)).as_ref(), owner, memory)?;
 
     inc_pc(pc)
 }
@@ -13,7 +13,7 @@ use super::*;
 
 #[test]
 fn test_recover_secp256k1() -> Result<(), RuntimeError> {
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let owner = OwnershipRegisters {
         sp: 1000,
         ssp: 1000,
@@ -63,7 +63,7 @@ fn test_recover_secp256r1() -> Result<(), RuntimeError> {
 
     let mut rng = &mut StdRng::seed_from_u64(8586);
 
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let owner = OwnershipRegisters {
         sp: 1000,
         ssp: 1000,
@@ -112,7 +112,7 @@ fn test_recover_secp256r1() -> Result<(), RuntimeError> {
 fn test_verify_ed25519() -> Result<(), RuntimeError> {
     use ed25519_dalek::Signer;
 
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let mut err = 0;
     let mut pc = 4;
 
@@ -146,7 +146,7 @@ fn test_verify_ed25519() -> Result<(), RuntimeError> {
 
 #[test]
 fn test_keccak256() -> Result<(), RuntimeError> {
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let owner = OwnershipRegisters {
         sp: 1000,
         ssp: 1000,
@@ -175,7 +175,7 @@ fn test_keccak256() -> Result<(), RuntimeError> {
 
 #[test]
 fn test_sha256() -> Result<(), RuntimeError> {
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let owner = OwnershipRegisters {
         sp: 1000,
         ssp: 1000,
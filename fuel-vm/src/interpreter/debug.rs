@@ -1,6 +1,29 @@
-use super::Interpreter;
-use crate::prelude::*;
-use fuel_asm::RegId;
+use super::{
+    Interpreter,
+    WriteRecord,
+};
+use crate::{
+    consts::*,
+    prelude::*,
+    state::{
+        ExecuteState,
+        Watchpoint,
+    },
+};
+use fuel_asm::{
+    RawInstruction,
+    RegId,
+};
+use fuel_tx::ReceiptRepr;
+
+/// State captured before executing an instruction, required to evaluate the
+/// watchpoints.
+pub(crate) struct WatchStep {
+    location: Breakpoint,
+    registers: [Word; VM_REGISTER_COUNT],
+    receipts: usize,
+    storage_writes: Option<super::state_slots::StateSlots>,
+}
 
 impl<S, Tx> Interpreter<S, Tx>
 where
@@ -26,6 +49,29 @@ where
         self.debugger.remove_breakpoint(breakpoint)
     }
 
+    /// Set a new watchpoint.
+    pub fn set_watchpoint(&mut self, watchpoint: Watchpoint) {
+        self.debugger.set_watchpoint(watchpoint)
+    }
+
+    /// Remove a previously set watchpoint.
+    pub fn remove_watchpoint(&mut self, watchpoint: &Watchpoint) {
+        self.debugger.remove_watchpoint(watchpoint)
+    }
+
+    /// Set the maximum amount of steps recorded by
+    /// [`Interpreter::step_forward`] to be reverted. `0` disables the recording.
+    pub fn set_history_limit(&mut self, limit: usize) {
+        self.debugger.set_history_limit(limit)
+    }
+
+    /// Set the maximum amount of bytes held by the steps recorded by
+    /// [`Interpreter::step_forward`], see
+    /// [`crate::state::Debugger::set_history_bytes_limit`].
+    pub fn set_history_bytes_limit(&mut self, limit: usize) {
+        self.debugger.set_history_bytes_limit(limit)
+    }
+
     pub(crate) fn eval_debugger_state(&mut self) -> DebugEval {
         let debugger = &mut self.debugger;
 
@@ -35,6 +81,84 @@ where
         debugger.eval_state(contract, pc)
     }
 
+    /// Capture the state required to evaluate the watchpoints after the
+    /// instruction is executed, if any watchpoint is set.
+    pub(crate) fn watch_begin(&mut self, raw: RawInstruction) -> Option<WatchStep> {
+        let watchpoints = self.debugger.watchpoints();
+        if watchpoints.is_empty() {
+            return None
+        }
+
+        let storage_writes = watchpoints
+            .iter()
+            .any(|w| matches!(w, Watchpoint::Storage { .. }))
+            .then(|| Instruction::try_from(raw).ok())
+            .flatten()
+            .filter(|i| matches!(i, Instruction::SWW(_) | Instruction::SWWQ(_)))
+            .and_then(|i| self.state_slots(&i));
+
+        let contract = self.frames.last().map(CallFrame::to).copied();
+        let pc = self.registers[RegId::PC].saturating_sub(self.registers[RegId::IS]);
+
+        Some(WatchStep {
+            location: Breakpoint::raw(contract.unwrap_or_default(), pc),
+            registers: self.registers,
+            receipts: self.receipts.len(),
+            storage_writes,
+        })
+    }
+
+    /// Evaluate the watchpoints against the effects of the instruction captured
+    /// in `step`, breaking the program on the first one triggered.
+    pub(crate) fn watch_end(
+        &self,
+        step: WatchStep,
+        memory_writes: &[WriteRecord],
+        state: ExecuteState,
+    ) -> ExecuteState {
+        if state != ExecuteState::Proceed {
+            return state
+        }
+
+        let receipts = &self.receipts.as_ref()[step.receipts..];
+        let ggas = (step.registers[RegId::GGAS], self.registers[RegId::GGAS]);
+
+        let triggered = self.debugger.watchpoints().iter().find(|w| match w {
+            Watchpoint::Register {
+                register,
+                condition,
+            } => match (step.registers.get(*register), self.registers.get(*register)) {
+                (Some(before), Some(after)) => {
+                    !condition.matches(*before) && condition.matches(*after)
+                }
+                _ => false,
+            },
+            Watchpoint::Memory { start, end } => memory_writes.iter().any(|write| {
+                let written = write.range();
+                written.start < *end && *start < written.end
+            }),
+            Watchpoint::Storage { contract, key } => matches!(
+                &step.storage_writes,
+                Some(slots) if &slots.contract == contract && slots.contains(key)
+            ),
+            Watchpoint::Receipt(kind) => receipts
+                .iter()
+                .any(|receipt| ReceiptRepr::from(receipt) == *kind),
+            Watchpoint::GasBelow(threshold) => {
+                ggas.0 >= *threshold && ggas.1 < *threshold
+            }
+        });
+
+        match triggered {
+            Some(watchpoint) => DebugEval::Watchpoint {
+                location: step.location,
+                watchpoint: *watchpoint,
+            }
+            .into(),
+            None => state,
+        }
+    }
+
     pub(crate) fn debugger_set_last_state(&mut self, state: ProgramState) {
         self.debugger.set_last_state(state)
     }
@@ -162,3 +286,456 @@ fn single_stepping() {
 
     assert_eq!(stops, vec![0, 4, 8, 4, 8, 4, 8, 4, 8, 4, 8, 12]);
 }
+
+#[cfg(test)]
+fn watch_script(
+    vm: &mut Interpreter<MemoryStorage, Script>,
+    script: Vec<Instruction>,
+) -> ProgramState {
+    let tx = TransactionBuilder::script(script.into_iter().collect(), vec![])
+        .gas_limit(1_000_000)
+        .add_random_fee_input()
+        .finalize()
+        .into_checked(
+            Default::default(),
+            &ConsensusParameters::default(),
+            vm.gas_costs(),
+        )
+        .expect("failed to generate checked tx");
+
+    vm.transact(tx)
+        .map(ProgramState::from)
+        .expect("Failed to execute script!")
+}
+
+#[test]
+fn watchpoint_register() {
+    use fuel_asm::op;
+
+    let mut vm = Interpreter::with_memory_storage();
+    let watchpoint = Watchpoint::register(0x10, RegisterCondition::GreaterOrEqual(2));
+    vm.set_watchpoint(watchpoint);
+
+    let script = vec![
+        op::addi(0x10, RegId::ZERO, 1),
+        op::addi(0x10, 0x10, 1),
+        op::addi(0x10, 0x10, 1),
+        op::ret(0x10),
+    ];
+    let state = watch_script(&mut vm, script);
+
+    let debug = state.debug_ref().expect("Expected watchpoint");
+    assert_eq!(debug.watchpoint(), Some(&watchpoint));
+    assert_eq!(debug.location(), Some(&Breakpoint::script(1)));
+    assert_eq!(vm.registers()[0x10], 2);
+
+    // The condition is still satisfied, so the watchpoint doesn't trigger again
+    let state = vm.resume().expect("Failed to resume");
+    assert_eq!(state, ProgramState::Return(3));
+}
+
+#[test]
+fn watchpoint_memory() {
+    use fuel_asm::op;
+
+    let mut vm = Interpreter::with_memory_storage();
+    let range = MemoryRange::new(VM_MAX_RAM - 4, 2).expect("Invalid range");
+    vm.set_watchpoint(Watchpoint::memory(&range));
+    vm.set_watchpoint(Watchpoint::memory(
+        &MemoryRange::new(0, 1).expect("Invalid range"),
+    ));
+
+    let script = vec![
+        op::movi(0x10, 8),
+        op::aloc(0x10),
+        op::sw(RegId::HP, RegId::ONE, 0),
+        op::ret(RegId::ONE),
+    ];
+    let state = watch_script(&mut vm, script);
+
+    let debug = state.debug_ref().expect("Expected watchpoint");
+    assert_eq!(debug.watchpoint(), Some(&Watchpoint::memory(&range)));
+    assert_eq!(debug.location(), Some(&Breakpoint::script(2)));
+
+    let state = vm.resume().expect("Failed to resume");
+    assert_eq!(state, ProgramState::Return(1));
+}
+
+#[test]
+fn watchpoint_receipt() {
+    use fuel_asm::op;
+
+    let mut vm = Interpreter::with_memory_storage();
+    vm.set_watchpoint(Watchpoint::Receipt(ReceiptRepr::Log));
+
+    let script = vec![
+        op::noop(),
+        op::log(RegId::ONE, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ];
+    let state = watch_script(&mut vm, script);
+
+    let debug = state.debug_ref().expect("Expected watchpoint");
+    assert_eq!(debug.location(), Some(&Breakpoint::script(1)));
+    assert_eq!(ReceiptRepr::from(&vm.receipts()[0]), ReceiptRepr::Log);
+}
+
+#[test]
+fn watchpoint_gas_below() {
+    use fuel_asm::op;
+
+    let mut vm = Interpreter::with_memory_storage();
+    let threshold = 1_000_000 - 2 * vm.gas_costs().addi;
+    vm.set_watchpoint(Watchpoint::GasBelow(threshold));
+
+    let script = vec![
+        op::addi(0x10, RegId::ZERO, 1),
+        op::addi(0x10, 0x10, 1),
+        op::addi(0x10, 0x10, 1),
+        op::ret(0x10),
+    ];
+    let state = watch_script(&mut vm, script);
+
+    let debug = state.debug_ref().expect("Expected watchpoint");
+    assert_eq!(debug.location(), Some(&Breakpoint::script(2)));
+    assert!(vm.remaining_gas() < threshold);
+}
+
+#[test]
+fn watchpoint_storage() {
+    use crate::{
+        script_with_data_offset,
+        util::test_helpers::TestBuilder,
+    };
+    use fuel_asm::op;
+
+    let mut test_context = TestBuilder::new(2322u64);
+
+    let program = vec![
+        op::movi(0x10, Bytes32::LEN as Immediate18),
+        op::aloc(0x10),
+        op::srw(0x13, 0x14, RegId::HP),
+        op::movi(0x11, 42),
+        op::sww(RegId::HP, 0x12, 0x11),
+        op::ret(RegId::ONE),
+    ];
+    let contract_id = test_context.setup_contract(program, None, None).contract_id;
+
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset as Immediate18),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.tx_offset()
+    );
+    let script_data: Vec<u8> = contract_id
+        .iter()
+        .copied()
+        .chain((0 as Word).to_be_bytes())
+        .chain((0 as Word).to_be_bytes())
+        .collect();
+
+    let tx = test_context
+        .start_script(script, script_data)
+        .gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+
+    let mut vm = Interpreter::with_storage(
+        test_context.get_storage().clone(),
+        *test_context.get_params(),
+        GasCosts::default(),
+    );
+    let watchpoint = Watchpoint::storage(contract_id, Bytes32::zeroed());
+    vm.set_watchpoint(watchpoint);
+    vm.set_watchpoint(Watchpoint::storage(contract_id, Bytes32::new([1; 32])));
+
+    let state = vm
+        .transact(tx)
+        .map(ProgramState::from)
+        .expect("Failed to execute script!");
+
+    let debug = state.debug_ref().expect("Expected watchpoint");
+    assert_eq!(debug.watchpoint(), Some(&watchpoint));
+    assert_eq!(debug.location(), Some(&Breakpoint::new(contract_id, 4)));
+
+    let state = vm.resume().expect("Failed to resume");
+    assert_eq!(state, ProgramState::Return(1));
+}
+
+#[cfg(test)]
+fn recorded_script(
+    script: Vec<Instruction>,
+) -> (
+    Interpreter<crate::interpreter::diff::Record<MemoryStorage>, Script>,
+    ProgramState,
+) {
+    recorded_script_with_gas_costs(script, GasCosts::default())
+}
+
+#[cfg(test)]
+fn recorded_script_with_gas_costs(
+    script: Vec<Instruction>,
+    gas_costs: GasCosts,
+) -> (
+    Interpreter<crate::interpreter::diff::Record<MemoryStorage>, Script>,
+    ProgramState,
+) {
+    let mut vm = Interpreter::with_storage(
+        MemoryStorage::default(),
+        Default::default(),
+        gas_costs,
+    )
+    .add_recording();
+    vm.set_single_stepping(true);
+    vm.set_history_limit(usize::MAX);
+
+    let tx = TransactionBuilder::script(script.into_iter().collect(), vec![])
+        .gas_limit(1_000_000)
+        .add_random_fee_input()
+        .finalize()
+        .into_checked(
+            Default::default(),
+            &ConsensusParameters::default(),
+            vm.gas_costs(),
+        )
+        .expect("failed to generate checked tx");
+
+    let state = vm
+        .transact(tx)
+        .map(ProgramState::from)
+        .expect("Failed to execute script!");
+
+    (vm, state)
+}
+
+#[test]
+fn step_back_restores_previous_state() {
+    use fuel_asm::op;
+
+    let script = vec![
+        op::movi(0x10, 16),
+        op::aloc(0x10),
+        op::sw(RegId::HP, RegId::ONE, 0),
+        op::sw(RegId::HP, 0x10, 1),
+        op::log(0x10, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::mcli(RegId::HP, 16),
+        op::ret(RegId::ONE),
+    ];
+    let (mut vm, mut state) = recorded_script(script);
+
+    let mut snapshots = vec![];
+    while state.is_debug() {
+        snapshots.push((vm.clone(), state));
+        state = vm.step_forward().expect("Failed to step");
+    }
+    assert_eq!(state, ProgramState::Return(1));
+    assert_eq!(vm.debugger().history_len(), snapshots.len());
+
+    while let Some((snapshot, expected)) = snapshots.pop() {
+        state = vm.step_back().expect("Expected a step to revert");
+        assert_eq!(state, expected);
+        assert_eq!(vm, snapshot);
+    }
+    assert_eq!(vm.step_back(), None);
+
+    // The execution can be replayed after reverting
+    while state.is_debug() {
+        state = vm.step_forward().expect("Failed to step");
+    }
+    assert_eq!(state, ProgramState::Return(1));
+}
+
+#[test]
+fn step_back_restores_contract_storage() {
+    use crate::{
+        interpreter::diff::Record,
+        script_with_data_offset,
+        storage::ContractsState,
+        util::test_helpers::TestBuilder,
+    };
+    use fuel_asm::op;
+    use fuel_storage::StorageInspect;
+
+    let mut test_context = TestBuilder::new(2322u64);
+
+    let program = vec![
+        op::movi(0x10, Bytes32::LEN as Immediate18),
+        op::aloc(0x10),
+        op::movi(0x11, 42),
+        op::sww(RegId::HP, 0x12, 0x11),
+        op::movi(0x11, 1),
+        op::swwq(RegId::HP, 0x12, RegId::HP, 0x11),
+        op::ret(RegId::ONE),
+    ];
+    let contract_id = test_context.setup_contract(program, None, None).contract_id;
+
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset as Immediate18),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.tx_offset()
+    );
+    let script_data: Vec<u8> = contract_id
+        .iter()
+        .copied()
+        .chain((0 as Word).to_be_bytes())
+        .chain((0 as Word).to_be_bytes())
+        .collect();
+
+    let tx = test_context
+        .start_script(script, script_data)
+        .gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+
+    let mut vm = Interpreter::with_storage(
+        Record::new(test_context.get_storage().clone()),
+        *test_context.get_params(),
+        GasCosts::default(),
+    );
+    vm.set_breakpoint(Breakpoint::new(contract_id, 3));
+    vm.set_history_limit(usize::MAX);
+
+    let state = vm
+        .transact(tx)
+        .map(ProgramState::from)
+        .expect("Failed to execute script!");
+    let breakpoint = Breakpoint::new(contract_id, 3);
+    assert_eq!(
+        state.debug_ref().and_then(DebugEval::breakpoint),
+        Some(&breakpoint)
+    );
+
+    let key = (&contract_id, &Bytes32::zeroed()).into();
+    let slot = |vm: &Interpreter<Record<MemoryStorage>, Script>| {
+        StorageInspect::<ContractsState>::get(vm.as_ref(), &key)
+            .expect("Failed to read the storage")
+            .map(|v| v.into_owned())
+    };
+    assert_eq!(slot(&vm), None);
+    let snapshot = vm.clone();
+
+    let state = vm.step_forward().expect("Failed to step");
+    assert_eq!(state, ProgramState::Return(1));
+    assert_eq!(slot(&vm), Some(Bytes32::zeroed()));
+
+    assert_eq!(
+        vm.resume_backwards(),
+        Some(ProgramState::RunProgram(DebugEval::Breakpoint(breakpoint)))
+    );
+    assert_eq!(vm, snapshot);
+    assert_eq!(slot(&vm), None);
+}
+
+#[test]
+fn resume_backwards_stops_at_breakpoint() {
+    use fuel_asm::op;
+
+    let script = vec![
+        op::movi(0x10, 1),
+        op::movi(0x11, 2),
+        op::movi(0x12, 3),
+        op::movi(0x13, 4),
+        op::ret(RegId::ONE),
+    ];
+    let (mut vm, mut state) = recorded_script(script);
+    vm.set_breakpoint(Breakpoint::script(1));
+
+    while state.is_debug() {
+        state = vm.step_forward().expect("Failed to step");
+    }
+
+    let state = vm.resume_backwards().expect("Expected a step to revert");
+    assert_eq!(state, Breakpoint::script(1));
+    assert_eq!(vm.registers()[0x10], 1);
+    assert_eq!(vm.registers()[0x11], 0);
+
+    let state = vm.resume_backwards().expect("Expected a step to revert");
+    assert_eq!(state, Breakpoint::script(0));
+    assert_eq!(vm.registers()[0x10], 0);
+    assert_eq!(vm.resume_backwards(), None);
+}
+
+#[test]
+fn step_history_is_bounded() {
+    use fuel_asm::op;
+
+    let script = vec![
+        op::movi(0x10, 1),
+        op::movi(0x11, 2),
+        op::movi(0x12, 3),
+        op::movi(0x13, 4),
+        op::ret(RegId::ONE),
+    ];
+    let (mut vm, mut state) = recorded_script(script);
+    vm.set_history_limit(2);
+
+    while state.is_debug() {
+        state = vm.step_forward().expect("Failed to step");
+    }
+    assert_eq!(vm.debugger().history_len(), 2);
+
+    assert!(vm.step_back().is_some());
+    assert!(vm.step_back().is_some());
+    assert_eq!(vm.step_back(), None);
+    assert_eq!(vm.registers()[0x13], 0);
+    assert_eq!(vm.registers()[0x12], 3);
+
+    // Resuming without recording invalidates the history
+    vm.step_forward().expect("Failed to step");
+    assert_eq!(vm.debugger().history_len(), 1);
+    vm.resume().expect("Failed to resume");
+    assert_eq!(vm.debugger().history_len(), 0);
+}
+
+#[test]
+fn step_history_is_bounded_in_bytes() {
+    use fuel_asm::op;
+
+    const LEN: usize = 1024 * 1024;
+    let script = vec![
+        op::movi(0x10, 1),
+        op::slli(0x10, 0x10, 20),
+        op::aloc(0x10),
+        op::mcl(RegId::HP, 0x10),
+        op::ret(RegId::ONE),
+    ];
+
+    // The cleared memory fits in the history, and the step can be reverted
+    let (mut vm, mut state) =
+        recorded_script_with_gas_costs(script.clone(), GasCosts::free());
+    vm.set_history_bytes_limit(4 * LEN);
+    while state.is_debug() {
+        state = vm.step_forward().expect("Failed to step");
+        assert!(vm.debugger().history_bytes() <= 4 * LEN);
+    }
+    assert_eq!(vm.debugger().history_len(), 5);
+    assert!(vm.debugger().history_bytes() > LEN);
+
+    // Lowering the limit discards the oldest steps
+    vm.set_history_bytes_limit(LEN);
+    assert!(vm.debugger().history_bytes() <= LEN);
+    assert!(vm.debugger().history_len() < 5);
+
+    // A step larger than the limit discards the whole history
+    let (mut vm, mut state) = recorded_script_with_gas_costs(script, GasCosts::free());
+    vm.set_history_bytes_limit(LEN / 2);
+    let mut lens = vec![];
+    while state.is_debug() {
+        state = vm.step_forward().expect("Failed to step");
+        lens.push(vm.debugger().history_len());
+    }
+    assert_eq!(state, ProgramState::Return(1));
+    assert_eq!(lens, vec![1, 2, 3, 0, 1]);
+    assert!(vm.debugger().history_bytes() <= LEN / 2);
+}
@@ -1,8 +1,10 @@
 //! # VM State Differences
 //! This module provides the ability to generate diffs between two VMs internal states.
 //! The diff can then be used to invert a VM to the original state.
-//! This module is experimental work in progress and currently only used in testing
-//! although it could potentially stabilize to be used in production.
+//!
+//! With the `debug` feature, the debugger records the diff of every step of an
+//! [`Interpreter`] with a [`Record`] storage, so the steps can be reverted with
+//! [`Interpreter::step_back`] and [`Interpreter::resume_backwards`].
 
 use std::{
     any::Any,
@@ -47,6 +49,18 @@ use super::{
     Interpreter,
     PanicContext,
 };
+#[cfg(feature = "debug")]
+use super::{
+    RuntimeBalances,
+    WriteCheckpoint,
+};
+#[cfg(feature = "debug")]
+use crate::{
+    consts::VM_REGISTER_COUNT,
+    state::ProgramState,
+    storage::InterpreterStorage,
+};
+pub use storage::Record;
 use storage::*;
 
 mod storage;
@@ -377,8 +391,8 @@ impl<S, Tx> Interpreter<S, Tx> {
 
         if self.panic_context != other.panic_context {
             diff.changes.push(Change::PanicContext(Delta {
-                from: self.panic_context.clone(),
-                to: other.panic_context.clone(),
+                from: self.panic_context,
+                to: other.panic_context,
             }))
         }
 
@@ -404,11 +418,12 @@ impl<S, Tx> Interpreter<S, Tx> {
                 invert_receipts_ctx(&mut self.receipts, value)
             }
             Change::Balance(Previous(value)) => invert_map(self.balances.as_mut(), value),
-            Change::Memory(Previous(Memory { start, bytes })) => {
-                self.memory[*start..(*start + bytes.len())].copy_from_slice(&bytes[..])
-            }
+            Change::Memory(Previous(Memory { start, bytes })) => self
+                .memory
+                .write(*start..(*start + bytes.len()))
+                .copy_from_slice(&bytes[..]),
             Change::Context(Previous(value)) => self.context = value.clone(),
-            Change::PanicContext(Previous(value)) => self.panic_context = value.clone(),
+            Change::PanicContext(Previous(value)) => self.panic_context = *value,
             Change::Txn(Previous(tx)) => {
                 self.tx = AsRef::<dyn AnyDebug>::as_ref(tx)
                     .as_any_ref()
@@ -468,6 +483,180 @@ fn invert_receipts_ctx(ctx: &mut ReceiptsCtx, value: &VecState<Option<Receipt>>)
     invert_vec(ctx_mut.receipts_mut(), value);
 }
 
+/// A debugger step recorded to be reverted.
+#[cfg(feature = "debug")]
+#[derive(Debug, Clone)]
+pub(crate) struct RecordedStep {
+    /// Changes reverting the step.
+    pub diff: Diff<InitialVmState>,
+    /// State the VM was interrupted in before the step.
+    pub last_state: ProgramState,
+    /// Amount of storage changes recorded before the step.
+    pub storage_deltas: usize,
+}
+
+#[cfg(feature = "debug")]
+impl RecordedStep {
+    /// Approximate amount of bytes held by the step.
+    ///
+    /// Besides the changes themselves, only the memory and contract code they
+    /// hold are counted, since they are the only ones of unbounded size.
+    pub fn size(&self) -> usize {
+        self.diff
+            .changes
+            .iter()
+            .map(|change| {
+                let held = match change {
+                    Change::Memory(Previous(memory)) => memory.bytes.len(),
+                    Change::Storage(Previous(StorageState::RawCode(code))) => code
+                        .value
+                        .as_ref()
+                        .map(|code| code.as_ref().len())
+                        .unwrap_or_default(),
+                    _ => 0,
+                };
+
+                std::mem::size_of::<Change<InitialVmState>>() + held
+            })
+            .sum()
+    }
+}
+
+/// VM state captured before a debugger step, see [`Interpreter::capture_step`].
+#[cfg(feature = "debug")]
+pub(crate) struct StepCapture<Tx> {
+    registers: [Word; VM_REGISTER_COUNT],
+    frames: Vec<CallFrame>,
+    receipts: usize,
+    balances: RuntimeBalances,
+    context: Context,
+    panic_context: PanicContext,
+    tx: Tx,
+    storage_deltas: usize,
+    memory: WriteCheckpoint,
+}
+
+#[cfg(feature = "debug")]
+impl<S, Tx> Interpreter<Record<S>, Tx>
+where
+    S: InterpreterStorage,
+    Tx: ExecutableTransaction + PartialEq + Debug + 'static,
+{
+    /// Capture the state required to revert the next step, and start recording
+    /// the memory writes.
+    ///
+    /// Memory and storage are not copied: their changes are recorded while
+    /// stepping.
+    pub(crate) fn capture_step(&mut self) -> StepCapture<Tx> {
+        StepCapture {
+            registers: self.registers,
+            frames: self.frames.clone(),
+            receipts: self.receipts.as_ref().len(),
+            balances: self.balances.clone(),
+            context: self.context.clone(),
+            panic_context: self.panic_context,
+            tx: self.tx.clone(),
+            storage_deltas: self.storage.1.len(),
+            memory: self.memory.write_checkpoint(),
+        }
+    }
+
+    /// Diff of the changes performed since the state was captured.
+    pub(crate) fn step_diff(&mut self, capture: StepCapture<Tx>) -> Diff<Deltas> {
+        let mut diff = Diff {
+            changes: Vec::new(),
+        };
+        let registers = capture_buffer_state(
+            capture.registers.iter(),
+            self.registers.iter(),
+            Change::Register,
+        );
+        diff.changes.extend(registers);
+        let frames =
+            capture_vec_state(capture.frames.iter(), self.frames.iter(), Change::Frame);
+        diff.changes.extend(frames);
+
+        // Receipts are only ever appended
+        let receipts = self
+            .receipts
+            .as_ref()
+            .iter()
+            .enumerate()
+            .skip(capture.receipts)
+            .map(|(index, receipt)| {
+                Change::Receipt(Delta {
+                    from: VecState { index, value: None },
+                    to: VecState {
+                        index,
+                        value: Some(receipt.clone()),
+                    },
+                })
+            });
+        diff.changes.extend(receipts);
+        let balances = capture_map_state(
+            capture.balances.as_ref(),
+            self.balances.as_ref(),
+            Change::Balance,
+        );
+        diff.changes.extend(balances);
+
+        // Memory and storage changes are inverted in the reverse order they were
+        // applied, so overlapping changes restore the oldest value.
+        let memory = self.memory.writes_since(capture.memory);
+        let memory = memory.iter().rev().map(|write| {
+            let range = write.range();
+            Change::Memory(Delta {
+                from: Memory {
+                    start: range.start,
+                    bytes: write.previous().to_vec(),
+                },
+                to: Memory {
+                    start: range.start,
+                    bytes: self.memory.read(range).into_owned(),
+                },
+            })
+        });
+        diff.changes.extend(memory);
+        let storage = self.storage.1[capture.storage_deltas..]
+            .iter()
+            .rev()
+            .map(storage_delta_to_change);
+        diff.changes.extend(storage);
+
+        if capture.context != self.context {
+            diff.changes.push(Change::Context(Delta {
+                from: capture.context,
+                to: self.context.clone(),
+            }))
+        }
+
+        if capture.panic_context != self.panic_context {
+            diff.changes.push(Change::PanicContext(Delta {
+                from: capture.panic_context,
+                to: self.panic_context,
+            }))
+        }
+
+        if capture.tx != self.tx {
+            let from: Arc<dyn AnyDebug> = Arc::new(capture.tx);
+            let to: Arc<dyn AnyDebug> = Arc::new(self.tx.clone());
+            diff.changes.push(Change::Txn(Delta { from, to }))
+        }
+
+        diff
+    }
+
+    /// Amount of storage changes recorded so far.
+    pub(crate) fn storage_deltas(&self) -> usize {
+        self.storage.1.len()
+    }
+
+    /// Discard the storage changes recorded after the first `len` ones.
+    pub(crate) fn truncate_storage_deltas(&mut self, len: usize) {
+        self.storage.1.truncate(len);
+    }
+}
+
 impl<S, Tx> PartialEq for Interpreter<S, Tx>
 where
     Tx: PartialEq,
@@ -483,7 +672,10 @@ where
             && self.context == other.context
             && self.balances == other.balances
             && self.gas_costs == other.gas_costs
+            && self.opcode_policy == other.opcode_policy
             && self.params == other.params
+            && self.schedule == other.schedule
+            && self.yielded == other.yielded
             && self.panic_context == other.panic_context
     }
 }
@@ -1,4 +1,5 @@
 use std::{
+    borrow::Cow,
     collections::HashMap,
     fmt::Debug,
 };
@@ -14,6 +15,7 @@ use fuel_types::{
 };
 
 use crate::storage::{
+    state_keys,
     ContractsAssetKey,
     ContractsAssetsStorage,
     ContractsStateKey,
@@ -26,7 +28,7 @@ use super::{
     *,
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 /// The set of state changes that are recorded.
 pub(super) enum StorageDelta {
     State(MappableDelta<ContractsStateKey, Bytes32>),
@@ -44,7 +46,7 @@ pub(super) enum StorageState {
     RawCode(MappableState<ContractId, Contract>),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 /// A [`Mappable`] type that has changed.
 pub(super) enum MappableDelta<Key, Value> {
     Insert(Key, Value, Option<Value>),
@@ -71,7 +73,8 @@ pub(super) trait StorageType: Mappable {
     fn record_remove(key: &Self::Key, value: Self::OwnedValue) -> StorageDelta;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+/// Storage wrapper recording every change performed on the underlying storage.
 pub struct Record<S>(pub(super) S, pub(super) Vec<StorageDelta>)
 where
     S: InterpreterStorage;
@@ -97,9 +100,17 @@ where
             context: self.context,
             balances: self.balances,
             gas_costs: self.gas_costs,
+            opcode_policy: self.opcode_policy,
+            ecal: self.ecal,
             params: self.params,
+            schedule: self.schedule,
+            yielded: self.yielded,
             panic_context: self.panic_context,
             profiler: self.profiler,
+            #[cfg(feature = "trace")]
+            tracer: self.tracer,
+            read_write_set: self.read_write_set,
+            state_diff: self.state_diff,
         }
     }
 
@@ -171,9 +182,17 @@ where
             context: self.context,
             balances: self.balances,
             gas_costs: self.gas_costs,
+            opcode_policy: self.opcode_policy,
+            ecal: self.ecal,
             params: self.params,
+            schedule: self.schedule,
+            yielded: self.yielded,
             panic_context: self.panic_context,
             profiler: self.profiler,
+            #[cfg(feature = "trace")]
+            tracer: self.tracer,
+            read_write_set: self.read_write_set,
+            state_diff: self.state_diff,
         }
     }
 
@@ -186,8 +205,8 @@ where
             self.inverse_inner(change);
             if let Change::Storage(Previous(from)) = change {
                 match from {
-                    StorageState::State(MappableState { key, value }) => {
-                        if let Some(value) = value {
+                    StorageState::State(MappableState { key, value }) => match value {
+                        Some(value) => {
                             StorageMutate::<ContractsState>::insert(
                                 &mut self.storage,
                                 key,
@@ -195,9 +214,16 @@ where
                             )
                             .unwrap();
                         }
-                    }
-                    StorageState::Assets(MappableState { key, value }) => {
-                        if let Some(value) = value {
+                        None => {
+                            StorageMutate::<ContractsState>::remove(
+                                &mut self.storage,
+                                key,
+                            )
+                            .unwrap();
+                        }
+                    },
+                    StorageState::Assets(MappableState { key, value }) => match value {
+                        Some(value) => {
                             StorageMutate::<ContractsAssets>::insert(
                                 &mut self.storage,
                                 key,
@@ -205,9 +231,16 @@ where
                             )
                             .unwrap();
                         }
-                    }
-                    StorageState::Info(MappableState { key, value }) => {
-                        if let Some(value) = value {
+                        None => {
+                            StorageMutate::<ContractsAssets>::remove(
+                                &mut self.storage,
+                                key,
+                            )
+                            .unwrap();
+                        }
+                    },
+                    StorageState::Info(MappableState { key, value }) => match value {
+                        Some(value) => {
                             StorageMutate::<ContractsInfo>::insert(
                                 &mut self.storage,
                                 key,
@@ -215,9 +248,16 @@ where
                             )
                             .unwrap();
                         }
-                    }
-                    StorageState::RawCode(MappableState { key, value }) => {
-                        if let Some(value) = value {
+                        None => {
+                            StorageMutate::<ContractsInfo>::remove(
+                                &mut self.storage,
+                                key,
+                            )
+                            .unwrap();
+                        }
+                    },
+                    StorageState::RawCode(MappableState { key, value }) => match value {
+                        Some(value) => {
                             StorageMutate::<ContractsRawCode>::insert(
                                 &mut self.storage,
                                 key,
@@ -225,7 +265,14 @@ where
                             )
                             .unwrap();
                         }
-                    }
+                        None => {
+                            StorageMutate::<ContractsRawCode>::remove(
+                                &mut self.storage,
+                                key,
+                            )
+                            .unwrap();
+                        }
+                    },
                 }
             }
         }
@@ -254,6 +301,45 @@ fn mappable_delta_to_hashmap<'value, K, V>(
     }
 }
 
+/// Change inverted by reverting a single recorded storage change.
+#[cfg(feature = "debug")]
+pub(super) fn storage_delta_to_change(delta: &StorageDelta) -> Change<Deltas> {
+    match delta {
+        StorageDelta::State(delta) => {
+            mappable_delta_to_change(delta, StorageState::State)
+        }
+        StorageDelta::Assets(delta) => {
+            mappable_delta_to_change(delta, StorageState::Assets)
+        }
+        StorageDelta::Info(delta) => mappable_delta_to_change(delta, StorageState::Info),
+        StorageDelta::RawCode(delta) => {
+            mappable_delta_to_change(delta, StorageState::RawCode)
+        }
+    }
+}
+
+#[cfg(feature = "debug")]
+fn mappable_delta_to_change<K, V>(
+    delta: &MappableDelta<K, V>,
+    f: fn(MappableState<K, V>) -> StorageState,
+) -> Change<Deltas>
+where
+    K: Copy,
+    V: Clone,
+{
+    let (key, from, to) = match delta {
+        MappableDelta::Insert(key, value, existing) => {
+            (*key, existing.clone(), Some(value.clone()))
+        }
+        MappableDelta::Remove(key, existing) => (*key, Some(existing.clone()), None),
+    };
+
+    Change::Storage(Delta {
+        from: f(MappableState { key, value: from }),
+        to: f(MappableState { key, value: to }),
+    })
+}
+
 fn storage_state_to_changes<K, V>(
     diff: &mut Diff<Deltas>,
     state: Delta<HashMap<K, &V>>,
@@ -430,8 +516,31 @@ where
         start_key: &Bytes32,
         values: &[Bytes32],
     ) -> Result<Option<()>, Self::DataError> {
-        self.0
-            .merkle_contract_state_insert_range(contract, start_key, values)
+        let existing = self.0.merkle_contract_state_range(
+            contract,
+            start_key,
+            values.len() as Word,
+        )?;
+        let existing: Vec<_> = existing
+            .into_iter()
+            .map(|v| v.map(Cow::into_owned))
+            .collect();
+        let result = self
+            .0
+            .merkle_contract_state_insert_range(contract, start_key, values)?;
+
+        let deltas = state_keys(start_key).zip(values).zip(existing).map(
+            |((key, value), existing)| {
+                StorageDelta::State(MappableDelta::Insert(
+                    (contract, &key).into(),
+                    *value,
+                    existing,
+                ))
+            },
+        );
+        self.1.extend(deltas);
+
+        Ok(result)
     }
 
     fn merkle_contract_state_remove_range(
@@ -440,8 +549,38 @@ where
         start_key: &Bytes32,
         range: Word,
     ) -> Result<Option<()>, Self::DataError> {
-        self.0
-            .merkle_contract_state_remove_range(contract, start_key, range)
+        let existing = self
+            .0
+            .merkle_contract_state_range(contract, start_key, range)?;
+        let existing: Vec<_> = existing
+            .into_iter()
+            .map(|v| v.map(Cow::into_owned))
+            .collect();
+        let result = self
+            .0
+            .merkle_contract_state_remove_range(contract, start_key, range)?;
+
+        let deltas = state_keys(start_key)
+            .zip(existing)
+            .filter_map(|(key, existing)| {
+                existing.map(|existing| {
+                    StorageDelta::State(MappableDelta::Remove(
+                        (contract, &key).into(),
+                        existing,
+                    ))
+                })
+            });
+        self.1.extend(deltas);
+
+        Ok(result)
+    }
+
+    fn take_read_write_set(&mut self) -> Option<crate::storage::ReadWriteSet> {
+        self.0.take_read_write_set()
+    }
+
+    fn take_changes(&mut self) -> Option<crate::storage::RecordedChanges> {
+        self.0.take_changes()
     }
 }
 
@@ -508,6 +647,7 @@ impl<S> Record<S>
 where
     S: InterpreterStorage,
 {
+    /// Wrap the storage, recording its changes from now on.
     pub fn new(s: S) -> Self {
         Self(s, Vec::new())
     }
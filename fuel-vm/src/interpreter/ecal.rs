@@ -0,0 +1,177 @@
+//! Host functions called with the `ECAL` instruction.
+//!
+//! `ECAL` is reserved for embedders of the VM and isn't part of the consensus
+//! rules. Unless an [`EcalHandler`] is set with
+//! [`Interpreter::with_ecal_handler`], it fails like an unknown instruction.
+
+#[cfg(test)]
+mod tests;
+
+use super::{
+    gas::{
+        gas_charge,
+        ProfileGas,
+    },
+    internal::inc_pc,
+    memory::{
+        try_mem_write,
+        OwnershipRegisters,
+    },
+    Interpreter,
+    Memory,
+    MemoryRange,
+};
+use crate::{
+    call::CallFrame,
+    constraints::reg_key::split_registers,
+    consts::*,
+    error::RuntimeError,
+    profiler::Profiler,
+};
+
+use dyn_clone::DynClone;
+use fuel_asm::{
+    PanicReason,
+    RegId,
+};
+use fuel_types::{
+    ContractId,
+    Word,
+};
+
+use std::{
+    borrow::Cow,
+    fmt,
+};
+
+/// Host function executed by the `ECAL` instruction.
+pub trait EcalHandler: DynClone {
+    /// Execute `ECAL` with the registers `a`, `b`, `c` and `d`.
+    ///
+    /// The gas used by the call must be charged with
+    /// [`EcalContext::gas_charge`]. An error panics the VM.
+    fn ecal(
+        &mut self,
+        vm: &mut EcalContext<'_>,
+        a: RegId,
+        b: RegId,
+        c: RegId,
+        d: RegId,
+    ) -> Result<(), RuntimeError>;
+}
+
+dyn_clone::clone_trait_object!(EcalHandler);
+
+/// Access to the VM from an [`EcalHandler`].
+pub struct EcalContext<'a> {
+    registers: &'a mut [Word; VM_REGISTER_COUNT],
+    memory: &'a mut Memory,
+    ownership: OwnershipRegisters,
+    frames: &'a [CallFrame],
+    profiler: &'a mut Profiler,
+    current_contract: Option<ContractId>,
+}
+
+impl<'a> EcalContext<'a> {
+    /// The registers of the VM.
+    pub fn registers(&self) -> &[Word] {
+        self.registers
+    }
+
+    /// Value of the register `id`.
+    pub fn register(&self, id: RegId) -> Word {
+        self.registers[id]
+    }
+
+    /// Set the value of the writable register `id`.
+    pub fn set_register(&mut self, id: RegId, value: Word) -> Result<(), RuntimeError> {
+        if id < RegId::WRITABLE {
+            return Err(PanicReason::ReservedRegisterNotWritable.into())
+        }
+
+        self.registers[id] = value;
+        Ok(())
+    }
+
+    /// The memory of the VM.
+    pub fn memory(&self) -> &Memory {
+        self.memory
+    }
+
+    /// Read `len` bytes of memory at `addr`.
+    pub fn read(&self, addr: Word, len: Word) -> Result<Cow<'_, [u8]>, RuntimeError> {
+        Ok(MemoryRange::new(addr, len)?.read(self.memory))
+    }
+
+    /// Write `data` to the memory at `addr`, which must be owned by the current
+    /// context.
+    pub fn write(&mut self, addr: Word, data: &[u8]) -> Result<(), RuntimeError> {
+        try_mem_write(addr, data, self.ownership.clone(), self.memory)
+    }
+
+    /// Charge `gas` to the current context.
+    pub fn gas_charge(&mut self, gas: Word) -> Result<(), RuntimeError> {
+        let (registers, _) = split_registers(self.registers);
+        let profiler = ProfileGas {
+            pc: registers.pc.as_ref(),
+            is: registers.is.as_ref(),
+            current_contract: self.current_contract,
+            frames: self.frames,
+            profiler: self.profiler,
+        };
+        gas_charge(registers.cgas, registers.ggas, profiler, gas)
+    }
+}
+
+/// Optional [`EcalHandler`] of an interpreter.
+#[derive(Default, Clone)]
+pub(crate) struct EcalSlot(Option<Box<dyn EcalHandler + Send + Sync>>);
+
+impl EcalSlot {
+    pub(crate) fn new<H>(handler: H) -> Self
+    where
+        H: EcalHandler + Send + Sync + 'static,
+    {
+        Self(Some(Box::new(handler)))
+    }
+
+    pub(crate) fn is_set(&self) -> bool {
+        self.0.is_some()
+    }
+}
+
+impl fmt::Debug for EcalSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(_) => write!(f, "EcalSlot(enabled)"),
+            None => write!(f, "EcalSlot(disabled)"),
+        }
+    }
+}
+
+impl<S, Tx> Interpreter<S, Tx> {
+    pub(crate) fn external_call(
+        &mut self,
+        a: RegId,
+        b: RegId,
+        c: RegId,
+        d: RegId,
+    ) -> Result<(), RuntimeError> {
+        let mut handler = self.ecal.0.take().ok_or(PanicReason::ErrorFlag)?;
+
+        let mut vm = EcalContext {
+            ownership: OwnershipRegisters::new(self),
+            current_contract: self.contract_id(),
+            registers: &mut self.registers,
+            memory: &mut self.memory,
+            frames: &self.frames,
+            profiler: &mut self.profiler,
+        };
+        let result = handler.ecal(&mut vm, a, b, c, d);
+        self.ecal = EcalSlot(Some(handler));
+        result?;
+
+        let (registers, _) = split_registers(&mut self.registers);
+        inc_pc(registers.pc)
+    }
+}
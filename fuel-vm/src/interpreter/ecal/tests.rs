@@ -0,0 +1,148 @@
+use fuel_asm::{
+    op,
+    PanicReason,
+    RegId,
+};
+use fuel_tx::{
+    ConsensusParameters,
+    Finalizable,
+    Receipt,
+    Script,
+    TransactionBuilder,
+};
+use fuel_types::Word;
+
+use crate::{
+    checked_transaction::IntoChecked,
+    prelude::*,
+};
+
+/// Sets `$a` to `$b + $c` and writes `$b * $c` at `$d`.
+#[derive(Clone)]
+struct Arithmetic {
+    gas: Word,
+}
+
+impl EcalHandler for Arithmetic {
+    fn ecal(
+        &mut self,
+        vm: &mut EcalContext<'_>,
+        a: RegId,
+        b: RegId,
+        c: RegId,
+        d: RegId,
+    ) -> Result<(), RuntimeError> {
+        vm.gas_charge(self.gas)?;
+        let (b, c, d) = (vm.register(b), vm.register(c), vm.register(d));
+        vm.set_register(a, b + c)?;
+        vm.write(d, &(b * c).to_be_bytes())
+    }
+}
+
+fn transact(vm: &mut Interpreter<MemoryStorage, Script>) -> Vec<Receipt> {
+    let script = vec![
+        op::movi(0x11, 3),
+        op::movi(0x12, 4),
+        op::move_(0x13, RegId::SP),
+        op::cfei(8),
+        op::ecal(0x10, 0x11, 0x12, 0x13),
+        op::lw(0x14, 0x13, 0),
+        op::log(0x10, 0x14, RegId::ZERO, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ];
+    let tx = TransactionBuilder::script(script.into_iter().collect(), vec![])
+        .gas_limit(1_000_000)
+        .add_random_fee_input()
+        .finalize()
+        .into_checked(
+            Default::default(),
+            &ConsensusParameters::default(),
+            vm.gas_costs(),
+        )
+        .expect("failed to generate checked tx");
+
+    vm.transact(tx)
+        .expect("Failed to transact")
+        .receipts()
+        .to_vec()
+}
+
+fn panic_reason(receipts: &[Receipt]) -> Option<PanicReason> {
+    receipts.iter().find_map(|receipt| match receipt {
+        Receipt::Panic { reason, .. } => Some(*reason.reason()),
+        _ => None,
+    })
+}
+
+fn gas_used(receipts: &[Receipt]) -> Word {
+    receipts
+        .iter()
+        .find_map(|receipt| match receipt {
+            Receipt::ScriptResult { gas_used, .. } => Some(*gas_used),
+            _ => None,
+        })
+        .expect("Missing script result")
+}
+
+#[test]
+fn ecal_is_disabled_by_default() {
+    let mut vm = Interpreter::with_memory_storage();
+    let receipts = transact(&mut vm);
+
+    assert_eq!(panic_reason(&receipts), Some(PanicReason::ErrorFlag));
+}
+
+#[test]
+fn ecal_handler_accesses_registers_and_memory() {
+    let mut vm = Interpreter::with_memory_storage();
+    vm.with_ecal_handler(Arithmetic { gas: 0 });
+    let receipts = transact(&mut vm);
+
+    assert_eq!(panic_reason(&receipts), None);
+    assert!(matches!(receipts[0], Receipt::Log { ra: 7, rb: 12, .. }));
+}
+
+#[test]
+fn ecal_handler_charges_gas() {
+    let mut vm = Interpreter::with_memory_storage();
+    vm.with_ecal_handler(Arithmetic { gas: 0 });
+    let free = gas_used(&transact(&mut vm));
+
+    vm.with_ecal_handler(Arithmetic { gas: 100 });
+    let charged = gas_used(&transact(&mut vm));
+    assert_eq!(charged, free + 100);
+
+    vm.with_ecal_handler(Arithmetic { gas: Word::MAX });
+    assert_eq!(
+        panic_reason(&transact(&mut vm)),
+        Some(PanicReason::OutOfGas)
+    );
+}
+
+#[test]
+fn ecal_handler_cannot_write_reserved_registers() {
+    #[derive(Clone)]
+    struct SetPc;
+
+    impl EcalHandler for SetPc {
+        fn ecal(
+            &mut self,
+            vm: &mut EcalContext<'_>,
+            _: RegId,
+            _: RegId,
+            _: RegId,
+            _: RegId,
+        ) -> Result<(), RuntimeError> {
+            vm.set_register(RegId::PC, 0)
+        }
+    }
+
+    let mut vm = Interpreter::with_memory_storage();
+    vm.with_ecal_handler(SetPc);
+    let receipts = transact(&mut vm);
+
+    assert_eq!(
+        panic_reason(&receipts),
+        Some(PanicReason::ReservedRegisterNotWritable)
+    );
+}
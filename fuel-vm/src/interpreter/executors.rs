@@ -1,6 +1,10 @@
+mod budget;
+mod gas_limit;
 mod instruction;
 mod main;
 mod predicate;
 
 #[cfg(feature = "debug")]
 mod debug;
+
+pub use gas_limit::GasLimitEstimate;
@@ -0,0 +1,169 @@
+//! Execution bounded by an instruction budget.
+//!
+//! A script can be executed in slices of a bounded number of instructions, so
+//! the caller regains control between them. The slices don't change the
+//! outcome of the transaction, nor its gas usage.
+
+#[cfg(test)]
+mod tests;
+
+use crate::{
+    checked_transaction::{
+        Checked,
+        IntoChecked,
+    },
+    error::InterpreterError,
+    interpreter::{
+        CheckedMetadata,
+        ExecutableTransaction,
+        Interpreter,
+    },
+    state::{
+        ExecutionSlice,
+        ProgramState,
+        StateTransitionRef,
+    },
+    storage::InterpreterStorage,
+};
+
+use fuel_types::Word;
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{
+        Context,
+        Poll,
+    },
+};
+
+impl<S, Tx> Interpreter<S, Tx>
+where
+    S: InterpreterStorage,
+    Tx: ExecutableTransaction,
+    <Tx as IntoChecked>::Metadata: CheckedMetadata,
+{
+    /// Initialize the interpreter with the provided transaction and execute at
+    /// most `budget` instructions of its script.
+    ///
+    /// If the budget is exhausted first, the execution yields and continues
+    /// with [`Interpreter::resume_bounded`]. `Create` transactions are always
+    /// executed at once.
+    pub fn transact_bounded(
+        &mut self,
+        tx: Checked<Tx>,
+        budget: Word,
+    ) -> Result<ExecutionSlice<StateTransitionRef<'_, Tx>>, InterpreterError> {
+        let result = self.init_script(tx).and_then(|_| self.run_bounded(budget));
+        self.slice(result)
+    }
+
+    /// Continue a yielded execution for at most `budget` instructions.
+    ///
+    /// Fails with [`InterpreterError::NotYielded`] if the last execution didn't
+    /// yield.
+    pub fn resume_bounded(
+        &mut self,
+        budget: Word,
+    ) -> Result<ExecutionSlice<StateTransitionRef<'_, Tx>>, InterpreterError> {
+        if !self.yielded {
+            return Err(InterpreterError::NotYielded)
+        }
+
+        let result = self.continue_bounded(budget);
+        self.slice(result)
+    }
+
+    /// Initialize the interpreter with the provided transaction and execute it
+    /// in slices of at most `slice` instructions, yielding to the async
+    /// executor between them.
+    ///
+    /// The result is the same as [`Interpreter::transact`].
+    pub async fn transact_async(
+        &mut self,
+        tx: Checked<Tx>,
+        slice: Word,
+    ) -> Result<StateTransitionRef<'_, Tx>, InterpreterError> {
+        let slice = slice.max(1);
+        let mut result = self.init_script(tx).and_then(|_| self.run_bounded(slice));
+
+        let state = loop {
+            match result.transpose() {
+                Some(result) => break self.complete_transaction(result)?,
+                None => {
+                    YieldNow(false).await;
+                    result = self.continue_bounded(slice);
+                }
+            }
+        };
+
+        Ok(
+            StateTransitionRef::new(state, self.transaction(), self.receipts())
+                .with_read_write_set(self.read_write_set.as_ref())
+                .with_state_diff(self.state_diff.as_ref()),
+        )
+    }
+
+    /// Start the execution of the initialized transaction, yielding with `None`
+    /// once `budget` instructions were executed.
+    fn run_bounded(
+        &mut self,
+        budget: Word,
+    ) -> Result<Option<ProgramState>, InterpreterError> {
+        if self.tx.as_create().is_some() || self.is_script_empty() {
+            return self.run().map(Some)
+        }
+
+        self.start_script()?;
+        self.continue_bounded(budget)
+    }
+
+    fn continue_bounded(
+        &mut self,
+        budget: Word,
+    ) -> Result<Option<ProgramState>, InterpreterError> {
+        let program = match self.run_program_bounded(Some(budget)).transpose() {
+            Some(program) => program,
+            None => {
+                self.yielded = true;
+                return Ok(None)
+            }
+        };
+
+        self.yielded = false;
+        self.finish_script(program).map(Some)
+    }
+
+    fn slice(
+        &mut self,
+        result: Result<Option<ProgramState>, InterpreterError>,
+    ) -> Result<ExecutionSlice<StateTransitionRef<'_, Tx>>, InterpreterError> {
+        let state = match result.transpose() {
+            Some(result) => self.complete_transaction(result)?,
+            None => return Ok(ExecutionSlice::Yielded),
+        };
+
+        Ok(ExecutionSlice::Completed(
+            StateTransitionRef::new(state, self.transaction(), self.receipts())
+                .with_read_write_set(self.read_write_set.as_ref())
+                .with_state_diff(self.state_diff.as_ref()),
+        ))
+    }
+}
+
+/// Future that is pending once, to let the executor schedule other tasks.
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            return Poll::Ready(())
+        }
+
+        self.0 = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
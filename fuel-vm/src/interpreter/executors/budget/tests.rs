@@ -0,0 +1,106 @@
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::{
+    ConsensusParameters,
+    Finalizable,
+    Receipt,
+    Script,
+    TransactionBuilder,
+};
+
+use crate::{
+    checked_transaction::{
+        Checked,
+        IntoChecked,
+    },
+    prelude::*,
+};
+
+/// Script looping 10 times over 2 instructions.
+fn tx(gas_costs: &GasCosts) -> Checked<Script> {
+    let script = vec![
+        op::movi(0x10, 10),
+        op::addi(0x11, 0x11, 1),
+        op::jnei(0x10, 0x11, 1),
+        op::log(0x11, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::ret(0x11),
+    ];
+    TransactionBuilder::script(script.into_iter().collect(), vec![])
+        .gas_limit(1_000_000)
+        .add_random_fee_input()
+        .finalize()
+        .into_checked(
+            Default::default(),
+            &ConsensusParameters::default(),
+            gas_costs,
+        )
+        .expect("failed to generate checked tx")
+}
+
+fn expected_receipts() -> Vec<Receipt> {
+    let mut vm = Interpreter::<_, Script>::with_memory_storage();
+    let tx = tx(vm.gas_costs());
+    vm.transact(tx)
+        .expect("Failed to transact")
+        .receipts()
+        .to_vec()
+}
+
+#[test]
+fn bounded_execution_yields_until_completion() {
+    let mut vm = Interpreter::<_, Script>::with_memory_storage();
+    let tx = tx(vm.gas_costs());
+
+    let mut slices = 1;
+    let mut slice = vm.transact_bounded(tx, 5).expect("Failed to transact");
+    while slice.is_yielded() {
+        slices += 1;
+        slice = vm.resume_bounded(5).expect("Failed to resume");
+    }
+    let transition = slice.completed().expect("Expected a completed execution");
+
+    // 23 instructions, in slices of 5
+    assert_eq!(slices, 5);
+    assert_eq!(transition.state(), &ProgramState::Return(10));
+    assert_eq!(transition.receipts(), expected_receipts().as_slice());
+
+    assert_eq!(
+        vm.resume_bounded(5).err(),
+        Some(InterpreterError::NotYielded)
+    );
+}
+
+#[test]
+fn transaction_restarts_after_a_yield() {
+    let mut vm = Interpreter::<_, Script>::with_memory_storage();
+
+    let slice = vm
+        .transact_bounded(tx(vm.gas_costs()), 3)
+        .expect("Failed to transact");
+    assert!(slice.is_yielded());
+
+    let receipts = vm
+        .transact(tx(vm.gas_costs()))
+        .expect("Failed to transact")
+        .receipts()
+        .to_vec();
+    assert_eq!(receipts, expected_receipts());
+    assert_eq!(
+        vm.resume_bounded(5).err(),
+        Some(InterpreterError::NotYielded)
+    );
+}
+
+#[test]
+fn async_execution_matches_transact() {
+    let mut vm = Interpreter::<_, Script>::with_memory_storage();
+    let tx = tx(vm.gas_costs());
+
+    let receipts = futures::executor::block_on(vm.transact_async(tx, 2))
+        .expect("Failed to transact")
+        .receipts()
+        .to_vec();
+    assert_eq!(receipts, expected_receipts());
+}
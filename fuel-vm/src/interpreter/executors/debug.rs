@@ -1,6 +1,10 @@
 use crate::{
     error::InterpreterError,
     interpreter::{
+        diff::{
+            Record,
+            RecordedStep,
+        },
         ExecutableTransaction,
         Interpreter,
     },
@@ -8,13 +12,23 @@ use crate::{
     storage::InterpreterStorage,
 };
 
+use std::fmt::Debug;
+
 impl<S, Tx> Interpreter<S, Tx>
 where
     S: InterpreterStorage,
     Tx: ExecutableTransaction,
 {
     /// Continue the execution from a previously interrupted program flow.
+    ///
+    /// The execution isn't recorded, so this discards the steps recorded by
+    /// [`Interpreter::step_forward`].
     pub fn resume(&mut self) -> Result<ProgramState, InterpreterError> {
+        self.debugger.clear_history();
+        self.resume_inner()
+    }
+
+    fn resume_inner(&mut self) -> Result<ProgramState, InterpreterError> {
         let state = self
             .debugger_last_state()
             .ok_or(InterpreterError::DebugStateNotInitialized)?;
@@ -38,3 +52,74 @@ where
         Ok(state)
     }
 }
+
+impl<S, Tx> Interpreter<Record<S>, Tx>
+where
+    S: InterpreterStorage,
+    Tx: ExecutableTransaction + PartialEq + Debug + 'static,
+{
+    /// Continue the execution like [`Interpreter::resume`], recording the
+    /// changes to the VM state so the step can be reverted with
+    /// [`Interpreter::step_back`].
+    ///
+    /// At most [`crate::state::Debugger::history_limit`] steps, holding at most
+    /// [`crate::state::Debugger::history_bytes_limit`] bytes, are kept, the
+    /// oldest ones being discarded first. With single-stepping enabled, a step
+    /// is a single instruction.
+    pub fn step_forward(&mut self) -> Result<ProgramState, InterpreterError> {
+        let last_state = self
+            .debugger_last_state()
+            .ok_or(InterpreterError::DebugStateNotInitialized)?;
+
+        if self.debugger.history_limit() == 0 {
+            return self.resume_inner()
+        }
+
+        let storage_deltas = self.storage_deltas();
+        let capture = self.capture_step();
+        let result = self.resume_inner();
+        let diff = self.step_diff(capture).into();
+
+        self.debugger.push_history(RecordedStep {
+            diff,
+            last_state,
+            storage_deltas,
+        });
+
+        result
+    }
+
+    /// Revert the last step recorded by [`Interpreter::step_forward`], restoring
+    /// the exact VM state and storage prior to it.
+    ///
+    /// Returns the state the VM was interrupted in before the step, which is
+    /// the state resumed by the next step; or `None` if there is no step left
+    /// to revert.
+    pub fn step_back(&mut self) -> Option<ProgramState> {
+        let step = self.debugger.pop_history()?;
+
+        self.reset_vm_state(&step.diff);
+        self.truncate_storage_deltas(step.storage_deltas);
+        self.debugger_set_last_state(step.last_state);
+
+        Some(step.last_state)
+    }
+
+    /// Revert the recorded steps until a breakpoint is reached, or no step is
+    /// left to revert.
+    ///
+    /// Returns the state the VM was interrupted in, or `None` if there was no
+    /// step to revert.
+    pub fn resume_backwards(&mut self) -> Option<ProgramState> {
+        let mut state = self.step_back()?;
+
+        while !self.debugger.is_breakpoint(&state) {
+            match self.step_back() {
+                Some(previous) => state = previous,
+                None => break,
+            }
+        }
+
+        Some(state)
+    }
+}
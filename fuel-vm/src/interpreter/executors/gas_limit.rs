@@ -0,0 +1,147 @@
+use crate::{
+    checked_transaction::IntoChecked,
+    error::InterpreterError,
+    gas::GasCosts,
+    interpreter::Interpreter,
+    storage::{
+        InterpreterStorage,
+        StateOverrides,
+    },
+};
+
+use fuel_tx::{
+    field::{
+        GasLimit,
+        GasPrice,
+    },
+    Chargeable,
+    CheckError,
+    ConsensusParameters,
+    Script,
+    TransactionFee,
+};
+use fuel_types::{
+    BlockHeight,
+    Word,
+};
+
+/// Gas limit recommended for a script transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasLimitEstimate {
+    gas_limit: Word,
+    gas_used: Word,
+    fee: TransactionFee,
+}
+
+impl GasLimitEstimate {
+    /// Minimal gas limit with which the transaction succeeds, including the gas
+    /// used by its predicates.
+    pub const fn gas_limit(&self) -> Word {
+        self.gas_limit
+    }
+
+    /// Gas used by the script execution.
+    pub const fn gas_used(&self) -> Word {
+        self.gas_used
+    }
+
+    /// Fee of the transaction with the recommended gas limit.
+    pub const fn fee(&self) -> &TransactionFee {
+        &self.fee
+    }
+}
+
+impl<S> Interpreter<S, Script>
+where
+    S: InterpreterStorage,
+{
+    /// Estimate the minimal gas limit with which `transaction` succeeds against
+    /// `storage`, and set it in the transaction.
+    ///
+    /// The transaction is first executed with the maximal gas limit to measure the
+    /// gas it uses. Since a call forwards gas to the called contract, the script may
+    /// need more than that; the limit is then searched between the gas used and the
+    /// maximum, assuming a transaction that succeeds with a limit also succeeds with
+    /// any higher one.
+    ///
+    /// The executions ignore the gas price, so the inputs don't have to cover the
+    /// fee of the maximal gas limit, and their writes never reach `storage`.
+    /// Signatures aren't checked, and the gas used by predicates is the one set in
+    /// the inputs, e.g. by [`crate::checked_transaction::EstimatePredicates`].
+    pub fn estimate_gas_limit(
+        transaction: &mut Script,
+        storage: &mut S,
+        params: ConsensusParameters,
+        gas_costs: GasCosts,
+    ) -> Result<GasLimitEstimate, InterpreterError> {
+        let block_height = storage.block_height().map_err(InterpreterError::from_io)?;
+        let predicates_gas = transaction.gas_used_by_predicates();
+        let max_gas = params.max_gas_per_tx.saturating_sub(predicates_gas);
+
+        let mut execute = |gas_limit| {
+            Self::execute_with_gas_limit(
+                transaction,
+                gas_limit,
+                storage,
+                params,
+                &gas_costs,
+                block_height,
+            )
+        };
+
+        let gas_used = execute(max_gas)?.ok_or(InterpreterError::GasEstimationFailed)?;
+
+        // `high` always succeeds, `low` is the first limit that may
+        let (mut low, mut high) = (gas_used, max_gas);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if execute(mid)?.is_some() {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+
+        *transaction.gas_limit_mut() = high + predicates_gas;
+        let fee = TransactionFee::checked_from_tx(&params, transaction)
+            .ok_or(CheckError::ArithmeticOverflow)?;
+
+        Ok(GasLimitEstimate {
+            gas_limit: high + predicates_gas,
+            gas_used,
+            fee,
+        })
+    }
+
+    /// Execute `transaction` with `gas_limit` and a zero gas price, returning the
+    /// gas used if it succeeds.
+    fn execute_with_gas_limit(
+        transaction: &Script,
+        gas_limit: Word,
+        storage: &mut S,
+        params: ConsensusParameters,
+        gas_costs: &GasCosts,
+        block_height: BlockHeight,
+    ) -> Result<Option<Word>, InterpreterError> {
+        let mut transaction = transaction.clone();
+        *transaction.gas_limit_mut() = gas_limit;
+        *transaction.gas_price_mut() = 0;
+        let checked = transaction.into_checked_basic(block_height, &params)?;
+
+        let mut vm = Interpreter::with_storage(
+            StateOverrides::new(&mut *storage),
+            params,
+            gas_costs.clone(),
+        );
+        let state = vm.transact(checked)?;
+
+        if state.should_revert() {
+            return Ok(None)
+        }
+
+        Ok(state
+            .receipts()
+            .iter()
+            .find_map(|receipt| receipt.gas_used()))
+    }
+}
@@ -10,6 +10,7 @@ use crate::{
             JumpArgs,
             JumpMode,
         },
+        panic_context::clear_recorded_panic_context,
         ExecutableTransaction,
         Interpreter,
     },
@@ -35,11 +36,22 @@ where
 {
     /// Execute the current instruction pair located in `$m[$pc]`.
     pub fn execute(&mut self) -> Result<ExecuteState, InterpreterError> {
-        // Safety: `chunks_exact` is guaranteed to return a well-formed slice
-        let [hi, lo] = self.memory[self.registers[RegId::PC] as usize..]
-            .chunks_exact(WORD_SIZE)
-            .next()
-            .map(|b| b.try_into().expect("Has to be correct size slice"))
+        self.execute_pair(true).map(|(state, _)| state)
+    }
+
+    /// Execute the current instruction pair located in `$m[$pc]`, or only its
+    /// first instruction if `pair` is `false`.
+    ///
+    /// Returns the amount of executed instructions with the resulting state.
+    pub(crate) fn execute_pair(
+        &mut self,
+        pair: bool,
+    ) -> Result<(ExecuteState, Word), InterpreterError> {
+        let pc = self.registers[RegId::PC] as usize;
+        let [hi, lo] = pc
+            .checked_add(WORD_SIZE)
+            .filter(|end| *end <= MEM_SIZE)
+            .map(|_| self.memory.read_bytes(pc))
             .map(Word::from_be_bytes)
             .map(fuel_asm::raw_instructions_from_word)
             .ok_or(InterpreterError::Panic(PanicReason::MemoryOverflow))?;
@@ -52,10 +64,10 @@ where
         // Should execute `lo` only if there is no rupture in the flow - that means
         // either a breakpoint or some instruction that would skip `lo` such as
         // `RET`, `JI` or `CALL`
-        if self.registers[RegId::PC] == pc && state.should_continue() {
-            self.instruction(lo)
+        if pair && self.registers[RegId::PC] == pc && state.should_continue() {
+            self.instruction(lo).map(|state| (state, 2))
         } else {
-            Ok(state)
+            Ok((state, 1))
         }
     }
 
@@ -72,8 +84,63 @@ where
             }
         }
 
-        self.instruction_inner(raw.into())
-            .map_err(|e| InterpreterError::from_runtime(e, raw.into()))
+        #[cfg(feature = "debug")]
+        let watch = self.watch_begin(raw.into());
+
+        #[cfg(feature = "trace")]
+        let trace = self.trace_begin(raw.into());
+
+        #[cfg(any(feature = "debug", feature = "trace"))]
+        let checkpoint = self
+            .wants_memory_writes()
+            .then(|| self.memory.write_checkpoint());
+
+        clear_recorded_panic_context();
+        let result = self.instruction_inner(raw.into());
+        if let Err(RuntimeError::Recoverable(reason)) = &result {
+            self.record_panic_context(*reason);
+        }
+        let result = result.map_err(|e| InterpreterError::from_runtime(e, raw.into()));
+
+        #[cfg(any(feature = "debug", feature = "trace"))]
+        let memory_writes = checkpoint
+            .map(|checkpoint| self.memory.writes_since(checkpoint))
+            .unwrap_or_default();
+
+        #[cfg(feature = "trace")]
+        if let Some(step) = trace {
+            self.trace_end(step, &memory_writes, &result);
+        }
+
+        #[cfg(feature = "debug")]
+        if let Some(step) = watch {
+            return result.map(|state| self.watch_end(step, &memory_writes, state))
+        }
+
+        result
+    }
+
+    /// Check if the memory writes of the instruction must be recorded for the
+    /// watchpoints or the tracer.
+    #[cfg(any(feature = "debug", feature = "trace"))]
+    fn wants_memory_writes(&self) -> bool {
+        let mut wants = false;
+
+        #[cfg(feature = "debug")]
+        {
+            wants |= self
+                .debugger
+                .watchpoints()
+                .iter()
+                .any(|w| matches!(w, crate::state::Watchpoint::Memory { .. }));
+        }
+
+        #[cfg(feature = "trace")]
+        {
+            wants |= self.tracer.is_enabled();
+        }
+
+        wants
     }
 
     fn instruction_inner(
@@ -83,12 +150,25 @@ where
         let instruction = Instruction::try_from(raw)
             .map_err(|_| RuntimeError::from(PanicReason::ErrorFlag))?;
 
+        // Without a handler, `ECAL` is an unknown instruction like before its
+        // introduction
+        if matches!(instruction, Instruction::ECAL(_)) && !self.ecal.is_set() {
+            return Err(PanicReason::ErrorFlag.into())
+        }
+
         // TODO additional branch that might be optimized after
         // https://github.com/FuelLabs/fuel-asm/issues/68
         if self.is_predicate() && !instruction.opcode().is_predicate_allowed() {
             return Err(PanicReason::ContractInstructionNotAllowed.into())
         }
 
+        if !self
+            .opcode_policy
+            .is_allowed(&self.context, instruction.opcode())
+        {
+            return Err(PanicReason::InstructionNotAllowed.into())
+        }
+
         // Short-hand for retrieving the value from the register with the given ID.
         // We use a macro to "close over" `self.registers` without taking ownership of it.
         macro_rules! r {
@@ -858,6 +938,11 @@ where
                 let (a, b, c, d) = tro.unpack();
                 self.transfer_output(r!(a), r!(b), r!(c), r!(d))?;
             }
+
+            Instruction::ECAL(ecal) => {
+                let (a, b, c, d) = ecal.unpack();
+                self.external_call(a, b, c, d)?;
+            }
         }
 
         Ok(ExecuteState::Proceed)
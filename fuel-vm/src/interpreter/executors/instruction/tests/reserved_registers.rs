@@ -203,6 +203,7 @@ fn writes_to_ra(opcode: Opcode) -> bool {
         Opcode::TIME => true,
         Opcode::CFE => false,
         Opcode::CFS => false,
+        Opcode::ECAL => false,
     }
 }
 
@@ -313,5 +314,6 @@ fn writes_to_rb(opcode: Opcode) -> bool {
         Opcode::TIME => false,
         Opcode::CFE => false,
         Opcode::CFS => false,
+        Opcode::ECAL => false,
     }
 }
@@ -24,10 +24,12 @@ use crate::{
         Interpreter,
         RuntimeBalances,
     },
+    policy::OpcodePolicy,
     predicate::RuntimePredicate,
     state::{
         ExecuteState,
         ProgramState,
+        StateDiff,
         StateTransition,
         StateTransitionRef,
     },
@@ -117,13 +119,39 @@ impl<T> Interpreter<PredicateStorage, T> {
         params: ConsensusParameters,
         gas_costs: GasCosts,
     ) -> Result<PredicatesChecked, PredicateVerificationFailed>
+    where
+        Tx: ExecutableTransaction,
+        <Tx as IntoChecked>::Metadata: CheckedMetadata,
+    {
+        Self::check_predicates_with_policy(
+            checked,
+            params,
+            gas_costs,
+            OpcodePolicy::default(),
+        )
+    }
+
+    /// Initialize the VM with the provided transaction and check all predicates defined
+    /// in the inputs, denying the opcodes of `policy` on top of the consensus rules.
+    pub fn check_predicates_with_policy<Tx>(
+        checked: &Checked<Tx>,
+        params: ConsensusParameters,
+        gas_costs: GasCosts,
+        policy: OpcodePolicy,
+    ) -> Result<PredicatesChecked, PredicateVerificationFailed>
     where
         Tx: ExecutableTransaction,
         <Tx as IntoChecked>::Metadata: CheckedMetadata,
     {
         let tx = checked.transaction();
         let balances = checked.metadata().balances();
-        Self::run_predicate(PredicateRunKind::Verifying(tx), balances, params, gas_costs)
+        Self::run_predicate(
+            PredicateRunKind::Verifying(tx),
+            balances,
+            params,
+            gas_costs,
+            policy,
+        )
     }
 
     /// Initialize the VM with the provided transaction and check all predicates defined
@@ -136,6 +164,29 @@ impl<T> Interpreter<PredicateStorage, T> {
         params: ConsensusParameters,
         gas_costs: GasCosts,
     ) -> Result<PredicatesChecked, PredicateVerificationFailed>
+    where
+        Tx: ExecutableTransaction + Send + 'static,
+        <Tx as IntoChecked>::Metadata: CheckedMetadata,
+        E: ParallelExecutor,
+    {
+        Self::check_predicates_async_with_policy::<Tx, E>(
+            checked,
+            params,
+            gas_costs,
+            OpcodePolicy::default(),
+        )
+        .await
+    }
+
+    /// Initialize the VM with the provided transaction and check all predicates defined
+    /// in the inputs in parallel, denying the opcodes of `policy` on top of the
+    /// consensus rules.
+    pub async fn check_predicates_async_with_policy<Tx, E>(
+        checked: &Checked<Tx>,
+        params: ConsensusParameters,
+        gas_costs: GasCosts,
+        policy: OpcodePolicy,
+    ) -> Result<PredicatesChecked, PredicateVerificationFailed>
     where
         Tx: ExecutableTransaction + Send + 'static,
         <Tx as IntoChecked>::Metadata: CheckedMetadata,
@@ -149,6 +200,7 @@ impl<T> Interpreter<PredicateStorage, T> {
             balances,
             params,
             gas_costs,
+            policy,
         )
         .await?;
 
@@ -167,6 +219,29 @@ impl<T> Interpreter<PredicateStorage, T> {
         params: ConsensusParameters,
         gas_costs: GasCosts,
     ) -> Result<(), PredicateVerificationFailed>
+    where
+        Tx: ExecutableTransaction,
+    {
+        Self::estimate_predicates_with_policy(
+            transaction,
+            balances,
+            params,
+            gas_costs,
+            OpcodePolicy::default(),
+        )
+    }
+
+    /// Initialize the VM with the provided transaction, check all predicates defined in
+    /// the inputs and set the predicate_gas_used to be the actual gas consumed during
+    /// execution for each predicate, denying the opcodes of `policy` on top of the
+    /// consensus rules.
+    pub fn estimate_predicates_with_policy<Tx>(
+        transaction: &mut Tx,
+        balances: InitialBalances,
+        params: ConsensusParameters,
+        gas_costs: GasCosts,
+        policy: OpcodePolicy,
+    ) -> Result<(), PredicateVerificationFailed>
     where
         Tx: ExecutableTransaction,
     {
@@ -175,6 +250,7 @@ impl<T> Interpreter<PredicateStorage, T> {
             balances,
             params,
             gas_costs,
+            policy,
         )?;
         Ok(())
     }
@@ -191,6 +267,31 @@ impl<T> Interpreter<PredicateStorage, T> {
         params: ConsensusParameters,
         gas_costs: GasCosts,
     ) -> Result<(), PredicateVerificationFailed>
+    where
+        Tx: ExecutableTransaction + Send + 'static,
+        E: ParallelExecutor,
+    {
+        Self::estimate_predicates_async_with_policy::<Tx, E>(
+            transaction,
+            balances,
+            params,
+            gas_costs,
+            OpcodePolicy::default(),
+        )
+        .await
+    }
+
+    /// Initialize the VM with the provided transaction, check all predicates defined in
+    /// the inputs and set the predicate_gas_used to be the actual gas consumed during
+    /// execution for each predicate in parallel, denying the opcodes of `policy` on top
+    /// of the consensus rules.
+    pub async fn estimate_predicates_async_with_policy<Tx, E>(
+        transaction: &mut Tx,
+        balances: InitialBalances,
+        params: ConsensusParameters,
+        gas_costs: GasCosts,
+        policy: OpcodePolicy,
+    ) -> Result<(), PredicateVerificationFailed>
     where
         Tx: ExecutableTransaction + Send + 'static,
         E: ParallelExecutor,
@@ -200,6 +301,7 @@ impl<T> Interpreter<PredicateStorage, T> {
             balances,
             params,
             gas_costs,
+            policy,
         )
         .await?;
 
@@ -211,6 +313,7 @@ impl<T> Interpreter<PredicateStorage, T> {
         balances: InitialBalances,
         params: ConsensusParameters,
         gas_costs: GasCosts,
+        policy: OpcodePolicy,
     ) -> Result<PredicatesChecked, PredicateVerificationFailed>
     where
         Tx: ExecutableTransaction + Send + 'static,
@@ -232,6 +335,7 @@ impl<T> Interpreter<PredicateStorage, T> {
                         index,
                         params,
                         gas_costs,
+                        policy,
                         balances,
                         predicate_action,
                         predicate,
@@ -252,6 +356,7 @@ impl<T> Interpreter<PredicateStorage, T> {
         balances: InitialBalances,
         params: ConsensusParameters,
         gas_costs: GasCosts,
+        policy: OpcodePolicy,
     ) -> Result<PredicatesChecked, PredicateVerificationFailed>
     where
         Tx: ExecutableTransaction,
@@ -271,6 +376,7 @@ impl<T> Interpreter<PredicateStorage, T> {
                     index,
                     params,
                     gas_costs,
+                    policy,
                     balances,
                     predicate_action,
                     predicate,
@@ -281,11 +387,13 @@ impl<T> Interpreter<PredicateStorage, T> {
         Self::finalize_check_predicate(kind, checks, predicate_action, params)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn check_predicate<Tx>(
         tx: Tx,
         index: usize,
         params: ConsensusParameters,
         gas_costs: GasCosts,
+        policy: OpcodePolicy,
         balances: InitialBalances,
         predicate_action: PredicateAction,
         predicate: RuntimePredicate,
@@ -319,6 +427,7 @@ impl<T> Interpreter<PredicateStorage, T> {
 
         let mut vm =
             Interpreter::with_storage(PredicateStorage::default(), params, gas_costs);
+        vm.with_opcode_policy(policy);
 
         let available_gas = match predicate_action {
             PredicateAction::Verifying => {
@@ -514,36 +623,11 @@ where
             self.update_transaction_outputs()?;
             ProgramState::Return(1)
         } else {
-            if self.transaction().inputs().iter().any(|input| {
-                if let Input::Contract(contract) = input {
-                    !self
-                        .check_contract_exists(&contract.contract_id)
-                        .unwrap_or(false)
-                } else {
-                    false
-                }
-            }) {
-                return Err(InterpreterError::Panic(PanicReason::ContractNotFound))
-            }
-
-            if let Some(script) = self.transaction().as_script() {
-                let offset = (self.tx_offset() + script.script_offset()) as Word;
-
-                self.registers[RegId::PC] = offset;
-                self.registers[RegId::IS] = offset;
-            }
-
-            // TODO set tree balance
+            self.start_script()?;
 
             // `Interpreter` supports only `Create` and `Script` transactions. It is not
             // `Create` -> it is `Script`.
-            let program = if !self
-                .transaction()
-                .as_script()
-                .expect("It should be `Script` transaction")
-                .script()
-                .is_empty()
-            {
+            let program = if !self.is_script_empty() {
                 self.run_program()
             } else {
                 // Return `1` as successful execution.
@@ -552,71 +636,131 @@ where
                 Ok(ProgramState::Return(return_val))
             };
 
-            let gas_used = self
-                .transaction()
-                .limit()
-                .checked_sub(self.remaining_gas())
-                .ok_or_else(|| Bug::new(BugId::ID002, BugVariant::GlobalGasUnderflow))?;
-
-            // Catch VM panic and don't propagate, generating a receipt
-            let (status, program) = match program {
-                Ok(s) => {
-                    // either a revert or success
-                    let res = if let ProgramState::Revert(_) = &s {
-                        ScriptExecutionResult::Revert
-                    } else {
-                        ScriptExecutionResult::Success
-                    };
-                    (res, s)
-                }
+            self.finish_script(program)?
+        };
 
-                Err(e) => match e.instruction_result() {
-                    Some(result) => {
-                        self.append_panic_receipt(result);
+        Ok(state)
+    }
 
-                        (ScriptExecutionResult::Panic, ProgramState::Revert(0))
-                    }
+    /// Check the contract inputs and point `$pc` to the script.
+    pub(crate) fn start_script(&mut self) -> Result<(), InterpreterError> {
+        if self.transaction().inputs().iter().any(|input| {
+            if let Input::Contract(contract) = input {
+                !self
+                    .check_contract_exists(&contract.contract_id)
+                    .unwrap_or(false)
+            } else {
+                false
+            }
+        }) {
+            return Err(InterpreterError::Panic(PanicReason::ContractNotFound))
+        }
 
-                    // This isn't a specified case of an erroneous program and should be
-                    // propagated. If applicable, OS errors will fall into this category.
-                    None => return Err(e),
-                },
-            };
+        if let Some(script) = self.transaction().as_script() {
+            let offset = (self.tx_offset() + script.script_offset()) as Word;
 
-            let receipt = Receipt::script_result(status, gas_used);
+            self.registers[RegId::PC] = offset;
+            self.registers[RegId::IS] = offset;
+        }
 
-            self.append_receipt(receipt);
+        // TODO set tree balance
 
-            #[cfg(feature = "debug")]
-            if program.is_debug() {
-                self.debugger_set_last_state(program);
-            }
+        Ok(())
+    }
+
+    /// Whether the transaction is a script without instructions.
+    pub(crate) fn is_script_empty(&self) -> bool {
+        self.transaction()
+            .as_script()
+            .expect("It should be `Script` transaction")
+            .script()
+            .is_empty()
+    }
 
-            if let Some(script) = self.tx.as_script_mut() {
-                let receipts_root = self.receipts.root();
-                *script.receipts_root_mut() = receipts_root;
+    /// Append the script result and finalize the outputs of the transaction.
+    pub(crate) fn finish_script(
+        &mut self,
+        program: Result<ProgramState, InterpreterError>,
+    ) -> Result<ProgramState, InterpreterError> {
+        let gas_used = self
+            .transaction()
+            .limit()
+            .checked_sub(self.remaining_gas())
+            .ok_or_else(|| Bug::new(BugId::ID002, BugVariant::GlobalGasUnderflow))?;
+
+        // Catch VM panic and don't propagate, generating a receipt
+        let (status, program) = match program {
+            Ok(s) => {
+                // either a revert or success
+                let res = if let ProgramState::Revert(_) = &s {
+                    ScriptExecutionResult::Revert
+                } else {
+                    ScriptExecutionResult::Success
+                };
+                (res, s)
             }
 
-            let revert = matches!(program, ProgramState::Revert(_));
-            let remaining_gas = self.remaining_gas();
-            Self::finalize_outputs(
-                &mut self.tx,
-                revert,
-                remaining_gas,
-                &self.initial_balances,
-                &self.balances,
-                &self.params,
-            )?;
-            self.update_transaction_outputs()?;
+            Err(e) => match e.instruction_result() {
+                Some(result) => {
+                    self.append_panic_receipt(result);
+
+                    (ScriptExecutionResult::Panic, ProgramState::Revert(0))
+                }
 
-            program
+                // This isn't a specified case of an erroneous program and should be
+                // propagated. If applicable, OS errors will fall into this category.
+                None => return Err(e),
+            },
         };
 
-        Ok(state)
+        let receipt = Receipt::script_result(status, gas_used);
+
+        self.append_receipt(receipt);
+
+        #[cfg(feature = "debug")]
+        if program.is_debug() {
+            self.debugger_set_last_state(program);
+        }
+
+        if let Some(script) = self.tx.as_script_mut() {
+            let receipts_root = self.receipts.root();
+            *script.receipts_root_mut() = receipts_root;
+        }
+
+        let revert = matches!(program, ProgramState::Revert(_));
+        let remaining_gas = self.remaining_gas();
+        Self::finalize_outputs(
+            &mut self.tx,
+            revert,
+            remaining_gas,
+            &self.initial_balances,
+            &self.balances,
+            &self.params,
+        )?;
+        self.update_transaction_outputs()?;
+
+        Ok(program)
     }
 
     pub(crate) fn run_program(&mut self) -> Result<ProgramState, InterpreterError> {
+        self.run_program_bounded(None)
+            .map(|state| state.expect("Only a bounded execution yields"))
+    }
+
+    /// Execute the program, yielding with `None` once `budget` instructions were
+    /// executed.
+    ///
+    /// Without a budget, the instructions are executed by pairs, see
+    /// [`Interpreter::execute`].
+    pub(crate) fn run_program_bounded(
+        &mut self,
+        mut budget: Option<Word>,
+    ) -> Result<Option<ProgramState>, InterpreterError> {
         loop {
+            if budget == Some(0) {
+                return Ok(None)
+            }
+
             if self.registers[RegId::PC] >= VM_MAX_RAM {
                 return Err(InterpreterError::Panic(PanicReason::MemoryOverflow))
             }
@@ -624,25 +768,44 @@ where
             // Check whether the instruction will be executed in a call context
             let in_call = !self.frames.is_empty();
 
-            let state = self.execute()?;
+            let state = match &mut budget {
+                Some(budget) => {
+                    let (state, executed) = self.execute_pair(*budget > 1)?;
+                    *budget -= executed;
+                    state
+                }
+                None => self.execute()?,
+            };
 
             if in_call {
-                // Only reverts should terminate execution from a call context
-                if let ExecuteState::Revert(r) = state {
-                    return Ok(ProgramState::Revert(r))
+                // Only reverts and debug events should terminate execution from a call
+                // context
+                match state {
+                    ExecuteState::Revert(r) => return Ok(Some(ProgramState::Revert(r))),
+
+                    #[cfg(feature = "debug")]
+                    ExecuteState::DebugEvent(d) => {
+                        return Ok(Some(ProgramState::RunProgram(d)))
+                    }
+
+                    _ => (),
                 }
             } else {
                 match state {
-                    ExecuteState::Return(r) => return Ok(ProgramState::Return(r)),
+                    ExecuteState::Return(r) => return Ok(Some(ProgramState::Return(r))),
 
-                    ExecuteState::ReturnData(d) => return Ok(ProgramState::ReturnData(d)),
+                    ExecuteState::ReturnData(d) => {
+                        return Ok(Some(ProgramState::ReturnData(d)))
+                    }
 
-                    ExecuteState::Revert(r) => return Ok(ProgramState::Revert(r)),
+                    ExecuteState::Revert(r) => return Ok(Some(ProgramState::Revert(r))),
 
                     ExecuteState::Proceed => (),
 
                     #[cfg(feature = "debug")]
-                    ExecuteState::DebugEvent(d) => return Ok(ProgramState::RunProgram(d)),
+                    ExecuteState::DebugEvent(d) => {
+                        return Ok(Some(ProgramState::RunProgram(d)))
+                    }
                 }
             }
         }
@@ -670,6 +833,8 @@ where
             .map(ProgramState::from)
             .map(|state| {
                 StateTransition::new(state, interpreter.tx, interpreter.receipts.into())
+                    .with_read_write_set(interpreter.read_write_set)
+                    .with_state_diff(interpreter.state_diff)
             })
     }
 
@@ -682,16 +847,43 @@ where
         tx: Checked<Tx>,
     ) -> Result<StateTransitionRef<'_, Tx>, InterpreterError> {
         let state_result = self.init_script(tx).and_then(|_| self.run());
+        let state = self.complete_transaction(state_result)?;
+        Ok(
+            StateTransitionRef::new(state, self.transaction(), self.receipts())
+                .with_read_write_set(self.read_write_set.as_ref())
+                .with_state_diff(self.state_diff.as_ref()),
+        )
+    }
+
+    /// Collect the storage accesses and changes, and notify the profiler and the receipt
+    /// sink once the transaction execution is over.
+    pub(crate) fn complete_transaction(
+        &mut self,
+        state_result: Result<ProgramState, InterpreterError>,
+    ) -> Result<ProgramState, InterpreterError> {
+        let reverted = state_result.is_err()
+            || self
+                .receipts()
+                .iter()
+                .any(|r| matches!(r, Receipt::Revert { .. } | Receipt::Panic { .. }));
+
+        self.read_write_set = self.storage.take_read_write_set();
+        self.state_diff = self.storage.take_changes().map(|changes| {
+            StateDiff::new(
+                &changes,
+                &self.tx.id(&self.params.chain_id),
+                self.tx.outputs(),
+                self.receipts(),
+                reverted,
+            )
+        });
+
+        self.receipts.complete(reverted);
 
         #[cfg(feature = "profile-any")]
         self.profiler.on_transaction(&state_result);
 
-        let state = state_result?;
-        Ok(StateTransitionRef::new(
-            state,
-            self.transaction(),
-            self.receipts(),
-        ))
+        state_result
     }
 }
 
@@ -704,6 +896,12 @@ where
     ///
     /// Returns `Create` transaction with all modifications after execution.
     pub fn deploy(&mut self, tx: Checked<Create>) -> Result<Create, InterpreterError> {
+        let block_height = self
+            .storage
+            .block_height()
+            .map_err(InterpreterError::from_io)?;
+        self.apply_schedule(block_height);
+
         let (mut create, metadata) = tx.into();
         Self::deploy_inner(
             &mut create,
@@ -17,8 +17,10 @@ use super::{
         set_frame_pointer,
         AppendReceipt,
     },
+    panic_context::memory_panic,
     ExecutableTransaction,
     Interpreter,
+    Memory,
     MemoryRange,
     RuntimeBalances,
 };
@@ -39,7 +41,6 @@ use crate::{
     interpreter::{
         receipts::ReceiptsCtx,
         InputContracts,
-        PanicContext,
     },
     profiler::Profiler,
     storage::{
@@ -73,7 +74,10 @@ use fuel_types::{
     ContractId,
     Word,
 };
-use std::cmp;
+use std::{
+    cmp,
+    mem,
+};
 
 #[cfg(test)]
 mod jump_tests;
@@ -93,8 +97,7 @@ where
 
     pub(crate) fn ret(&mut self, a: Word) -> Result<(), RuntimeError> {
         let current_contract =
-            current_contract(&self.context, self.registers.fp(), self.memory.as_ref())?
-                .copied();
+            current_contract(&self.context, self.registers.fp(), &self.memory)?;
         let input = RetCtx {
             append: AppendReceipt {
                 receipts: &mut self.receipts,
@@ -112,8 +115,7 @@ where
 
     pub(crate) fn ret_data(&mut self, a: Word, b: Word) -> Result<Bytes32, RuntimeError> {
         let current_contract =
-            current_contract(&self.context, self.registers.fp(), self.memory.as_ref())?
-                .copied();
+            current_contract(&self.context, self.registers.fp(), &self.memory)?;
         let input = RetCtx {
             append: AppendReceipt {
                 receipts: &mut self.receipts,
@@ -131,8 +133,8 @@ where
 
     pub(crate) fn revert(&mut self, a: Word) {
         let current_contract =
-            current_contract(&self.context, self.registers.fp(), self.memory.as_ref())
-                .map_or_else(|_| Some(ContractId::zeroed()), Option::<&_>::copied);
+            current_contract(&self.context, self.registers.fp(), &self.memory)
+                .unwrap_or(Some(ContractId::zeroed()));
         let append = AppendReceipt {
             receipts: &mut self.receipts,
             script: self.tx.as_script_mut(),
@@ -152,16 +154,8 @@ where
         let pc = self.registers[RegId::PC];
         let is = self.registers[RegId::IS];
 
-        let mut receipt =
-            Receipt::panic(self.internal_contract_or_default(), result, pc, is);
-
-        match self.panic_context {
-            PanicContext::None => {}
-            PanicContext::ContractId(contract_id) => {
-                receipt = receipt.with_panic_contract_id(Some(contract_id));
-            }
-        };
-        self.panic_context = PanicContext::None;
+        let receipt = Receipt::panic(self.internal_contract_or_default(), result, pc, is)
+            .with_panic_context(mem::take(&mut self.panic_context));
 
         self.append_receipt(receipt);
     }
@@ -225,7 +219,7 @@ impl RetCtx<'_> {
 
     pub(crate) fn ret_data(self, a: Word, b: Word) -> Result<Bytes32, RuntimeError> {
         if b > MEM_MAX_ACCESS_SIZE || a > VM_MAX_RAM - b {
-            return Err(PanicReason::MemoryOverflow.into())
+            return Err(memory_panic(PanicReason::MemoryOverflow, a, b))
         }
 
         let ab = (a + b) as usize;
@@ -235,7 +229,7 @@ impl RetCtx<'_> {
             a,
             self.registers[RegId::PC],
             self.registers[RegId::IS],
-            self.append.memory[a as usize..ab].to_vec(),
+            self.append.memory.read(a as usize..ab).into_owned(),
         );
         let digest = *receipt
             .digest()
@@ -387,9 +381,8 @@ where
             amount_of_gas_to_forward,
         };
         let current_contract =
-            current_contract(&self.context, self.registers.fp(), self.memory.as_ref())?
-                .copied();
-        let memory = PrepareCallMemory::try_from((self.memory.as_mut(), &params))?;
+            current_contract(&self.context, self.registers.fp(), &self.memory)?;
+        let memory = PrepareCallMemory::try_from((&mut self.memory, &params))?;
         let input_contracts = self.tx.input_contracts().copied().collect::<Vec<_>>();
 
         PrepareCallCtx {
@@ -462,7 +455,7 @@ impl<'a> PrepareCallRegisters<'a> {
 }
 
 struct PrepareCallMemory<'a> {
-    memory: &'a mut [u8; MEM_SIZE],
+    memory: &'a mut Memory,
     call_params: CheckedMemValue<Call>,
     asset_id: CheckedMemValue<AssetId>,
 }
@@ -511,6 +504,7 @@ where
             pc: self.registers.system_registers.pc.as_ref(),
             is: self.registers.system_registers.is.as_ref(),
             current_contract: self.current_contract,
+            frames: self.frames,
             profiler: self.profiler,
         };
         dependent_gas_charge(
@@ -567,7 +561,11 @@ where
             || *self.registers.system_registers.sp
                 > *self.registers.system_registers.hp - len
         {
-            return Err(PanicReason::MemoryOverflow.into())
+            return Err(memory_panic(
+                PanicReason::MemoryOverflow,
+                *self.registers.system_registers.sp,
+                len,
+            ))
         }
         let id = internal_contract_or_default(
             self.context,
@@ -632,7 +630,7 @@ fn write_call_to_memory<S>(
     frame: &CallFrame,
     frame_bytes: Vec<u8>,
     code_mem_range: MemoryRange,
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     storage: &S,
 ) -> Result<Word, RuntimeError>
 where
@@ -756,13 +754,11 @@ impl<'reg> From<SystemRegisters<'reg>>
     }
 }
 
-impl<'mem> TryFrom<(&'mem mut [u8; MEM_SIZE], &PrepareCallParams)>
-    for PrepareCallMemory<'mem>
-{
+impl<'mem> TryFrom<(&'mem mut Memory, &PrepareCallParams)> for PrepareCallMemory<'mem> {
     type Error = RuntimeError;
 
     fn try_from(
-        (memory, params): (&'mem mut [u8; MEM_SIZE], &PrepareCallParams),
+        (memory, params): (&'mem mut Memory, &PrepareCallParams),
     ) -> Result<Self, Self::Error> {
         Ok(Self {
             memory,
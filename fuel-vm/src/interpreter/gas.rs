@@ -1,6 +1,10 @@
-use super::Interpreter;
+use super::{
+    Interpreter,
+    PanicContext,
+};
 use crate::{
     arith,
+    call::CallFrame,
     constraints::reg_key::*,
     error::RuntimeError,
     gas::DependentCost,
@@ -51,9 +55,21 @@ impl<S, Tx> Interpreter<S, Tx> {
             pc: pc.as_ref(),
             is: is.as_ref(),
             current_contract,
+            frames: &self.frames,
             profiler: &mut self.profiler,
         };
-        dependent_gas_charge(cgas, ggas, profiler, gas_cost, arg)
+        let available = *cgas;
+        let result = dependent_gas_charge(cgas, ggas, profiler, gas_cost, arg);
+        if matches!(
+            result,
+            Err(RuntimeError::Recoverable(PanicReason::OutOfGas))
+        ) {
+            self.panic_context = PanicContext::Values {
+                expected: dependent_cost(gas_cost, arg),
+                actual: available,
+            };
+        }
+        result
     }
 
     /// Do a gas charge with the given amount, panicing when running out of gas.
@@ -67,9 +83,21 @@ impl<S, Tx> Interpreter<S, Tx> {
             pc: pc.as_ref(),
             is: is.as_ref(),
             current_contract,
+            frames: &self.frames,
             profiler: &mut self.profiler,
         };
-        gas_charge(cgas, ggas, profiler, gas)
+        let available = *cgas;
+        let result = gas_charge(cgas, ggas, profiler, gas);
+        if matches!(
+            result,
+            Err(RuntimeError::Recoverable(PanicReason::OutOfGas))
+        ) {
+            self.panic_context = PanicContext::Values {
+                expected: gas,
+                actual: available,
+            };
+        }
+        result
     }
 }
 
@@ -95,12 +123,16 @@ fn dependent_gas_charge_inner(
     gas_cost: DependentCost,
     arg: Word,
 ) -> Result<Word, RuntimeError> {
-    let cost = gas_cost
-        .base
-        .saturating_add(arg.saturating_mul(gas_cost.dep_per_unit));
+    let cost = dependent_cost(gas_cost, arg);
     gas_charge_inner(cgas, ggas, cost).map(|_| cost)
 }
 
+fn dependent_cost(gas_cost: DependentCost, arg: Word) -> Word {
+    gas_cost
+        .base
+        .saturating_add(arg.saturating_mul(gas_cost.dep_per_unit))
+}
+
 pub(crate) fn gas_charge(
     cgas: RegMut<CGAS>,
     ggas: RegMut<GGAS>,
@@ -136,6 +168,7 @@ pub(crate) struct ProfileGas<'a> {
     pub pc: Reg<'a, PC>,
     pub is: Reg<'a, IS>,
     pub current_contract: Option<ContractId>,
+    pub frames: &'a [CallFrame],
     pub profiler: &'a mut Profiler,
 }
 
@@ -155,6 +188,10 @@ impl<'a> ProfileGas<'a> {
             let location =
                 super::current_location(self.current_contract, self.pc, self.is);
             self.profiler.add_gas(location, gas_use);
+            self.profiler.add_call_stack_gas(
+                super::current_call_stack(self.frames, location),
+                gas_use,
+            );
         }
     }
 }
@@ -2,6 +2,7 @@ use super::{
     ExecutableTransaction,
     InitialBalances,
     Interpreter,
+    PanicContext,
     RuntimeBalances,
 };
 use crate::{
@@ -45,6 +46,14 @@ where
 
         self.frames.clear();
         self.receipts.clear();
+        self.yielded = false;
+        self.panic_context = PanicContext::None;
+
+        #[cfg(feature = "debug")]
+        self.debugger.clear_history();
+
+        #[cfg(feature = "trace")]
+        self.tracer.clear();
 
         // Optimized for memset
         self.registers.iter_mut().for_each(|r| *r = 0);
@@ -113,6 +122,14 @@ where
             .map_err(InterpreterError::from_io)?;
 
         self.context = Context::Script { block_height };
+        self.apply_schedule(block_height);
+
+        // Discard the accesses and changes made before the transaction, e.g. by
+        // deployments
+        self.storage.take_read_write_set();
+        self.read_write_set = None;
+        self.storage.take_changes();
+        self.state_diff = None;
 
         let gas_used_by_predicates = checked.metadata().gas_used_by_predicates();
         let (mut tx, metadata): (Tx, Tx::Metadata) = checked.into();
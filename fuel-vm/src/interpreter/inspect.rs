@@ -0,0 +1,166 @@
+//! Read-only inspection of the call frames and the memory of an interpreter.
+//!
+//! Debuggers and tracers can use it instead of decoding the memory layout.
+
+#[cfg(test)]
+mod tests;
+
+use super::{
+    memory::OwnershipRegisters,
+    Interpreter,
+    MemoryRange,
+};
+use crate::{
+    call::CallFrame,
+    consts::*,
+    error::RuntimeError,
+};
+
+use fuel_asm::{
+    PanicReason,
+    RegId,
+};
+use fuel_types::{
+    AssetId,
+    Bytes32,
+    ContractId,
+    Word,
+};
+
+use std::borrow::Cow;
+
+/// Call frame of the current execution, with its location in memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameInfo<'a> {
+    frame: &'a CallFrame,
+    context_gas: Word,
+}
+
+impl<'a> FrameInfo<'a> {
+    /// The call frame.
+    pub const fn frame(&self) -> &'a CallFrame {
+        self.frame
+    }
+
+    /// Called contract id.
+    pub const fn contract_id(&self) -> &'a ContractId {
+        self.frame.to()
+    }
+
+    /// Asset ID of the forwarded coins.
+    pub const fn asset_id(&self) -> &'a AssetId {
+        self.frame.asset_id()
+    }
+
+    /// Remaining gas of the call context.
+    pub const fn context_gas(&self) -> Word {
+        self.context_gas
+    }
+
+    /// Registers of the caller, restored when the call returns.
+    pub const fn saved_registers(&self) -> &'a [Word] {
+        self.frame.registers()
+    }
+
+    /// Address of the call frame, which is `$fp` of the call context.
+    pub fn address(&self) -> Word {
+        self.frame.registers()[RegId::SP]
+    }
+
+    /// Memory range of the contract code, following the call frame.
+    pub fn code_range(&self) -> MemoryRange {
+        let offset = self.address() + CallFrame::serialized_size() as Word;
+        MemoryRange::new(offset, self.frame.code_size()).unwrap_or_default()
+    }
+}
+
+impl<S, Tx> Interpreter<S, Tx> {
+    /// Call frames of the current execution, outermost first.
+    pub fn frames(&self) -> impl Iterator<Item = FrameInfo<'_>> + '_ {
+        let callee_registers = self
+            .frames
+            .iter()
+            .skip(1)
+            .map(CallFrame::registers)
+            .chain(Some(&self.registers[..]));
+
+        self.frames
+            .iter()
+            .zip(callee_registers)
+            .map(|(frame, registers)| FrameInfo {
+                frame,
+                context_gas: registers[RegId::CGAS],
+            })
+    }
+
+    /// Stack of the current context, `[$ssp, $sp[`.
+    pub fn stack_range(&self) -> MemoryRange {
+        let ssp = self.registers[RegId::SSP];
+        let sp = self.registers[RegId::SP];
+        MemoryRange::new(ssp, sp.saturating_sub(ssp)).unwrap_or_default()
+    }
+
+    /// Heap of the current context, from `$hp` to the heap of the caller, or
+    /// to the end of the memory outside of calls.
+    pub fn heap_range(&self) -> MemoryRange {
+        let ownership = OwnershipRegisters::new(self);
+        let end = if ownership.context.is_external() {
+            VM_MAX_RAM
+        } else {
+            ownership.prev_hp
+        };
+        MemoryRange::new(ownership.hp, end.saturating_sub(ownership.hp))
+            .unwrap_or_default()
+    }
+
+    /// Check if `range` can be written by the current context.
+    pub fn is_owned(&self, range: &MemoryRange) -> bool {
+        OwnershipRegisters::new(self).has_ownership_range(range)
+    }
+
+    /// Read `len` bytes of allocated memory at `addr`.
+    ///
+    /// Fails with [`PanicReason::MemoryOverflow`] if the range is out of
+    /// bounds, and with [`PanicReason::MemoryOwnership`] if it overlaps the
+    /// unallocated memory between `$sp` and `$hp`.
+    pub fn read_memory(
+        &self,
+        addr: Word,
+        len: Word,
+    ) -> Result<Cow<'_, [u8]>, RuntimeError> {
+        let range = MemoryRange::new(addr, len)?;
+        if range.end > VM_MAX_RAM as usize {
+            return Err(PanicReason::MemoryOverflow.into())
+        }
+
+        let sp = self.registers[RegId::SP] as usize;
+        let hp = self.registers[RegId::HP] as usize;
+        if !range.is_empty() && range.start < hp && range.end > sp {
+            return Err(PanicReason::MemoryOwnership.into())
+        }
+
+        Ok(range.read(&self.memory))
+    }
+
+    /// Read the word at `addr`, see [`Interpreter::read_memory`].
+    pub fn read_word(&self, addr: Word) -> Result<Word, RuntimeError> {
+        let bytes = self.read_memory(addr, WORD_SIZE as Word)?;
+        let bytes = bytes.as_ref().try_into().expect("The length is a word");
+        Ok(Word::from_be_bytes(bytes))
+    }
+
+    /// Read the `Bytes32` at `addr`, see [`Interpreter::read_memory`].
+    pub fn read_bytes32(&self, addr: Word) -> Result<Bytes32, RuntimeError> {
+        let bytes = self.read_memory(addr, Bytes32::LEN as Word)?;
+        let bytes: [u8; Bytes32::LEN] =
+            bytes.as_ref().try_into().expect("The length is 32 bytes");
+        Ok(bytes.into())
+    }
+
+    /// Read the string of `len` bytes at `addr`, replacing invalid UTF-8, see
+    /// [`Interpreter::read_memory`].
+    pub fn read_string(&self, addr: Word, len: Word) -> Result<String, RuntimeError> {
+        let bytes = self.read_memory(addr, len)?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
@@ -0,0 +1,114 @@
+use fuel_asm::{
+    op,
+    GTFArgs,
+    PanicReason,
+    RegId,
+};
+use fuel_tx::Script;
+use fuel_types::Bytes32;
+
+use crate::{
+    call::Call,
+    consts::*,
+    prelude::*,
+};
+
+#[test]
+fn inspect_frames_and_memory_inside_a_call() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let code = vec![
+        op::cfei(16),
+        op::movi(0x10, 42),
+        op::sw(RegId::SSP, 0x10, 0),
+        op::movi(0x11, 32),
+        op::aloc(0x11),
+        op::sw(RegId::HP, 0x10, 0),
+        op::ret(RegId::ONE),
+    ];
+    let code_bytes: Vec<u8> = code.iter().copied().collect();
+    let contract_id = test_context.setup_contract(code, None, None).contract_id;
+
+    let script = vec![
+        op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+        op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+        op::ret(RegId::ONE),
+    ];
+    let call: [u8; Call::LEN] = Call::new(contract_id, 0, 0).into();
+    let tx = test_context
+        .start_script(script, call.to_vec())
+        .gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+
+    let mut vm = Interpreter::with_storage(
+        test_context.get_storage().clone(),
+        *test_context.get_params(),
+        GasCosts::default(),
+    );
+
+    // Stop before the `RET` of the contract
+    let slice = vm.transact_bounded(tx, 8).expect("Failed to transact");
+    assert!(slice.is_yielded());
+
+    let frames: Vec<_> = vm.frames().collect();
+    assert_eq!(frames.len(), 1);
+    let frame = frames[0];
+    assert_eq!(frame.frame(), &vm.call_stack()[0]);
+    assert_eq!(frame.contract_id(), &contract_id);
+    // The asset id is read at `$c`, which is zero
+    assert_eq!(
+        frame.asset_id().as_ref(),
+        vm.read_bytes32(0).expect("Failed to read").as_ref()
+    );
+    assert_eq!(frame.context_gas(), vm.registers()[RegId::CGAS]);
+    assert_eq!(frame.address(), vm.registers()[RegId::FP]);
+    assert_eq!(
+        frame.saved_registers()[RegId::SP],
+        vm.registers()[RegId::FP]
+    );
+    assert_eq!(
+        vm.paged_memory().read(frame.code_range().usizes()).as_ref(),
+        code_bytes.as_slice()
+    );
+
+    let stack = vm.stack_range();
+    assert_eq!(stack.len(), 16);
+    assert!(vm.is_owned(&stack));
+    assert!(!vm.is_owned(&frame.code_range()));
+    assert_eq!(vm.read_word(stack.start as Word), Ok(42));
+    assert_eq!(
+        vm.read_string(stack.start as Word + 7, 1).as_deref(),
+        Ok("*")
+    );
+
+    let heap = vm.heap_range();
+    assert_eq!(heap.start as Word, vm.registers()[RegId::HP]);
+    assert_eq!(heap.end as Word, frame.saved_registers()[RegId::HP]);
+    let mut expected = [0u8; 32];
+    expected[7] = 42;
+    assert_eq!(
+        vm.read_bytes32(heap.start as Word),
+        Ok(Bytes32::from(expected))
+    );
+
+    // The memory between `$sp` and `$hp` isn't allocated
+    assert_eq!(
+        vm.read_word(stack.end as Word),
+        Err(PanicReason::MemoryOwnership.into())
+    );
+    assert_eq!(
+        vm.read_word(VM_MAX_RAM - 4),
+        Err(PanicReason::MemoryOverflow.into())
+    );
+}
+
+#[test]
+fn no_frames_outside_of_calls() {
+    let vm = Interpreter::<_, Script>::with_memory_storage();
+
+    assert_eq!(vm.frames().count(), 0);
+    assert!(vm.call_stack().is_empty());
+    assert!(vm.stack_range().is_empty());
+}
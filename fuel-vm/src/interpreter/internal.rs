@@ -1,7 +1,12 @@
 use super::{
+    panic_context::{
+        memory_panic,
+        panic_with_context,
+    },
     receipts::ReceiptsCtx,
     ExecutableTransaction,
     Interpreter,
+    Memory,
     MemoryRange,
     RuntimeBalances,
 };
@@ -10,7 +15,6 @@ use crate::{
         reg_key::*,
         CheckedMemConstLen,
     },
-    consts::*,
     context::Context,
     error::RuntimeError,
 };
@@ -27,6 +31,7 @@ use fuel_tx::{
         ReceiptsRoot,
     },
     Output,
+    PanicContext,
     Receipt,
     Script,
 };
@@ -74,7 +79,7 @@ where
 /// and the serialized tx in vm memory.
 pub(crate) fn set_variable_output<Tx: ExecutableTransaction>(
     tx: &mut Tx,
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     tx_offset: usize,
     idx: usize,
     variable: Output,
@@ -109,7 +114,7 @@ pub(crate) fn absolute_output_mem_range<Tx: Outputs>(
 
 pub(crate) fn update_memory_output<Tx: ExecutableTransaction>(
     tx: &mut Tx,
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     tx_offset: usize,
     idx: usize,
 ) -> Result<(), RuntimeError> {
@@ -126,7 +131,7 @@ pub(crate) struct AppendReceipt<'vm> {
     pub receipts: &'vm mut ReceiptsCtx,
     pub script: Option<&'vm mut Script>,
     pub tx_offset: usize,
-    pub memory: &'vm mut [u8; MEM_SIZE],
+    pub memory: &'vm mut Memory,
 }
 
 pub(crate) fn append_receipt(input: AppendReceipt, receipt: Receipt) {
@@ -150,7 +155,9 @@ pub(crate) fn append_receipt(input: AppendReceipt, receipt: Receipt) {
 
         // Transaction memory space length is already checked on initialization so its
         // guaranteed to fit
-        memory[offset..offset + Bytes32::LEN].copy_from_slice(&root[..]);
+        memory
+            .write(offset..offset + Bytes32::LEN)
+            .copy_from_slice(&root[..]);
     }
 }
 
@@ -159,7 +166,11 @@ impl<S, Tx> Interpreter<S, Tx> {
         let (ssp, overflow) = self.registers[RegId::SSP].overflowing_add(len);
 
         if overflow || !self.is_external_context() && ssp > self.registers[RegId::SP] {
-            Err(PanicReason::MemoryOverflow.into())
+            Err(memory_panic(
+                PanicReason::MemoryOverflow,
+                self.registers[RegId::SSP],
+                len,
+            ))
         } else {
             Ok(mem::replace(&mut self.registers[RegId::SSP], ssp))
         }
@@ -168,7 +179,8 @@ impl<S, Tx> Interpreter<S, Tx> {
     pub(crate) fn push_stack(&mut self, data: &[u8]) -> Result<(), RuntimeError> {
         let ssp = self.reserve_stack(data.len() as Word)?;
 
-        self.memory[ssp as usize..self.registers[RegId::SSP] as usize]
+        self.memory
+            .write(ssp as usize..self.registers[RegId::SSP] as usize)
             .copy_from_slice(data);
 
         Ok(())
@@ -194,16 +206,12 @@ impl<S, Tx> Interpreter<S, Tx> {
         )
     }
 
-    pub(crate) fn internal_contract(&self) -> Result<&ContractId, RuntimeError> {
+    pub(crate) fn internal_contract(&self) -> Result<ContractId, RuntimeError> {
         internal_contract(&self.context, self.registers.fp(), &self.memory)
     }
 
     pub(crate) fn internal_contract_or_default(&self) -> ContractId {
-        internal_contract_or_default(
-            &self.context,
-            self.registers.fp(),
-            self.memory.as_ref(),
-        )
+        internal_contract_or_default(&self.context, self.registers.fp(), &self.memory)
     }
 
     pub(crate) const fn tx_offset(&self) -> usize {
@@ -230,7 +238,9 @@ pub(crate) fn set_flag(
     pc: RegMut<PC>,
     a: Word,
 ) -> Result<(), RuntimeError> {
-    let Some(flags) = Flags::from_bits(a) else { return Err(PanicReason::ErrorFlag.into()) };
+    let Some(flags) = Flags::from_bits(a) else {
+        return Err(PanicReason::ErrorFlag.into())
+    };
 
     *flag = flags.bits();
 
@@ -243,18 +253,15 @@ pub(crate) fn inc_pc(mut pc: RegMut<PC>) -> Result<(), RuntimeError> {
         .map(|i| *pc = i)
 }
 
-pub(crate) fn tx_id(memory: &[u8; MEM_SIZE]) -> &Bytes32 {
-    let memory = (&memory[..Bytes32::LEN])
-        .try_into()
-        .expect("Bytes32::LEN < MEM_SIZE");
+pub(crate) fn tx_id(memory: &Memory) -> Bytes32 {
     // Safety: vm parameters guarantees enough space for txid
-    Bytes32::from_bytes_ref(memory)
+    Bytes32::from(memory.read_bytes::<{ Bytes32::LEN }>(0))
 }
 
 /// Reduces the unspent balance of the base asset
 pub(crate) fn base_asset_balance_sub(
     balances: &mut RuntimeBalances,
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     value: Word,
 ) -> Result<(), RuntimeError> {
     external_asset_id_balance_sub(balances, memory, &AssetId::zeroed(), value)
@@ -263,13 +270,23 @@ pub(crate) fn base_asset_balance_sub(
 /// Reduces the unspent balance of a given asset ID
 pub(crate) fn external_asset_id_balance_sub(
     balances: &mut RuntimeBalances,
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     asset_id: &AssetId,
     value: Word,
 ) -> Result<(), RuntimeError> {
+    let available = balances.balance(asset_id).unwrap_or_default();
     balances
         .checked_balance_sub(memory, asset_id, value)
-        .ok_or(PanicReason::NotEnoughBalance)?;
+        .ok_or_else(|| {
+            panic_with_context(
+                PanicReason::NotEnoughBalance,
+                PanicContext::Balance {
+                    asset_id: *asset_id,
+                    expected: value,
+                    actual: available,
+                },
+            )
+        })?;
 
     Ok(())
 }
@@ -277,17 +294,16 @@ pub(crate) fn external_asset_id_balance_sub(
 pub(crate) fn internal_contract_or_default(
     context: &Context,
     register: Reg<FP>,
-    memory: &[u8; MEM_SIZE],
+    memory: &Memory,
 ) -> ContractId {
-    internal_contract(context, register, memory)
-        .map_or(Default::default(), |contract| *contract)
+    internal_contract(context, register, memory).unwrap_or_default()
 }
 
-pub(crate) fn current_contract<'a>(
+pub(crate) fn current_contract(
     context: &Context,
     fp: Reg<FP>,
-    memory: &'a [u8; MEM_SIZE],
-) -> Result<Option<&'a ContractId>, RuntimeError> {
+    memory: &Memory,
+) -> Result<Option<ContractId>, RuntimeError> {
     if context.is_internal() {
         Ok(Some(internal_contract(context, fp, memory)?))
     } else {
@@ -295,15 +311,15 @@ pub(crate) fn current_contract<'a>(
     }
 }
 
-pub(crate) fn internal_contract<'a>(
+pub(crate) fn internal_contract(
     context: &Context,
     register: Reg<FP>,
-    memory: &'a [u8; MEM_SIZE],
-) -> Result<&'a ContractId, RuntimeError> {
+    memory: &Memory,
+) -> Result<ContractId, RuntimeError> {
     let range = internal_contract_bounds(context, register)?;
 
     // Safety: Memory bounds logically verified by the interpreter
-    let contract = ContractId::from_bytes_ref(range.read(memory));
+    let contract = ContractId::from(range.read(memory));
 
     Ok(contract)
 }
@@ -142,7 +142,10 @@ fn variable_output_updates_in_memory() {
     let position = vm.tx_offset() + vm.transaction().outputs_offset_at(0).unwrap();
     let mut mem_output =
         Output::variable(Default::default(), Default::default(), Default::default());
-    let _ = mem_output.write(&vm.memory()[position..]).unwrap();
+    let output_size = mem_output.serialized_size();
+    let _ = mem_output
+        .write(&vm.paged_memory().read(position..position + output_size))
+        .unwrap();
     assert_eq!(vm.transaction().outputs()[0], mem_output);
 }
 
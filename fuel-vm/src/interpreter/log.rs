@@ -5,9 +5,11 @@ use super::{
         internal_contract_or_default,
         AppendReceipt,
     },
+    panic_context::memory_panic,
     receipts::ReceiptsCtx,
     ExecutableTransaction,
     Interpreter,
+    Memory,
 };
 use crate::{
     constraints::reg_key::*,
@@ -76,7 +78,7 @@ where
 }
 
 struct LogInput<'vm> {
-    memory: &'vm mut [u8; MEM_SIZE],
+    memory: &'vm mut Memory,
     tx_offset: usize,
     context: &'vm Context,
     receipts: &'vm mut ReceiptsCtx,
@@ -125,7 +127,7 @@ impl LogInput<'_> {
         d: Word,
     ) -> Result<(), RuntimeError> {
         if d > MEM_MAX_ACCESS_SIZE || c > VM_MAX_RAM - d {
-            return Err(PanicReason::MemoryOverflow.into())
+            return Err(memory_panic(PanicReason::MemoryOverflow, c, d))
         }
 
         let cd = (c + d) as usize;
@@ -137,7 +139,7 @@ impl LogInput<'_> {
             c,
             *self.pc,
             *self.is,
-            self.memory[c as usize..cd].to_vec(),
+            self.memory.read(c as usize..cd).into_owned(),
         );
 
         append_receipt(
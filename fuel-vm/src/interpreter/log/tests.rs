@@ -4,7 +4,7 @@ use super::*;
 
 #[test]
 fn test_log() -> Result<(), RuntimeError> {
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let context = Context::Script {
         block_height: Default::default(),
     };
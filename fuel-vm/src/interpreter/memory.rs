@@ -1,5 +1,6 @@
 use super::{
     internal::inc_pc,
+    panic_context::memory_panic,
     ExecutableTransaction,
     Interpreter,
 };
@@ -20,18 +21,411 @@ use fuel_types::{
 };
 
 use std::{
+    borrow::Cow,
+    fmt,
     ops,
     ops::Range,
 };
 
-pub type Memory<const SIZE: usize> = Box<[u8; SIZE]>;
-
 #[cfg(test)]
 mod tests;
 
 #[cfg(test)]
 mod allocation_tests;
 
+/// The VM memory.
+///
+/// The address space is `MEM_SIZE` bytes long, but it is backed lazily: the stack side
+/// (growing up from address `0`) and the heap side (growing down from `MEM_SIZE`) are
+/// two separate buffers that are extended by whole pages of [`MEM_PAGE_SIZE`] bytes
+/// the first time an address inside a page is written. Everything between the two
+/// buffers has never been written, and reads as zero.
+///
+/// When an access spans the gap between both buffers, they are merged into a single
+/// buffer covering the whole address space.
+#[derive(Clone)]
+pub struct Memory {
+    /// Bytes of `[0, stack.len()[`.
+    stack: Vec<u8>,
+    /// Bytes of `[MEM_SIZE - heap.len(), MEM_SIZE[`.
+    heap: Vec<u8>,
+    /// Writes performed since the recording started, if it is enabled.
+    writes: Option<Vec<WriteRecord>>,
+}
+
+/// A memory range written while the recording was enabled, with its previous
+/// contents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriteRecord {
+    range: Range<usize>,
+    previous: Vec<u8>,
+}
+
+impl WriteRecord {
+    /// Written range.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Contents of the range before the write.
+    pub fn previous(&self) -> &[u8] {
+        &self.previous
+    }
+}
+
+/// Position in the write recording, see [`Memory::write_checkpoint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteCheckpoint {
+    started: bool,
+    index: usize,
+}
+
+/// The part of the backing storage containing a memory range.
+enum Region {
+    Stack(Range<usize>),
+    Heap(Range<usize>),
+}
+
+impl Default for Memory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Memory {
+    /// Create a new memory without any allocated page.
+    pub const fn new() -> Self {
+        Self {
+            stack: Vec::new(),
+            heap: Vec::new(),
+            writes: None,
+        }
+    }
+
+    /// Amount of bytes currently backed by allocated pages.
+    pub fn allocated(&self) -> usize {
+        self.stack.len() + self.heap.len()
+    }
+
+    /// Allocated bytes of the stack side and of the heap side.
+    #[cfg(feature = "debug")]
+    pub(crate) fn sides(&self) -> (&[u8], &[u8]) {
+        (&self.stack, &self.heap)
+    }
+
+    /// Memory backed by the given stack and heap sides, `None` if they overlap or
+    /// aren't made of whole pages.
+    #[cfg(feature = "debug")]
+    pub(crate) fn from_sides(stack: Vec<u8>, heap: Vec<u8>) -> Option<Self> {
+        let pages = stack.len() % MEM_PAGE_SIZE == 0 && heap.len() % MEM_PAGE_SIZE == 0;
+
+        (pages && stack.len() + heap.len() <= MEM_SIZE).then_some(Self {
+            stack,
+            heap,
+            writes: None,
+        })
+    }
+
+    /// First address backed by the heap side buffer.
+    fn heap_start(&self) -> usize {
+        MEM_SIZE - self.heap.len()
+    }
+
+    /// Locate the range in the backing buffers, if it is fully contained in one of
+    /// them.
+    fn region(&self, range: &Range<usize>) -> Option<Region> {
+        let heap_start = self.heap_start();
+
+        if range.end <= self.stack.len() {
+            Some(Region::Stack(range.clone()))
+        } else if range.start >= heap_start {
+            Some(Region::Heap(
+                range.start - heap_start..range.end - heap_start,
+            ))
+        } else if range.is_empty() {
+            Some(Region::Stack(0..0))
+        } else {
+            None
+        }
+    }
+
+    /// Allocate the pages required to back `range`, zero-filling them.
+    fn touch(&mut self, range: &Range<usize>) {
+        assert!(range.end <= MEM_SIZE, "Memory range out of bounds");
+
+        let stack_end = self.stack.len();
+        let heap_start = self.heap_start();
+
+        if self.region(range).is_some() {
+            return
+        }
+
+        if range.start < stack_end && range.end > heap_start {
+            self.merge();
+        } else if range.start < stack_end
+            || range.end <= heap_start
+                && range.start - stack_end <= heap_start - range.end
+        {
+            self.stack.resize(round_up_to_page(range.end), 0);
+        } else {
+            // The heap buffer is at least doubled on growth to amortize the copy of its
+            // current content.
+            let start = round_down_to_page(range.start)
+                .min(MEM_SIZE.saturating_sub(self.heap.len().saturating_mul(2)))
+                .max(stack_end);
+            let mut heap = vec![0; heap_start - start];
+            heap.append(&mut self.heap);
+            self.heap = heap;
+        }
+    }
+
+    /// Merge both buffers into a single one covering the whole address space.
+    fn merge(&mut self) {
+        let heap_start = self.heap_start();
+        self.stack.resize(heap_start, 0);
+        self.stack.append(&mut self.heap);
+    }
+
+    /// Read the bytes of the range.
+    ///
+    /// Borrows the backing buffer if the range is allocated, otherwise the bytes are
+    /// copied with the unallocated part reading as zero.
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn read(&self, range: Range<usize>) -> Cow<'_, [u8]> {
+        assert!(range.end <= MEM_SIZE, "Memory range out of bounds");
+
+        match self.region(&range) {
+            Some(Region::Stack(r)) => Cow::Borrowed(&self.stack[r]),
+            Some(Region::Heap(r)) => Cow::Borrowed(&self.heap[r]),
+            None => {
+                let mut bytes = vec![0; range.len()];
+                if range.start < self.stack.len() {
+                    let end = self.stack.len();
+                    bytes[..end - range.start]
+                        .copy_from_slice(&self.stack[range.start..]);
+                }
+                let heap_start = self.heap_start();
+                if range.end > heap_start {
+                    let start = heap_start - range.start;
+                    bytes[start..].copy_from_slice(&self.heap[..range.end - heap_start]);
+                }
+                Cow::Owned(bytes)
+            }
+        }
+    }
+
+    /// Read a constant-sized byte array.
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn read_bytes<const COUNT: usize>(&self, addr: usize) -> [u8; COUNT] {
+        let mut bytes = [0u8; COUNT];
+        bytes.copy_from_slice(&self.read(addr..addr + COUNT));
+        bytes
+    }
+
+    /// Read the byte at the address.
+    ///
+    /// Panics if the address is out of bounds.
+    pub fn read_byte(&self, addr: usize) -> u8 {
+        self.read_bytes::<1>(addr)[0]
+    }
+
+    /// Get a mutable slice of the range, allocating its pages if required.
+    ///
+    /// Panics if the range is out of bounds.
+    pub fn write(&mut self, range: Range<usize>) -> &mut [u8] {
+        self.touch(&range);
+        self.record_write(&range);
+
+        match self.region(&range) {
+            Some(Region::Stack(r)) => &mut self.stack[r],
+            Some(Region::Heap(r)) => &mut self.heap[r],
+            None => unreachable!("The range was allocated"),
+        }
+    }
+
+    /// Copy `len` bytes from `src` to `dst`. The ranges may overlap.
+    ///
+    /// Panics if any of the ranges is out of bounds.
+    pub fn copy_within(&mut self, src: usize, dst: usize, len: usize) {
+        let src = src..src + len;
+        let dst = dst..dst + len;
+        self.touch(&src);
+        self.touch(&dst);
+        self.record_write(&dst);
+
+        match (self.region(&src), self.region(&dst)) {
+            (Some(Region::Stack(s)), Some(Region::Stack(d))) => {
+                self.stack.copy_within(s, d.start)
+            }
+            (Some(Region::Heap(s)), Some(Region::Heap(d))) => {
+                self.heap.copy_within(s, d.start)
+            }
+            (Some(Region::Stack(s)), Some(Region::Heap(d))) => {
+                self.heap[d].copy_from_slice(&self.stack[s])
+            }
+            (Some(Region::Heap(s)), Some(Region::Stack(d))) => {
+                self.stack[d].copy_from_slice(&self.heap[s])
+            }
+            _ => unreachable!("The ranges were allocated"),
+        }
+    }
+
+    /// Iterate over the contents of the whole address space.
+    pub fn iter(&self) -> impl Iterator<Item = &u8> + '_ {
+        self.stack
+            .iter()
+            .chain((self.stack.len()..self.heap_start()).map(|_| &0))
+            .chain(self.heap.iter())
+    }
+
+    /// Copy the whole address space into a flat buffer.
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(MEM_SIZE);
+        bytes.extend_from_slice(&self.stack);
+        bytes.resize(self.heap_start(), 0);
+        bytes.extend_from_slice(&self.heap);
+        bytes
+    }
+}
+
+const fn round_up_to_page(addr: usize) -> usize {
+    round_down_to_page(addr + MEM_PAGE_SIZE - 1)
+}
+
+const fn round_down_to_page(addr: usize) -> usize {
+    addr / MEM_PAGE_SIZE * MEM_PAGE_SIZE
+}
+
+impl Memory {
+    /// Start recording the writes to the memory, if not already recording.
+    ///
+    /// Checkpoints can be nested: the recording stops once
+    /// [`Self::writes_since`] is called with the checkpoint that started it.
+    pub fn write_checkpoint(&mut self) -> WriteCheckpoint {
+        match self.writes.as_ref() {
+            Some(writes) => WriteCheckpoint {
+                started: false,
+                index: writes.len(),
+            },
+            None => {
+                self.writes = Some(Vec::new());
+                WriteCheckpoint {
+                    started: true,
+                    index: 0,
+                }
+            }
+        }
+    }
+
+    /// Writes performed since the checkpoint was taken, in write order.
+    pub fn writes_since(&mut self, checkpoint: WriteCheckpoint) -> Vec<WriteRecord> {
+        if checkpoint.started {
+            self.writes.take().unwrap_or_default()
+        } else {
+            self.writes
+                .as_ref()
+                .and_then(|writes| writes.get(checkpoint.index..))
+                .map(<[WriteRecord]>::to_vec)
+                .unwrap_or_default()
+        }
+    }
+
+    /// Record the previous contents of `range`, which must be allocated.
+    fn record_write(&mut self, range: &Range<usize>) {
+        if self.writes.is_none() || range.is_empty() {
+            return
+        }
+
+        let record = WriteRecord {
+            range: range.clone(),
+            previous: self.read(range.clone()).into_owned(),
+        };
+
+        if let Some(writes) = self.writes.as_mut() {
+            writes.push(record);
+        }
+    }
+}
+
+impl PartialEq for Memory {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for Memory {}
+
+impl fmt::Debug for Memory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Memory")
+            .field("stack", &self.stack.len())
+            .field("heap", &self.heap.len())
+            .finish()
+    }
+}
+
+impl TryFrom<Vec<u8>> for Memory {
+    type Error = Vec<u8>;
+
+    /// Create a fully allocated memory out of a buffer of `MEM_SIZE` bytes.
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        if bytes.len() != MEM_SIZE {
+            return Err(bytes)
+        }
+
+        Ok(Self {
+            stack: bytes,
+            heap: Vec::new(),
+            writes: None,
+        })
+    }
+}
+
+#[cfg(test)]
+impl<I> ops::Index<I> for Memory
+where
+    I: ops::RangeBounds<usize> + std::slice::SliceIndex<[u8], Output = [u8]>,
+{
+    type Output = [u8];
+
+    /// Panics if the range isn't fully allocated.
+    fn index(&self, index: I) -> &Self::Output {
+        let range = slice_range(&index);
+        match self.region(&range) {
+            Some(Region::Stack(r)) => &self.stack[r],
+            Some(Region::Heap(r)) => &self.heap[r],
+            None => panic!("Memory range is not allocated"),
+        }
+    }
+}
+
+#[cfg(test)]
+impl<I> ops::IndexMut<I> for Memory
+where
+    I: ops::RangeBounds<usize> + std::slice::SliceIndex<[u8], Output = [u8]>,
+{
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        self.write(slice_range(&index))
+    }
+}
+
+#[cfg(test)]
+fn slice_range<I: ops::RangeBounds<usize>>(index: &I) -> Range<usize> {
+    let start = match index.start_bound() {
+        ops::Bound::Included(s) => *s,
+        ops::Bound::Excluded(s) => s + 1,
+        ops::Bound::Unbounded => 0,
+    };
+    let end = match index.end_bound() {
+        ops::Bound::Included(e) => e + 1,
+        ops::Bound::Excluded(e) => *e,
+        ops::Bound::Unbounded => MEM_SIZE,
+    };
+    start..end
+}
+
 /// Used to handle `Word` to `usize` conversions for memory addresses,
 /// as well as checking that the resulting value is withing the VM ram boundaries.
 pub trait ToAddr {
@@ -39,6 +433,9 @@ pub trait ToAddr {
     /// Returns `Err` with `MemoryOverflow` if the resulting value does't fit in the VM
     /// memory. This can be used for both addresses and offsets.
     fn to_addr(self) -> Result<usize, RuntimeError>;
+
+    /// Raw value, as recorded in the context of a panic.
+    fn to_word(&self) -> Word;
 }
 
 impl ToAddr for usize {
@@ -48,6 +445,10 @@ impl ToAddr for usize {
         }
         Ok(self)
     }
+
+    fn to_word(&self) -> Word {
+        *self as Word
+    }
 }
 
 impl ToAddr for Word {
@@ -55,6 +456,10 @@ impl ToAddr for Word {
         let value = usize::try_from(self).map_err(|_| PanicReason::MemoryOverflow)?;
         value.to_addr()
     }
+
+    fn to_word(&self) -> Word {
+        *self
+    }
 }
 
 #[cfg(feature = "test-helpers")]
@@ -67,6 +472,10 @@ impl ToAddr for i32 {
         let value = usize::try_from(self).map_err(|_| PanicReason::MemoryOverflow)?;
         value.to_addr()
     }
+
+    fn to_word(&self) -> Word {
+        *self as Word
+    }
 }
 
 /// Memory range representation for the VM, checked to be in-bounds on construction.
@@ -91,15 +500,17 @@ impl ops::Deref for MemoryRange {
 impl MemoryRange {
     /// Create a new memory range represented as `[address, address + size[`.
     pub fn new<A: ToAddr, B: ToAddr>(address: A, size: B) -> Result<Self, RuntimeError> {
-        let start = address.to_addr()?;
-        let size = size.to_addr()?;
-        let end = start.checked_add(size).ok_or(PanicReason::MemoryOverflow)?;
+        let (raw_address, raw_size) = (address.to_word(), size.to_word());
+        let overflow =
+            || memory_panic(PanicReason::MemoryOverflow, raw_address, raw_size);
 
-        if end > MEM_SIZE {
-            return Err(PanicReason::MemoryOverflow.into())
-        }
+        let start = address.to_addr().map_err(|_| overflow())?;
+        let size = size.to_addr().map_err(|_| overflow())?;
 
-        Ok(Self(start..end))
+        match start.checked_add(size) {
+            Some(end) if end <= MEM_SIZE => Ok(Self(start..end)),
+            _ => Err(overflow()),
+        }
     }
 
     /// Create a new const sized memory range.
@@ -153,13 +564,13 @@ impl MemoryRange {
     }
 
     /// Get the memory slice for this range.
-    pub fn read(self, memory: &[u8; MEM_SIZE]) -> &[u8] {
-        &memory[self.0]
+    pub fn read(self, memory: &Memory) -> Cow<'_, [u8]> {
+        memory.read(self.0)
     }
 
     /// Get the mutable memory slice for this range.
-    pub fn write(self, memory: &mut [u8; MEM_SIZE]) -> &mut [u8] {
-        &mut memory[self.0]
+    pub fn write(self, memory: &mut Memory) -> &mut [u8] {
+        memory.write(self.0)
     }
 }
 
@@ -288,7 +699,7 @@ where
 }
 
 pub(crate) fn load_byte(
-    memory: &[u8; MEM_SIZE],
+    memory: &Memory,
     pc: RegMut<PC>,
     result: &mut Word,
     b: Word,
@@ -297,16 +708,20 @@ pub(crate) fn load_byte(
     let bc = b.saturating_add(c) as usize;
 
     if bc >= VM_MAX_RAM as RegisterId {
-        Err(PanicReason::MemoryOverflow.into())
+        Err(memory_panic(
+            PanicReason::MemoryOverflow,
+            b.saturating_add(c),
+            1,
+        ))
     } else {
-        *result = memory[bc] as Word;
+        *result = memory.read_byte(bc) as Word;
 
         inc_pc(pc)
     }
 }
 
 pub(crate) fn load_word(
-    memory: &[u8; MEM_SIZE],
+    memory: &Memory,
     pc: RegMut<PC>,
     result: &mut Word,
     b: Word,
@@ -314,13 +729,15 @@ pub(crate) fn load_word(
 ) -> Result<(), RuntimeError> {
     // C is expressed in words; mul by 8. This cannot overflow since it's a 12 bit
     // immediate value.
-    let addr = b.checked_add(c * 8).ok_or(PanicReason::MemoryOverflow)?;
+    let addr = b
+        .checked_add(c * 8)
+        .ok_or_else(|| memory_panic(PanicReason::MemoryOverflow, b, WORD_SIZE as Word))?;
     *result = Word::from_be_bytes(read_bytes(memory, addr)?);
     inc_pc(pc)
 }
 
 pub(crate) fn store_byte(
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     owner: OwnershipRegisters,
     pc: RegMut<PC>,
     a: Word,
@@ -333,16 +750,16 @@ pub(crate) fn store_byte(
         || ac >= VM_MAX_RAM
         || !(owner.has_ownership_stack(&range) || owner.has_ownership_heap(&range))
     {
-        Err(PanicReason::MemoryOverflow.into())
+        Err(memory_panic(PanicReason::MemoryOverflow, ac, 1))
     } else {
-        memory[ac as usize] = b as u8;
+        memory.write(ac as usize..ac as usize + 1)[0] = b as u8;
 
         inc_pc(pc)
     }
 }
 
 pub(crate) fn store_word(
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     owner: OwnershipRegisters,
     pc: RegMut<PC>,
     a: Word,
@@ -351,7 +768,9 @@ pub(crate) fn store_word(
 ) -> Result<(), RuntimeError> {
     // C is expressed in words; mul by 8. This cannot overflow since it's a 12 bit
     // immediate value.
-    let addr = a.checked_add(c * 8).ok_or(PanicReason::MemoryOverflow)?;
+    let addr = a
+        .checked_add(c * 8)
+        .ok_or_else(|| memory_panic(PanicReason::MemoryOverflow, a, WORD_SIZE as Word))?;
     write_bytes(memory, owner, addr, b.to_be_bytes())?;
     inc_pc(pc)
 }
@@ -374,7 +793,7 @@ pub(crate) fn malloc(
 }
 
 pub(crate) fn memclear(
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     owner: OwnershipRegisters,
     pc: RegMut<PC>,
     a: Word,
@@ -382,15 +801,15 @@ pub(crate) fn memclear(
 ) -> Result<(), RuntimeError> {
     let range = MemoryRange::new(a, b)?;
     if b > MEM_MAX_ACCESS_SIZE || !owner.has_ownership_range(&range) {
-        Err(PanicReason::MemoryOverflow.into())
+        Err(memory_panic(PanicReason::MemoryOverflow, a, b))
     } else {
-        memory[range.usizes()].fill(0);
+        memory.write(range.usizes()).fill(0);
         inc_pc(pc)
     }
 }
 
 pub(crate) fn memcopy(
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     owner: OwnershipRegisters,
     pc: RegMut<PC>,
     a: Word,
@@ -401,11 +820,11 @@ pub(crate) fn memcopy(
     let src_range = MemoryRange::new(b, c)?;
 
     if c > MEM_MAX_ACCESS_SIZE {
-        return Err(PanicReason::MemoryOverflow.into())
+        return Err(memory_panic(PanicReason::MemoryOverflow, b, c))
     }
 
     if !owner.has_ownership_range(&dst_range) {
-        return Err(PanicReason::MemoryOwnership.into())
+        return Err(memory_panic(PanicReason::MemoryOwnership, a, c))
     }
 
     if dst_range.start <= src_range.start && src_range.start < dst_range.end
@@ -413,23 +832,16 @@ pub(crate) fn memcopy(
         || dst_range.start < src_range.end && src_range.end <= dst_range.end
         || src_range.start < dst_range.end && dst_range.end <= src_range.end
     {
-        return Err(PanicReason::MemoryWriteOverlap.into())
+        return Err(memory_panic(PanicReason::MemoryWriteOverlap, a, c))
     }
 
-    let len = src_range.len();
-    if a <= b {
-        let (dst, src) = memory.split_at_mut(src_range.start);
-        dst[dst_range.usizes()].copy_from_slice(&src[..len]);
-    } else {
-        let (src, dst) = memory.split_at_mut(dst_range.start);
-        dst[..len].copy_from_slice(&src[src_range.usizes()]);
-    }
+    memory.copy_within(src_range.start, dst_range.start, src_range.len());
 
     inc_pc(pc)
 }
 
 pub(crate) fn memeq(
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     result: &mut Word,
     pc: RegMut<PC>,
     b: Word,
@@ -438,19 +850,20 @@ pub(crate) fn memeq(
 ) -> Result<(), RuntimeError> {
     let (bd, overflow) = b.overflowing_add(d);
     let (cd, of) = c.overflowing_add(d);
-    let overflow = overflow || of;
 
-    if overflow || bd > VM_MAX_RAM || cd > VM_MAX_RAM || d > MEM_MAX_ACCESS_SIZE {
-        Err(PanicReason::MemoryOverflow.into())
+    if overflow || bd > VM_MAX_RAM || d > MEM_MAX_ACCESS_SIZE {
+        Err(memory_panic(PanicReason::MemoryOverflow, b, d))
+    } else if of || cd > VM_MAX_RAM {
+        Err(memory_panic(PanicReason::MemoryOverflow, c, d))
     } else {
-        *result =
-            (memory[b as usize..bd as usize] == memory[c as usize..cd as usize]) as Word;
+        *result = (memory.read(b as usize..bd as usize)
+            == memory.read(c as usize..cd as usize)) as Word;
 
         inc_pc(pc)
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct OwnershipRegisters {
     pub(crate) sp: u64,
     pub(crate) ssp: u64,
@@ -517,19 +930,28 @@ impl OwnershipRegisters {
     }
 }
 
+/// Panic with [`PanicReason::MemoryOwnership`] on the write to `range`.
+pub(crate) fn ownership_panic(range: &MemoryRange) -> RuntimeError {
+    memory_panic(
+        PanicReason::MemoryOwnership,
+        range.start as Word,
+        range.len() as Word,
+    )
+}
+
 pub(crate) fn try_mem_write<A: ToAddr>(
     addr: A,
     data: &[u8],
     registers: OwnershipRegisters,
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
 ) -> Result<(), RuntimeError> {
     let range = MemoryRange::new(addr, data.len())?;
 
     if !registers.has_ownership_range(&range) {
-        return Err(PanicReason::MemoryOwnership.into())
+        return Err(ownership_panic(&range))
     }
 
-    memory[range.usizes()].copy_from_slice(data);
+    memory.write(range.usizes()).copy_from_slice(data);
     Ok(())
 }
 
@@ -537,47 +959,55 @@ pub(crate) fn try_zeroize<A: ToAddr, B: ToAddr>(
     addr: A,
     len: B,
     registers: OwnershipRegisters,
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
 ) -> Result<(), RuntimeError> {
     let range = MemoryRange::new(addr, len)?;
 
     if !registers.has_ownership_range(&range) {
-        return Err(PanicReason::MemoryOwnership.into())
+        return Err(ownership_panic(&range))
     }
 
-    memory[range.usizes()].fill(0);
+    memory.write(range.usizes()).fill(0);
     Ok(())
 }
 
 /// Reads a constant-sized byte array from memory, performing overflow and memory range
 /// checks.
 pub(crate) fn read_bytes<const COUNT: usize>(
-    memory: &[u8; MEM_SIZE],
+    memory: &Memory,
     addr: Word,
 ) -> Result<[u8; COUNT], RuntimeError> {
     let addr = addr as usize;
     let (end, overflow) = addr.overflowing_add(COUNT);
 
     if overflow || end > VM_MAX_RAM as RegisterId {
-        return Err(PanicReason::MemoryOverflow.into())
+        return Err(memory_panic(
+            PanicReason::MemoryOverflow,
+            addr as Word,
+            COUNT as Word,
+        ))
     }
 
-    Ok(<[u8; COUNT]>::try_from(&memory[addr..end]).unwrap_or_else(|_| unreachable!()))
+    Ok(memory.read_bytes(addr))
 }
 
 /// Writes a constant-sized byte array to memory, performing overflow, memory range and
 /// ownership checks.
 pub(crate) fn write_bytes<const COUNT: usize>(
-    memory: &mut [u8; MEM_SIZE],
+    memory: &mut Memory,
     owner: OwnershipRegisters,
     addr: Word,
     bytes: [u8; COUNT],
 ) -> Result<(), RuntimeError> {
     let range = MemoryRange::new_const::<_, COUNT>(addr)?;
     if !owner.has_ownership_range(&range) {
-        return Err(PanicReason::MemoryOverflow.into())
+        return Err(memory_panic(
+            PanicReason::MemoryOverflow,
+            addr,
+            COUNT as Word,
+        ))
     }
 
-    memory[range.usizes()].copy_from_slice(&bytes);
+    memory.write(range.usizes()).copy_from_slice(&bytes);
     Ok(())
 }
@@ -24,7 +24,7 @@ fn test_malloc(mut hp: Word, sp: Word, a: Word) -> Result<Word, RuntimeError> {
 #[test_case(true, MEM_SIZE as Word - 10, 10 => Ok(()); "Memory range ends at last address")]
 #[test_case(true, 1, MEM_MAX_ACCESS_SIZE + 1 => Err(RuntimeError::Recoverable(PanicReason::MemoryOverflow)); "Memory range size exceeds limit")]
 fn test_memclear(has_ownership: bool, a: Word, b: Word) -> Result<(), RuntimeError> {
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let mut pc = 4;
     let mut owner = OwnershipRegisters {
         sp: 0,
@@ -63,7 +63,7 @@ fn test_memcopy(
     b: Word,
     c: Word,
 ) -> Result<(), RuntimeError> {
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     memory[b as usize..b as usize + c as usize].copy_from_slice(&vec![2u8; c as usize]);
     let mut pc = 4;
     let mut owner = OwnershipRegisters {
@@ -98,7 +98,7 @@ fn test_memcopy(
 #[test_case(0, 0, 0 => Ok(()); "smallest input values")]
 #[test_case(0, MEM_MAX_ACCESS_SIZE/2, MEM_MAX_ACCESS_SIZE/2 => Ok(()); "maximum range of addressable memory")]
 fn test_memeq(b: Word, c: Word, d: Word) -> Result<(), RuntimeError> {
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let r = (b as usize).min(MEM_SIZE)
         ..((b as usize).min(MEM_SIZE) + (d as usize).min(MEM_SIZE)).min(MEM_SIZE);
     memory[r].fill(2u8);
@@ -163,8 +163,9 @@ fn test_stack_pointer_overflow(
 #[test_case(0, VM_MAX_RAM - 1 => Ok(()); "c eq VM_MAX_RAM - 1")]
 #[test_case(u32::MAX as u64, u32::MAX as u64 => Err(RuntimeError::Recoverable(PanicReason::MemoryOverflow)); "b + c overflow")]
 fn test_load_byte(b: Word, c: Word) -> Result<(), RuntimeError> {
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
-    memory[((b + c) as usize).min(MEM_SIZE - 1)] = 2;
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let bc = ((b + c) as usize).min(MEM_SIZE - 1);
+    memory[bc..bc + 1][0] = 2;
     let mut pc = 4;
     let mut result = 0;
 
@@ -180,7 +181,7 @@ fn test_load_byte(b: Word, c: Word) -> Result<(), RuntimeError> {
 #[test_case(VM_MAX_RAM, 1 => Err(RuntimeError::Recoverable(PanicReason::MemoryOverflow)); "b + 8 * c gteq VM_MAX_RAM")]
 fn test_load_word(b: Word, c: Word) -> Result<(), RuntimeError> {
     // create a mutable memory with size `MEM_SIZE`
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
 
     // calculate start location where 8 bytes of value will be stored based on `b` and `c`
     // values.
@@ -216,7 +217,7 @@ fn test_store_byte(
     b: Word,
     c: Word,
 ) -> Result<(), RuntimeError> {
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let mut pc = 4;
     let mut owner = OwnershipRegisters {
         sp: 0,
@@ -235,7 +236,7 @@ fn test_store_byte(
     store_byte(&mut memory, owner, RegMut::new(&mut pc), a, b, c)?;
 
     assert_eq!(pc, 8);
-    assert_eq!(memory[(a + c) as usize], b as u8);
+    assert_eq!(memory.read_byte((a + c) as usize), b as u8);
 
     Ok(())
 }
@@ -249,7 +250,7 @@ fn test_store_word(
     b: Word,
     c: Word,
 ) -> Result<(), RuntimeError> {
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let mut pc = 4;
     let mut owner = OwnershipRegisters {
         sp: 0,
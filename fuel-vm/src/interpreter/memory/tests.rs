@@ -238,7 +238,7 @@ fn test_mem_write(
     data: &[u8],
     registers: OwnershipRegisters,
 ) -> (bool, [u8; 100]) {
-    let mut memory: Memory<MEM_SIZE> = vec![0u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![0u8; MEM_SIZE].try_into().unwrap();
     let r = try_mem_write(addr, data, registers, &mut memory).is_ok();
     let memory: [u8; 100] = memory[..100].try_into().unwrap();
     (r, memory)
@@ -294,8 +294,118 @@ fn test_try_zeroize(
     len: usize,
     registers: OwnershipRegisters,
 ) -> (bool, [u8; 100]) {
-    let mut memory: Memory<MEM_SIZE> = vec![1u8; MEM_SIZE].try_into().unwrap();
+    let mut memory: Memory = vec![1u8; MEM_SIZE].try_into().unwrap();
     let r = try_zeroize(addr, len, registers, &mut memory).is_ok();
     let memory: [u8; 100] = memory[..100].try_into().unwrap();
     (r, memory)
 }
+
+#[test]
+fn memory_is_allocated_on_first_write() {
+    let mut memory = Memory::new();
+    assert_eq!(memory.allocated(), 0);
+
+    memory.write(10..20).fill(1);
+    assert_eq!(memory.allocated(), MEM_PAGE_SIZE);
+
+    memory.write(MEM_SIZE - 10..MEM_SIZE).fill(2);
+    assert_eq!(memory.allocated(), 2 * MEM_PAGE_SIZE);
+
+    // Reads never allocate, and unallocated memory reads as zero
+    assert_eq!(&*memory.read(5..15), &[0, 0, 0, 0, 0, 1, 1, 1, 1, 1]);
+    assert_eq!(&*memory.read(MEM_SIZE / 2..MEM_SIZE / 2 + 4), &[0; 4]);
+    assert_eq!(&*memory.read(MEM_SIZE - 12..MEM_SIZE - 8), &[0, 0, 2, 2]);
+    assert_eq!(memory.allocated(), 2 * MEM_PAGE_SIZE);
+}
+
+#[test_case(0..MEM_SIZE; "Whole address space")]
+#[test_case(MEM_PAGE_SIZE - 1..MEM_SIZE - MEM_PAGE_SIZE + 1; "Across the gap")]
+#[test_case(MEM_SIZE / 2 - 1..MEM_SIZE / 2 + 1; "Inside the gap")]
+fn memory_read_spanning_unallocated_pages(range: Range<usize>) {
+    let mut memory = Memory::new();
+    memory.write(0..MEM_PAGE_SIZE).fill(1);
+    memory.write(MEM_SIZE - MEM_PAGE_SIZE..MEM_SIZE).fill(2);
+
+    let expected: Vec<u8> = (0..MEM_SIZE)
+        .map(|addr| match addr {
+            a if a < MEM_PAGE_SIZE => 1,
+            a if a >= MEM_SIZE - MEM_PAGE_SIZE => 2,
+            _ => 0,
+        })
+        .collect();
+
+    assert_eq!(&*memory.read(range.clone()), &expected[range]);
+}
+
+#[test]
+fn memory_write_spanning_the_gap_merges_pages() {
+    let mut memory = Memory::new();
+    memory.write(MEM_SIZE - 1..MEM_SIZE).fill(2);
+    memory.write(0..MEM_SIZE).fill(1);
+
+    assert_eq!(memory.allocated(), MEM_SIZE);
+    assert!(memory.iter().all(|b| *b == 1));
+}
+
+#[test_case(0, MEM_SIZE - 64, 32; "Stack to heap")]
+#[test_case(MEM_SIZE - 64, 0, 32; "Heap to stack")]
+#[test_case(MEM_SIZE / 2, 16, 32; "Unallocated to stack")]
+#[test_case(16, 32, 32; "Overlapping")]
+fn memory_copy_within(src: usize, dst: usize, len: usize) {
+    let mut memory = Memory::new();
+    memory.write(0..64).copy_from_slice(&[3; 64]);
+    memory
+        .write(MEM_SIZE - 64..MEM_SIZE)
+        .copy_from_slice(&[4; 64]);
+
+    let mut expected = memory.to_vec();
+    expected.copy_within(src..src + len, dst);
+
+    memory.copy_within(src, dst, len);
+
+    assert_eq!(memory.to_vec(), expected);
+}
+
+#[test]
+fn memory_equality_ignores_allocation() {
+    let mut a = Memory::new();
+    let mut b = Memory::new();
+    assert_eq!(a, b);
+
+    b.write(MEM_SIZE / 2..MEM_SIZE / 2 + 1).fill(0);
+    assert_eq!(a, b);
+
+    a.write(0..1).fill(1);
+    assert_ne!(a, b);
+}
+
+#[test]
+fn interpreter_memory_is_allocated_lazily() {
+    let mut vm = Interpreter::with_memory_storage();
+    assert_eq!(vm.paged_memory().allocated(), 0);
+
+    let script = [
+        op::movi(0x10, 1024),
+        op::aloc(0x10),
+        op::sw(RegId::HP, 0x10, 0),
+        op::ret(RegId::ONE),
+    ]
+    .into_iter()
+    .collect();
+    let tx = TransactionBuilder::script(script, vec![])
+        .gas_limit(1_000_000)
+        .add_random_fee_input()
+        .finalize()
+        .into_checked(Default::default(), &Default::default(), &Default::default())
+        .expect("failed to generate a checked tx");
+
+    vm.transact(tx).expect("failed to execute the script");
+
+    let hp = vm.registers()[RegId::HP] as usize;
+    assert_eq!(vm.paged_memory().read_bytes(hp), 1024u64.to_be_bytes());
+    assert!(vm.paged_memory().allocated() < MEM_SIZE / 64);
+
+    let memory = vm.memory();
+    assert_eq!(memory.len(), MEM_SIZE);
+    assert_eq!(memory[hp..hp + 8], 1024u64.to_be_bytes());
+}
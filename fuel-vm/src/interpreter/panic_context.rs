@@ -0,0 +1,65 @@
+//! Details of the reason of a panic, recorded by the check failing the
+//! instruction.
+
+#[cfg(test)]
+mod tests;
+
+use super::{
+    Interpreter,
+    PanicContext,
+};
+use crate::error::RuntimeError;
+
+use fuel_asm::PanicReason;
+use fuel_types::Word;
+
+use std::cell::Cell;
+
+thread_local! {
+    /// Context of the last failed check on this thread, with its panic reason.
+    ///
+    /// The memory and balance checks are free functions without access to the
+    /// interpreter, so they leave the context here and the interpreter collects
+    /// it once the instruction fails.
+    static RECORDED: Cell<Option<(PanicReason, PanicContext)>> = const { Cell::new(None) };
+}
+
+/// Panic with `reason`, recording `context` for the panic receipt.
+pub(crate) fn panic_with_context(
+    reason: PanicReason,
+    context: PanicContext,
+) -> RuntimeError {
+    RECORDED.with(|recorded| recorded.set(Some((reason, context))));
+    reason.into()
+}
+
+/// Panic with `reason` on the access to `[address, address + size[`.
+pub(crate) fn memory_panic(
+    reason: PanicReason,
+    address: Word,
+    size: Word,
+) -> RuntimeError {
+    panic_with_context(reason, PanicContext::MemoryRange { address, size })
+}
+
+/// Discard the context recorded by a check whose failure was handled.
+pub(crate) fn clear_recorded_panic_context() {
+    RECORDED.with(|recorded| recorded.set(None));
+}
+
+impl<S, Tx> Interpreter<S, Tx> {
+    /// Record the details of the panic with `reason`, unless the instruction
+    /// already did.
+    ///
+    /// The context left by the failed check is only used if it failed with the
+    /// same reason as the instruction.
+    pub(crate) fn record_panic_context(&mut self, reason: PanicReason) {
+        let recorded = RECORDED.with(Cell::take);
+
+        if let Some((recorded, context)) = recorded {
+            if recorded == reason && self.panic_context.is_none() {
+                self.panic_context = context;
+            }
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use fuel_asm::{
+    op,
+    Instruction,
+    PanicReason,
+    RegId,
+};
+use fuel_tx::{
+    ConsensusParameters,
+    Finalizable,
+    PanicContext,
+    Receipt,
+    Script,
+    TransactionBuilder,
+};
+use fuel_types::{
+    AssetId,
+    ContractId,
+    Word,
+};
+
+use super::memory_panic;
+use crate::{
+    checked_transaction::IntoChecked,
+    prelude::*,
+};
+
+/// Panic reason and context of `script`.
+fn panic(script: Vec<Instruction>, gas_limit: Word) -> (PanicReason, PanicContext) {
+    let mut vm = Interpreter::<_, Script>::with_memory_storage();
+    let tx = TransactionBuilder::script(script.into_iter().collect(), vec![])
+        .gas_limit(gas_limit)
+        .add_random_fee_input()
+        .finalize()
+        .into_checked(
+            Default::default(),
+            &ConsensusParameters::default(),
+            vm.gas_costs(),
+        )
+        .expect("failed to generate checked tx");
+
+    vm.transact(tx)
+        .expect("Failed to transact")
+        .receipts()
+        .iter()
+        .find_map(|receipt| match receipt {
+            Receipt::Panic {
+                reason, context, ..
+            } => Some((*reason.reason(), *context)),
+            _ => None,
+        })
+        .expect("Expected a panic")
+}
+
+#[test]
+fn memory_ownership_has_the_range() {
+    let script = vec![
+        op::movi(0x10, 16),
+        op::movi(0x11, 8),
+        op::mcp(0x10, RegId::ZERO, 0x11),
+    ];
+
+    assert_eq!(
+        panic(script, 1_000_000),
+        (
+            PanicReason::MemoryOwnership,
+            PanicContext::MemoryRange {
+                address: 16,
+                size: 8
+            }
+        )
+    );
+}
+
+#[test]
+fn memory_overflow_has_the_range() {
+    let script = vec![
+        op::movi(0x10, 16),
+        op::not(0x11, RegId::ZERO),
+        op::mcp(0x10, 0x11, 0x10),
+    ];
+
+    assert_eq!(
+        panic(script, 1_000_000),
+        (
+            PanicReason::MemoryOverflow,
+            PanicContext::MemoryRange {
+                address: Word::MAX,
+                size: 16
+            }
+        )
+    );
+}
+
+#[test]
+fn contract_not_in_inputs_has_the_contract_id() {
+    // The transaction id is at address zero
+    let script = vec![op::csiz(0x10, RegId::ZERO)];
+
+    let (reason, context) = panic(script, 1_000_000);
+    assert_eq!(reason, PanicReason::ContractNotInInputs);
+    assert!(
+        matches!(context, PanicContext::ContractId(id) if id != ContractId::zeroed())
+    );
+}
+
+#[test]
+fn not_enough_balance_has_the_asset_id_and_amounts() {
+    let script = vec![
+        op::movi(0x10, 1),
+        op::smo(RegId::ZERO, RegId::ZERO, RegId::ZERO, 0x10),
+    ];
+
+    assert_eq!(
+        panic(script, 1_000_000),
+        (
+            PanicReason::NotEnoughBalance,
+            PanicContext::Balance {
+                asset_id: AssetId::zeroed(),
+                expected: 1,
+                actual: 0
+            }
+        )
+    );
+}
+
+#[test]
+fn allocated_stack_has_the_stack_start_and_pointer() {
+    let script = vec![op::cfei(8), op::ldc(RegId::ZERO, RegId::ZERO, RegId::ZERO)];
+
+    let (reason, context) = panic(script, 1_000_000);
+    assert_eq!(reason, PanicReason::ExpectedUnallocatedStack);
+    assert!(matches!(
+        context,
+        PanicContext::Values { expected, actual } if actual == expected + 8
+    ));
+}
+
+#[test]
+fn context_of_another_reason_is_discarded() {
+    let mut vm = Interpreter::<_, Script>::with_memory_storage();
+
+    let _ = memory_panic(PanicReason::MemoryOverflow, 16, 8);
+    vm.record_panic_context(PanicReason::OutOfGas);
+    assert_eq!(vm.panic_context, PanicContext::None);
+
+    // The context was taken, it doesn't leak into a later panic
+    vm.record_panic_context(PanicReason::MemoryOverflow);
+    assert_eq!(vm.panic_context, PanicContext::None);
+}
+
+#[test]
+fn out_of_gas_has_the_expected_and_available_gas() {
+    let script = vec![op::noop()];
+
+    assert_eq!(
+        panic(script, 0),
+        (
+            PanicReason::OutOfGas,
+            PanicContext::Values {
+                expected: GasCosts::default().noop,
+                actual: 0
+            }
+        )
+    );
+}
@@ -1,6 +1,10 @@
 use core::ops::Index;
-use std::mem;
+use std::{
+    fmt,
+    mem,
+};
 
+use dyn_clone::DynClone;
 use fuel_merkle::binary;
 use fuel_tx::Receipt;
 use fuel_types::{
@@ -8,18 +12,45 @@ use fuel_types::{
     Bytes32,
 };
 
-#[derive(Debug, Default, Clone)]
+/// Receives the receipts of the transactions while they are executed.
+pub trait ReceiptSink: DynClone {
+    /// Called when `receipt` is appended, even if the transaction reverts
+    /// later.
+    fn on_receipt(&mut self, receipt: &Receipt);
+
+    /// Called once the transaction is over, with whether it reverted.
+    fn on_transaction(&mut self, reverted: bool);
+}
+
+dyn_clone::clone_trait_object!(ReceiptSink);
+
+#[derive(Default, Clone)]
 pub(crate) struct ReceiptsCtx {
     receipts: Vec<Receipt>,
     receipts_tree: binary::in_memory::MerkleTree,
+    sink: Option<Box<dyn ReceiptSink + Send + Sync>>,
 }
 
 impl ReceiptsCtx {
     pub fn push(&mut self, mut receipt: Receipt) {
+        if let Some(sink) = &mut self.sink {
+            sink.on_receipt(&receipt);
+        }
         self.receipts_tree.push(receipt.to_bytes().as_slice());
         self.receipts.push(receipt)
     }
 
+    pub fn set_sink(&mut self, sink: Box<dyn ReceiptSink + Send + Sync>) {
+        self.sink = Some(sink);
+    }
+
+    /// Notify the sink that the transaction is over.
+    pub fn complete(&mut self, reverted: bool) {
+        if let Some(sink) = &mut self.sink {
+            sink.on_transaction(reverted);
+        }
+    }
+
     pub fn clear(&mut self) {
         self.receipts_tree.reset();
         self.receipts.clear();
@@ -64,6 +95,22 @@ impl AsRef<Vec<Receipt>> for ReceiptsCtx {
     }
 }
 
+impl fmt::Debug for ReceiptsCtx {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReceiptsCtx")
+            .field("receipts", &self.receipts)
+            .field("receipts_tree", &self.receipts_tree)
+            .field(
+                "sink",
+                &match self.sink {
+                    Some(_) => "enabled",
+                    None => "disabled",
+                },
+            )
+            .finish()
+    }
+}
+
 impl PartialEq for ReceiptsCtx {
     fn eq(&self, other: &Self) -> bool {
         self.root() == other.root()
@@ -119,12 +166,68 @@ impl<'a> Drop for ReceiptsCtxMut<'a> {
 #[cfg(test)]
 mod tests {
     use crate::{
+        checked_transaction::IntoChecked,
         crypto::ephemeral_merkle_root,
         interpreter::receipts::ReceiptsCtx,
+        prelude::*,
+    };
+    use fuel_asm::{
+        op,
+        Instruction,
+        RegId,
+    };
+    use fuel_tx::{
+        ConsensusParameters,
+        Finalizable,
+        Receipt,
+        Script,
+        TransactionBuilder,
     };
-    use fuel_tx::Receipt;
     use fuel_types::bytes::SerializableVec;
-    use std::iter;
+    use std::{
+        iter,
+        sync::{
+            Arc,
+            Mutex,
+        },
+    };
+
+    /// Sink recording the receipts and the outcome of the transactions.
+    #[derive(Debug, Default, Clone)]
+    struct RecordingSink {
+        receipts: Arc<Mutex<Vec<Receipt>>>,
+        reverted: Arc<Mutex<Vec<bool>>>,
+    }
+
+    impl ReceiptSink for RecordingSink {
+        fn on_receipt(&mut self, receipt: &Receipt) {
+            self.receipts.lock().unwrap().push(receipt.clone());
+        }
+
+        fn on_transaction(&mut self, reverted: bool) {
+            self.reverted.lock().unwrap().push(reverted);
+        }
+    }
+
+    fn transact_with_sink(script: Vec<Instruction>) -> RecordingSink {
+        let sink = RecordingSink::default();
+        let mut vm = Interpreter::<_, Script>::with_memory_storage();
+        vm.with_receipt_sink(sink.clone());
+        let tx = TransactionBuilder::script(script.into_iter().collect(), vec![])
+            .gas_limit(1_000_000)
+            .add_random_fee_input()
+            .finalize()
+            .into_checked(
+                Default::default(),
+                &ConsensusParameters::default(),
+                vm.gas_costs(),
+            )
+            .expect("failed to generate checked tx");
+
+        let state = vm.transact(tx).expect("Failed to transact");
+        assert_eq!(*sink.receipts.lock().unwrap(), state.receipts());
+        sink
+    }
 
     fn create_receipt() -> Receipt {
         Receipt::call(
@@ -177,4 +280,41 @@ mod tests {
         let expected_root = ephemeral_merkle_root(leaves);
         assert_eq!(root, expected_root)
     }
+
+    #[test]
+    fn sink_is_notified_of_each_receipt() {
+        let mut ctx = ReceiptsCtx::default();
+        let sink = RecordingSink::default();
+        ctx.set_sink(Box::new(sink.clone()));
+
+        for _ in 0..3 {
+            ctx.push(create_receipt());
+            assert_eq!(sink.receipts.lock().unwrap().last(), ctx.as_ref().last());
+        }
+        assert_eq!(*sink.receipts.lock().unwrap(), *ctx.as_ref());
+    }
+
+    #[test]
+    fn sink_reports_successful_transaction() {
+        let sink = transact_with_sink(vec![
+            op::log(RegId::ONE, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+            op::ret(RegId::ONE),
+        ]);
+
+        assert_eq!(sink.receipts.lock().unwrap().len(), 3);
+        assert_eq!(*sink.reverted.lock().unwrap(), vec![false]);
+    }
+
+    #[test]
+    fn sink_receives_receipts_of_reverted_transaction() {
+        let sink = transact_with_sink(vec![
+            op::log(RegId::ONE, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+            op::rvrt(RegId::ONE),
+        ]);
+
+        let receipts = sink.receipts.lock().unwrap();
+        assert!(matches!(receipts[0], Receipt::Log { ra: 1, .. }));
+        assert!(matches!(receipts[1], Receipt::Revert { ra: 1, .. }));
+        assert_eq!(*sink.reverted.lock().unwrap(), vec![true]);
+    }
 }
@@ -0,0 +1,97 @@
+use super::{
+    ExecutableTransaction,
+    Interpreter,
+};
+use crate::constraints::CheckedMemConstLen;
+
+use fuel_asm::{
+    Instruction,
+    RegId,
+};
+use fuel_types::{
+    Bytes32,
+    ContractId,
+    Word,
+};
+use primitive_types::U256;
+
+/// Contiguous range of contract state slots accessed by an instruction.
+pub(crate) struct StateSlots {
+    /// Whether the slots are written or cleared, rather than read.
+    #[cfg_attr(not(feature = "trace"), allow(dead_code))]
+    pub write: bool,
+    pub contract: ContractId,
+    pub start_key: Bytes32,
+    pub count: Word,
+}
+
+impl StateSlots {
+    /// Keys of the accessed slots, in order.
+    #[cfg(feature = "trace")]
+    pub fn keys(&self) -> impl Iterator<Item = Bytes32> {
+        let start = U256::from_big_endian(self.start_key.as_ref());
+
+        (0..self.count).map_while(move |i| {
+            let mut key = Bytes32::zeroed();
+            start.checked_add(i.into())?.to_big_endian(key.as_mut());
+            Some(key)
+        })
+    }
+
+    /// Check if `key` is one of the accessed slots.
+    #[cfg(feature = "debug")]
+    pub fn contains(&self, key: &Bytes32) -> bool {
+        let start = U256::from_big_endian(self.start_key.as_ref());
+        let key = U256::from_big_endian(key.as_ref());
+
+        matches!(key.checked_sub(start), Some(offset) if offset < U256::from(self.count))
+    }
+}
+
+impl<S, Tx> Interpreter<S, Tx>
+where
+    Tx: ExecutableTransaction,
+{
+    /// Contract state slots the instruction is about to access.
+    ///
+    /// The keys are read before the execution, since the instruction might
+    /// overwrite the memory they are stored in.
+    pub(crate) fn state_slots(&self, instruction: &Instruction) -> Option<StateSlots> {
+        let r = |id: RegId| self.registers[id];
+
+        let (write, key, count) = match instruction {
+            Instruction::SRW(srw) => {
+                let (_, _, c) = srw.unpack();
+                (false, r(c), 1)
+            }
+            Instruction::SRWQ(srwq) => {
+                let (_, _, c, d) = srwq.unpack();
+                (false, r(c), r(d))
+            }
+            Instruction::SWW(sww) => {
+                let (a, _, _) = sww.unpack();
+                (true, r(a), 1)
+            }
+            Instruction::SWWQ(swwq) => {
+                let (a, _, _, d) = swwq.unpack();
+                (true, r(a), r(d))
+            }
+            Instruction::SCWQ(scwq) => {
+                let (a, _, c) = scwq.unpack();
+                (true, r(a), r(c))
+            }
+            _ => return None,
+        };
+
+        let contract = self.internal_contract().ok()?;
+        let start_key = CheckedMemConstLen::<{ Bytes32::LEN }>::new(key).ok()?;
+        let start_key = Bytes32::from(start_key.read(&self.memory));
+
+        Some(StateSlots {
+            write,
+            contract,
+            start_key,
+            count,
+        })
+    }
+}
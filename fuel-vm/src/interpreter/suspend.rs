@@ -0,0 +1,644 @@
+//! Suspension of a paused [`Interpreter`] to a versioned blob.
+//!
+//! The blob holds everything the interpreter needs to continue with
+//! [`Interpreter::resume`]: registers, memory, call frames, receipts,
+//! balances, context, debugger state and the transaction. The storage is
+//! saved separately, with a [`StorageSnapshot`] for a [`MemoryStorage`].
+//!
+//! The consensus parameters and gas costs aren't part of the blob and must be
+//! provided again, and the opcode policy is reset. The profiler, tracer, recorded steps
+//! and storage accesses start over after a restore.
+
+#[cfg(test)]
+mod tests;
+
+use super::{
+    balances::Balance,
+    ExecutableTransaction,
+    InitialBalances,
+    Interpreter,
+    Memory,
+    MemoryRange,
+    PanicContext,
+    RuntimeBalances,
+};
+use crate::{
+    call::CallFrame,
+    checked_transaction::{
+        NonRetryableFreeBalances,
+        RetryableAmount,
+    },
+    consts::*,
+    context::Context,
+    gas::GasCosts,
+    interpreter::ecal::EcalSlot,
+    policy::OpcodePolicy,
+    predicate::RuntimePredicate,
+    profiler::Profiler,
+    state::{
+        Breakpoint,
+        DebugEval,
+        Debugger,
+        ProgramState,
+        RegisterCondition,
+        Watchpoint,
+    },
+    storage::{
+        MemoryStorage,
+        StorageSnapshot,
+    },
+};
+
+use fuel_tx::{
+    Cacheable,
+    ConsensusParameters,
+    Receipt,
+    ReceiptRepr,
+};
+use fuel_types::{
+    bytes::{
+        Deserializable,
+        SerializableVec,
+    },
+    Word,
+};
+
+use std::io::{
+    Read,
+    Write,
+};
+
+/// Header of the blob.
+const MAGIC: [u8; 8] = *b"FUELVMSP";
+
+/// Failure to restore a suspended interpreter.
+#[derive(Debug, thiserror::Error)]
+pub enum SuspendError {
+    /// The blob was written in another version of the format.
+    #[error("Unsupported suspended interpreter version {0}")]
+    UnsupportedVersion(u32),
+    /// The blob is truncated or corrupted.
+    #[error("Malformed suspended interpreter")]
+    Malformed,
+}
+
+impl<S, Tx> Interpreter<S, Tx>
+where
+    Tx: ExecutableTransaction,
+{
+    /// Version of the suspended interpreter format.
+    pub const SUSPEND_VERSION: u32 = 3;
+
+    /// Encode the state of the interpreter, to be restored with
+    /// [`Interpreter::restore`].
+    ///
+    /// The storage isn't included.
+    pub fn suspend(&self) -> Vec<u8> {
+        let mut w = Writer(MAGIC.to_vec());
+        w.0.extend_from_slice(&Self::SUSPEND_VERSION.to_be_bytes());
+
+        for register in self.registers {
+            w.word(register);
+        }
+
+        let (stack, heap) = self.memory.sides();
+        w.bytes(stack);
+        w.bytes(heap);
+
+        w.len(self.frames.len());
+        for frame in &self.frames {
+            let mut buf = [0; CallFrame::serialized_size()];
+            frame
+                .clone()
+                .read_exact(&mut buf)
+                .expect("The buffer has the size of a frame");
+            w.0.extend_from_slice(&buf);
+        }
+
+        w.len(self.receipts.len());
+        for receipt in self.receipts.as_ref() {
+            w.bytes(&receipt.clone().to_bytes());
+        }
+
+        w.bytes(&self.tx.clone().to_bytes());
+
+        w.len(self.initial_balances.non_retryable.len());
+        for (asset, amount) in self.initial_balances.non_retryable.iter() {
+            w.0.extend_from_slice(asset.as_ref());
+            w.word(*amount);
+        }
+        match self.initial_balances.retryable {
+            Some(amount) => {
+                w.flag(true);
+                w.word(*amount);
+            }
+            None => w.flag(false),
+        }
+
+        let mut balances: Vec<_> = self.balances.iter_with_offsets().collect();
+        balances.sort_by_key(|(asset, _)| **asset);
+        w.len(balances.len());
+        for (asset, balance) in balances {
+            w.0.extend_from_slice(asset.as_ref());
+            w.word(balance.value());
+            w.word(balance.offset() as Word);
+        }
+
+        match &self.context {
+            Context::NotInitialized => w.0.push(0),
+            Context::PredicateEstimation { program } => {
+                w.0.push(1);
+                w.predicate(program);
+            }
+            Context::PredicateVerification { program } => {
+                w.0.push(2);
+                w.predicate(program);
+            }
+            Context::Script { block_height } => {
+                w.0.push(3);
+                w.0.extend_from_slice(&block_height.to_bytes());
+            }
+            Context::Call { block_height } => {
+                w.0.push(4);
+                w.0.extend_from_slice(&block_height.to_bytes());
+            }
+        }
+
+        match &self.panic_context {
+            PanicContext::None => w.0.push(0),
+            PanicContext::ContractId(id) => {
+                w.0.push(1);
+                w.0.extend_from_slice(id.as_ref());
+            }
+            PanicContext::AssetId(id) => {
+                w.0.push(2);
+                w.0.extend_from_slice(id.as_ref());
+            }
+            PanicContext::MemoryRange { address, size } => {
+                w.0.push(3);
+                w.word(*address);
+                w.word(*size);
+            }
+            PanicContext::Values { expected, actual } => {
+                w.0.push(4);
+                w.word(*expected);
+                w.word(*actual);
+            }
+            PanicContext::Balance {
+                asset_id,
+                expected,
+                actual,
+            } => {
+                w.0.push(5);
+                w.0.extend_from_slice(asset_id.as_ref());
+                w.word(*expected);
+                w.word(*actual);
+            }
+        }
+
+        w.flag(self.yielded);
+
+        w.flag(self.debugger.single_stepping());
+        let mut breakpoints: Vec<_> = self.debugger.breakpoints().collect();
+        breakpoints.sort_by_key(|b| (*b.contract(), b.pc()));
+        w.len(breakpoints.len());
+        for breakpoint in &breakpoints {
+            w.breakpoint(breakpoint);
+        }
+        w.len(self.debugger.watchpoints().len());
+        for watchpoint in self.debugger.watchpoints() {
+            w.watchpoint(watchpoint);
+        }
+        match self.debugger.last_state() {
+            Some(state) => {
+                w.flag(true);
+                w.program_state(state);
+            }
+            None => w.flag(false),
+        }
+        w.word(self.debugger.history_limit() as Word);
+        w.word(self.debugger.history_bytes_limit() as Word);
+
+        w.0
+    }
+
+    /// Restore an interpreter encoded with [`Interpreter::suspend`] on top of
+    /// `storage`.
+    ///
+    /// The storage should be in the state it was in when the interpreter was
+    /// suspended.
+    pub fn restore(
+        bytes: &[u8],
+        storage: S,
+        params: ConsensusParameters,
+        gas_costs: GasCosts,
+    ) -> Result<Self, SuspendError>
+    where
+        Tx: Deserializable + Cacheable,
+    {
+        let mut r = Reader(bytes);
+        if r.array()? != MAGIC {
+            return Err(SuspendError::Malformed)
+        }
+        let version = u32::from_be_bytes(r.array()?);
+        if version != Self::SUSPEND_VERSION {
+            return Err(SuspendError::UnsupportedVersion(version))
+        }
+
+        let mut registers = [0; VM_REGISTER_COUNT];
+        for register in registers.iter_mut() {
+            *register = r.word()?;
+        }
+
+        let stack = r.bytes()?.to_vec();
+        let heap = r.bytes()?.to_vec();
+        let memory = Memory::from_sides(stack, heap).ok_or(SuspendError::Malformed)?;
+
+        let mut frames = vec![];
+        for _ in 0..r.len()? {
+            let mut frame = CallFrame::new(
+                Default::default(),
+                Default::default(),
+                [0; VM_REGISTER_COUNT],
+                0,
+                0,
+                0,
+            );
+            frame
+                .write_all(r.take(CallFrame::serialized_size())?)
+                .map_err(|_| SuspendError::Malformed)?;
+            frames.push(frame);
+        }
+
+        let mut receipts = super::ReceiptsCtx::default();
+        for _ in 0..r.len()? {
+            let receipt =
+                Receipt::from_bytes(r.bytes()?).map_err(|_| SuspendError::Malformed)?;
+            receipts.push(receipt);
+        }
+
+        let mut tx = Tx::from_bytes(r.bytes()?).map_err(|_| SuspendError::Malformed)?;
+        tx.precompute(&params.chain_id)
+            .map_err(|_| SuspendError::Malformed)?;
+
+        let mut non_retryable = NonRetryableFreeBalances::default();
+        for _ in 0..r.len()? {
+            let asset = r.array()?.into();
+            non_retryable.0.insert(asset, r.word()?);
+        }
+        let retryable = match r.flag()? {
+            true => Some(RetryableAmount(r.word()?)),
+            false => None,
+        };
+        let initial_balances = InitialBalances {
+            non_retryable,
+            retryable,
+        };
+
+        let mut balances = vec![];
+        for _ in 0..r.len()? {
+            let asset = r.array()?.into();
+            let value = r.word()?;
+            let offset = r.offset()?;
+            balances.push((asset, Balance::new(value, offset)));
+        }
+        let balances = RuntimeBalances::from_offsets(balances);
+
+        let context = match r.array::<1>()? {
+            [0] => Context::NotInitialized,
+            [1] => Context::PredicateEstimation {
+                program: r.predicate()?,
+            },
+            [2] => Context::PredicateVerification {
+                program: r.predicate()?,
+            },
+            [3] => Context::Script {
+                block_height: u32::from_be_bytes(r.array()?).into(),
+            },
+            [4] => Context::Call {
+                block_height: u32::from_be_bytes(r.array()?).into(),
+            },
+            _ => return Err(SuspendError::Malformed),
+        };
+
+        let panic_context = match r.array::<1>()? {
+            [0] => PanicContext::None,
+            [1] => PanicContext::ContractId(r.array()?.into()),
+            [2] => PanicContext::AssetId(r.array()?.into()),
+            [3] => PanicContext::MemoryRange {
+                address: r.word()?,
+                size: r.word()?,
+            },
+            [4] => PanicContext::Values {
+                expected: r.word()?,
+                actual: r.word()?,
+            },
+            [5] => PanicContext::Balance {
+                asset_id: r.array()?.into(),
+                expected: r.word()?,
+                actual: r.word()?,
+            },
+            _ => return Err(SuspendError::Malformed),
+        };
+
+        let yielded = r.flag()?;
+
+        let mut debugger = Debugger::default();
+        debugger.set_single_stepping(r.flag()?);
+        for _ in 0..r.len()? {
+            debugger.set_breakpoint(r.breakpoint()?);
+        }
+        for _ in 0..r.len()? {
+            debugger.set_watchpoint(r.watchpoint()?);
+        }
+        if r.flag()? {
+            debugger.set_last_state(r.program_state()?);
+        }
+        debugger.set_history_limit(r.offset()?);
+        debugger.set_history_bytes_limit(r.offset()?);
+
+        if !r.0.is_empty() {
+            return Err(SuspendError::Malformed)
+        }
+
+        Ok(Self {
+            registers,
+            memory,
+            frames,
+            receipts,
+            tx,
+            initial_balances,
+            storage,
+            debugger,
+            context,
+            balances,
+            gas_costs,
+            opcode_policy: OpcodePolicy::default(),
+            ecal: EcalSlot::default(),
+            profiler: Profiler::default(),
+            #[cfg(feature = "trace")]
+            tracer: Default::default(),
+            read_write_set: None,
+            state_diff: None,
+            params,
+            schedule: None,
+            yielded,
+            panic_context,
+        })
+    }
+}
+
+impl<Tx> Interpreter<MemoryStorage, Tx>
+where
+    Tx: ExecutableTransaction,
+{
+    /// Encode the state of the interpreter with a snapshot of its storage.
+    pub fn suspend_with_storage(&self) -> (Vec<u8>, StorageSnapshot) {
+        (self.suspend(), StorageSnapshot::from_storage(&self.storage))
+    }
+
+    /// Restore an interpreter encoded with
+    /// [`Interpreter::suspend_with_storage`].
+    ///
+    /// The changes of the suspended transaction are part of the snapshot, so
+    /// they are committed in the restored storage and a revert of the
+    /// transaction doesn't discard them.
+    pub fn restore_with_storage(
+        bytes: &[u8],
+        snapshot: &StorageSnapshot,
+        params: ConsensusParameters,
+        gas_costs: GasCosts,
+    ) -> Result<Self, SuspendError>
+    where
+        Tx: Deserializable + Cacheable,
+    {
+        Self::restore(bytes, snapshot.to_memory_storage(), params, gas_costs)
+    }
+}
+
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn word(&mut self, word: Word) {
+        self.0.extend_from_slice(&word.to_be_bytes());
+    }
+
+    fn len(&mut self, len: usize) {
+        let len = u32::try_from(len).expect("Length exceeds u32::MAX");
+        self.0.extend_from_slice(&len.to_be_bytes());
+    }
+
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.len(bytes.len());
+        self.0.extend_from_slice(bytes);
+    }
+
+    fn flag(&mut self, flag: bool) {
+        self.0.push(flag as u8);
+    }
+
+    fn predicate(&mut self, predicate: &RuntimePredicate) {
+        self.word(predicate.program().start as Word);
+        self.word(predicate.program().len() as Word);
+        self.word(predicate.idx() as Word);
+    }
+
+    fn breakpoint(&mut self, breakpoint: &Breakpoint) {
+        self.0.extend_from_slice(breakpoint.contract().as_ref());
+        self.word(breakpoint.pc());
+    }
+
+    fn watchpoint(&mut self, watchpoint: &Watchpoint) {
+        match watchpoint {
+            Watchpoint::Register {
+                register,
+                condition,
+            } => {
+                self.0.push(0);
+                self.word(*register as Word);
+                let (tag, operand) = match *condition {
+                    RegisterCondition::Equal(w) => (0, w),
+                    RegisterCondition::NotEqual(w) => (1, w),
+                    RegisterCondition::Less(w) => (2, w),
+                    RegisterCondition::LessOrEqual(w) => (3, w),
+                    RegisterCondition::Greater(w) => (4, w),
+                    RegisterCondition::GreaterOrEqual(w) => (5, w),
+                };
+                self.0.push(tag);
+                self.word(operand);
+            }
+            Watchpoint::Memory { start, end } => {
+                self.0.push(1);
+                self.word(*start as Word);
+                self.word(*end as Word);
+            }
+            Watchpoint::Storage { contract, key } => {
+                self.0.push(2);
+                self.0.extend_from_slice(contract.as_ref());
+                self.0.extend_from_slice(key.as_ref());
+            }
+            Watchpoint::Receipt(repr) => {
+                self.0.push(3);
+                self.word(*repr as Word);
+            }
+            Watchpoint::GasBelow(threshold) => {
+                self.0.push(4);
+                self.word(*threshold);
+            }
+        }
+    }
+
+    fn debug_eval(&mut self, eval: &DebugEval) {
+        match eval {
+            DebugEval::Breakpoint(breakpoint) => {
+                self.0.push(0);
+                self.breakpoint(breakpoint);
+            }
+            DebugEval::Watchpoint {
+                location,
+                watchpoint,
+            } => {
+                self.0.push(1);
+                self.breakpoint(location);
+                self.watchpoint(watchpoint);
+            }
+            DebugEval::Continue => self.0.push(2),
+        }
+    }
+
+    fn program_state(&mut self, state: &ProgramState) {
+        match state {
+            ProgramState::Return(w) => {
+                self.0.push(0);
+                self.word(*w);
+            }
+            ProgramState::ReturnData(digest) => {
+                self.0.push(1);
+                self.0.extend_from_slice(digest.as_ref());
+            }
+            ProgramState::Revert(w) => {
+                self.0.push(2);
+                self.word(*w);
+            }
+            ProgramState::RunProgram(eval) => {
+                self.0.push(3);
+                self.debug_eval(eval);
+            }
+            ProgramState::VerifyPredicate(eval) => {
+                self.0.push(4);
+                self.debug_eval(eval);
+            }
+        }
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], SuspendError> {
+        if self.0.len() < len {
+            return Err(SuspendError::Malformed)
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], SuspendError> {
+        self.take(N)?
+            .try_into()
+            .map_err(|_| SuspendError::Malformed)
+    }
+
+    fn word(&mut self) -> Result<Word, SuspendError> {
+        Ok(Word::from_be_bytes(self.array()?))
+    }
+
+    fn offset(&mut self) -> Result<usize, SuspendError> {
+        usize::try_from(self.word()?).map_err(|_| SuspendError::Malformed)
+    }
+
+    fn len(&mut self) -> Result<usize, SuspendError> {
+        Ok(u32::from_be_bytes(self.array()?) as usize)
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8], SuspendError> {
+        let len = self.len()?;
+        self.take(len)
+    }
+
+    fn flag(&mut self) -> Result<bool, SuspendError> {
+        match self.array::<1>()? {
+            [0] => Ok(false),
+            [1] => Ok(true),
+            _ => Err(SuspendError::Malformed),
+        }
+    }
+
+    fn predicate(&mut self) -> Result<RuntimePredicate, SuspendError> {
+        let start = self.word()?;
+        let len = self.word()?;
+        let program =
+            MemoryRange::new(start, len).map_err(|_| SuspendError::Malformed)?;
+        Ok(RuntimePredicate::new(program, self.offset()?))
+    }
+
+    fn breakpoint(&mut self) -> Result<Breakpoint, SuspendError> {
+        let contract = self.array()?.into();
+        Ok(Breakpoint::raw(contract, self.word()?))
+    }
+
+    fn watchpoint(&mut self) -> Result<Watchpoint, SuspendError> {
+        Ok(match self.array::<1>()? {
+            [0] => {
+                let register = self.offset()?;
+                let tag = self.array::<1>()?;
+                let operand = self.word()?;
+                let condition = match tag {
+                    [0] => RegisterCondition::Equal(operand),
+                    [1] => RegisterCondition::NotEqual(operand),
+                    [2] => RegisterCondition::Less(operand),
+                    [3] => RegisterCondition::LessOrEqual(operand),
+                    [4] => RegisterCondition::Greater(operand),
+                    [5] => RegisterCondition::GreaterOrEqual(operand),
+                    _ => return Err(SuspendError::Malformed),
+                };
+                Watchpoint::register(register, condition)
+            }
+            [1] => Watchpoint::Memory {
+                start: self.offset()?,
+                end: self.offset()?,
+            },
+            [2] => Watchpoint::storage(self.array()?.into(), self.array()?.into()),
+            [3] => Watchpoint::Receipt(
+                ReceiptRepr::try_from(self.word()?)
+                    .map_err(|_| SuspendError::Malformed)?,
+            ),
+            [4] => Watchpoint::GasBelow(self.word()?),
+            _ => return Err(SuspendError::Malformed),
+        })
+    }
+
+    fn debug_eval(&mut self) -> Result<DebugEval, SuspendError> {
+        Ok(match self.array::<1>()? {
+            [0] => DebugEval::Breakpoint(self.breakpoint()?),
+            [1] => DebugEval::Watchpoint {
+                location: self.breakpoint()?,
+                watchpoint: self.watchpoint()?,
+            },
+            [2] => DebugEval::Continue,
+            _ => return Err(SuspendError::Malformed),
+        })
+    }
+
+    fn program_state(&mut self) -> Result<ProgramState, SuspendError> {
+        Ok(match self.array::<1>()? {
+            [0] => ProgramState::Return(self.word()?),
+            [1] => ProgramState::ReturnData(self.array()?.into()),
+            [2] => ProgramState::Revert(self.word()?),
+            [3] => ProgramState::RunProgram(self.debug_eval()?),
+            [4] => ProgramState::VerifyPredicate(self.debug_eval()?),
+            _ => return Err(SuspendError::Malformed),
+        })
+    }
+}
@@ -0,0 +1,245 @@
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::{
+    ConsensusParameters,
+    Finalizable,
+    Script,
+    TransactionBuilder,
+};
+use fuel_types::{
+    Bytes32,
+    Immediate18,
+    Word,
+};
+
+use crate::{
+    checked_transaction::IntoChecked,
+    consts::VM_REGISTER_COUNT,
+    interpreter::SuspendError,
+    prelude::*,
+    script_with_data_offset,
+};
+
+fn assert_same_debugger(a: &Debugger, b: &Debugger) {
+    let mut breakpoints_a: Vec<_> = a.breakpoints().collect();
+    let mut breakpoints_b: Vec<_> = b.breakpoints().collect();
+    breakpoints_a.sort_by_key(|b| b.pc());
+    breakpoints_b.sort_by_key(|b| b.pc());
+    assert_eq!(breakpoints_a, breakpoints_b);
+    assert_eq!(a.watchpoints(), b.watchpoints());
+    assert_eq!(a.last_state(), b.last_state());
+    assert_eq!(a.single_stepping(), b.single_stepping());
+    assert_eq!(a.history_limit(), b.history_limit());
+    assert_eq!(a.history_bytes_limit(), b.history_bytes_limit());
+}
+
+#[test]
+fn restored_script_resumes_like_the_original() {
+    let mut vm = Interpreter::<_, Script>::with_memory_storage();
+    vm.set_breakpoint(Breakpoint::script(4));
+    vm.set_breakpoint(Breakpoint::script(5));
+    vm.set_watchpoint(Watchpoint::register(
+        0x11,
+        RegisterCondition::GreaterOrEqual(100),
+    ));
+
+    let script = vec![
+        op::movi(0x10, 16),
+        op::aloc(0x10),
+        op::sw(RegId::HP, RegId::ONE, 0),
+        op::log(0x10, RegId::ZERO, RegId::ZERO, RegId::ZERO),
+        op::addi(0x11, RegId::ZERO, 7),
+        op::mcli(RegId::HP, 16),
+        op::ret(0x11),
+    ];
+    let tx = TransactionBuilder::script(script.into_iter().collect(), vec![])
+        .gas_limit(1_000_000)
+        .add_random_fee_input()
+        .finalize()
+        .into_checked(
+            Default::default(),
+            &ConsensusParameters::default(),
+            vm.gas_costs(),
+        )
+        .expect("failed to generate checked tx");
+
+    let state = vm
+        .transact(tx)
+        .map(ProgramState::from)
+        .expect("Failed to execute script!");
+    assert_eq!(state, Breakpoint::script(4));
+
+    let (blob, snapshot) = vm.suspend_with_storage();
+    let mut restored = Interpreter::<MemoryStorage, Script>::restore_with_storage(
+        &blob,
+        &snapshot,
+        *vm.params(),
+        vm.gas_costs().clone(),
+    )
+    .expect("Failed to restore");
+    assert_eq!(restored, vm);
+    assert_same_debugger(restored.debugger(), vm.debugger());
+    assert_eq!(restored.suspend(), blob);
+
+    for _ in 0..2 {
+        let expected = vm.resume().expect("Failed to resume");
+        let state = restored.resume().expect("Failed to resume restored");
+        assert_eq!(state, expected);
+        assert_eq!(restored, vm);
+    }
+    assert_eq!(restored.receipts(), vm.receipts());
+}
+
+#[test]
+fn restored_call_resumes_like_the_original() {
+    let mut test_context = TestBuilder::new(2322u64);
+
+    let program = vec![
+        op::movi(0x10, Bytes32::LEN as Immediate18),
+        op::aloc(0x10),
+        op::movi(0x11, 42),
+        op::sww(RegId::HP, 0x12, 0x11),
+        op::srw(0x13, 0x14, RegId::HP),
+        op::ret(0x13),
+    ];
+    let contract_id = test_context.setup_contract(program, None, None).contract_id;
+
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset as Immediate18),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::RET),
+        ],
+        test_context.tx_offset()
+    );
+    let script_data: Vec<u8> = contract_id
+        .iter()
+        .copied()
+        .chain((0 as Word).to_be_bytes())
+        .chain((0 as Word).to_be_bytes())
+        .collect();
+
+    let tx = test_context
+        .start_script(script, script_data)
+        .gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+
+    let mut vm = Interpreter::with_storage(
+        test_context.get_storage().clone(),
+        *test_context.get_params(),
+        GasCosts::default(),
+    );
+    vm.set_breakpoint(Breakpoint::new(contract_id, 4));
+
+    let state = vm
+        .transact(tx)
+        .map(ProgramState::from)
+        .expect("Failed to execute script!");
+    assert_eq!(state, Breakpoint::new(contract_id, 4));
+
+    let (blob, snapshot) = vm.suspend_with_storage();
+    let mut restored = Interpreter::<MemoryStorage, Script>::restore_with_storage(
+        &blob,
+        &snapshot,
+        *vm.params(),
+        vm.gas_costs().clone(),
+    )
+    .expect("Failed to restore");
+    assert_eq!(restored, vm);
+
+    let expected = vm.resume().expect("Failed to resume");
+    let state = restored.resume().expect("Failed to resume restored");
+    assert_eq!(state, expected);
+    assert_eq!(state, ProgramState::Return(42));
+    assert_eq!(restored.receipts(), vm.receipts());
+}
+
+#[test]
+fn restored_bounded_execution_resumes_like_the_original() {
+    let mut vm = Interpreter::<_, Script>::with_memory_storage();
+
+    let script = vec![
+        op::movi(0x10, 10),
+        op::addi(0x11, 0x11, 1),
+        op::jnei(0x10, 0x11, 1),
+        op::ret(0x11),
+    ];
+    let tx = TransactionBuilder::script(script.into_iter().collect(), vec![])
+        .gas_limit(1_000_000)
+        .add_random_fee_input()
+        .finalize()
+        .into_checked(
+            Default::default(),
+            &ConsensusParameters::default(),
+            vm.gas_costs(),
+        )
+        .expect("failed to generate checked tx");
+
+    let slice = vm.transact_bounded(tx, 5).expect("Failed to transact");
+    assert!(slice.is_yielded());
+
+    let (blob, snapshot) = vm.suspend_with_storage();
+    let mut restored = Interpreter::<MemoryStorage, Script>::restore_with_storage(
+        &blob,
+        &snapshot,
+        *vm.params(),
+        vm.gas_costs().clone(),
+    )
+    .expect("Failed to restore");
+    assert_eq!(restored, vm);
+
+    loop {
+        let expected = vm.resume_bounded(5).expect("Failed to resume");
+        let slice = restored
+            .resume_bounded(5)
+            .expect("Failed to resume restored");
+        assert_eq!(slice.is_yielded(), expected.is_yielded());
+        if !slice.is_yielded() {
+            let state = *slice.completed().expect("Expected a completion").state();
+            assert_eq!(state, ProgramState::Return(10));
+            break
+        }
+    }
+    assert_eq!(restored.receipts(), vm.receipts());
+}
+
+#[test]
+fn invalid_blobs_are_rejected() {
+    let vm = Interpreter::<_, Script>::with_memory_storage();
+    let blob = vm.suspend();
+
+    let restore = |blob: &[u8]| {
+        Interpreter::<MemoryStorage, Script>::restore(
+            blob,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+        )
+    };
+    assert!(restore(&blob).is_ok());
+    assert!(matches!(
+        restore(&blob[..blob.len() - 1]),
+        Err(SuspendError::Malformed)
+    ));
+
+    // Sides of the memory that aren't made of whole pages
+    let stack_offset = 12 + VM_REGISTER_COUNT * 8;
+    let mut misaligned = blob.clone();
+    misaligned[stack_offset..stack_offset + 4].copy_from_slice(&10u32.to_be_bytes());
+    misaligned.splice(stack_offset + 4..stack_offset + 4, [0; 10]);
+    assert!(matches!(restore(&misaligned), Err(SuspendError::Malformed)));
+
+    let mut future = blob;
+    let version = Interpreter::<MemoryStorage, Script>::SUSPEND_VERSION + 1;
+    future[8..12].copy_from_slice(&version.to_be_bytes());
+    assert!(matches!(
+        restore(&future),
+        Err(SuspendError::UnsupportedVersion(v)) if v == version
+    ));
+}
@@ -0,0 +1,317 @@
+use super::{
+    current_location,
+    state_slots::StateSlots,
+    ExecutableTransaction,
+    Interpreter,
+    WriteRecord,
+};
+use crate::{
+    constraints::{
+        reg_key::*,
+        CheckedMemConstLen,
+        InstructionLocation,
+    },
+    consts::*,
+    error::InterpreterError,
+    state::ExecuteState,
+    storage::InterpreterStorage,
+    tracer::{
+        MemoryWrite,
+        RegisterChange,
+        StorageAccess,
+        StorageAccessKind,
+        StorageEntry,
+        TraceEntry,
+        Tracer,
+    },
+};
+
+use fuel_asm::{
+    Instruction,
+    RawInstruction,
+    RegId,
+};
+use fuel_tx::ContractIdExt;
+use fuel_types::{
+    AssetId,
+    Bytes32,
+    ContractId,
+    Word,
+};
+
+/// State captured before executing a traced instruction.
+pub(crate) struct TraceStep {
+    location: InstructionLocation,
+    raw_instruction: RawInstruction,
+    registers: [Word; VM_REGISTER_COUNT],
+    state_slots: Option<StateSlots>,
+    entries: Vec<(StorageAccessKind, ContractId, EntryKey)>,
+}
+
+/// Key of a contract storage entry, other than a state slot.
+enum EntryKey {
+    Balance(AssetId),
+    Code,
+    CodeRoot,
+}
+
+impl<S, Tx> Interpreter<S, Tx> {
+    /// Enable or disable the execution tracer
+    pub fn set_tracing(&mut self, enabled: bool) {
+        self.tracer.set_enabled(enabled)
+    }
+
+    /// Reference to the execution tracer
+    pub const fn tracer(&self) -> &Tracer {
+        &self.tracer
+    }
+
+    /// Take the entries recorded by the execution tracer
+    pub fn take_trace(&mut self) -> Vec<TraceEntry> {
+        self.tracer.take_entries()
+    }
+}
+
+impl<S, Tx> Interpreter<S, Tx>
+where
+    S: InterpreterStorage,
+    Tx: ExecutableTransaction,
+{
+    /// Capture the state required to trace the instruction, if tracing is enabled.
+    ///
+    /// An invalid instruction is traced too, without storage accesses.
+    pub(crate) fn trace_begin(&mut self, raw: RawInstruction) -> Option<TraceStep> {
+        if !self.tracer.is_enabled() {
+            return None
+        }
+
+        let location = current_location(
+            self.contract_id(),
+            Reg::new(&self.registers[RegId::PC]),
+            Reg::new(&self.registers[RegId::IS]),
+        );
+        let (state_slots, entries) = match Instruction::try_from(raw) {
+            Ok(instruction) => (
+                self.state_slots(&instruction),
+                self.storage_entries(&instruction),
+            ),
+            Err(_) => (None, vec![]),
+        };
+
+        Some(TraceStep {
+            location,
+            raw_instruction: raw,
+            registers: self.registers,
+            state_slots,
+            entries,
+        })
+    }
+
+    /// Record the effects of the instruction captured in `step`.
+    pub(crate) fn trace_end(
+        &mut self,
+        step: TraceStep,
+        memory_writes: &[WriteRecord],
+        result: &Result<ExecuteState, InterpreterError>,
+    ) {
+        let memory_writes = memory_writes
+            .iter()
+            .map(|write| {
+                let range = write.range();
+                MemoryWrite::new(
+                    range.start as Word,
+                    self.memory.read(range).into_owned(),
+                )
+            })
+            .collect();
+
+        let registers = step
+            .registers
+            .iter()
+            .zip(self.registers.iter())
+            .enumerate()
+            .filter(|(_, (before, after))| before != after)
+            .map(|(register, (before, after))| {
+                RegisterChange::new(register, *before, *after)
+            })
+            .collect();
+
+        let storage = if result.is_ok() {
+            let mut storage = step
+                .state_slots
+                .map(|slots| self.state_slots_access(&slots))
+                .unwrap_or_default();
+            storage.extend(step.entries.into_iter().map(|(kind, contract, key)| {
+                StorageAccess::new(kind, contract, self.storage_entry(&contract, key))
+            }));
+            storage
+        } else {
+            vec![]
+        };
+
+        let entry = TraceEntry::new(
+            step.location,
+            step.raw_instruction,
+            registers,
+            memory_writes,
+            step.registers[RegId::GGAS],
+            self.registers[RegId::GGAS],
+            storage,
+            result
+                .as_ref()
+                .err()
+                .and_then(InterpreterError::panic_reason),
+        );
+
+        self.tracer.push(entry);
+    }
+
+    /// Values of the accessed slots after the execution of the instruction.
+    fn state_slots_access(&self, slots: &StateSlots) -> Vec<StorageAccess> {
+        let values = self
+            .storage
+            .merkle_contract_state_range(&slots.contract, &slots.start_key, slots.count)
+            .unwrap_or_default();
+
+        slots
+            .keys()
+            .zip(values)
+            .map(|(key, value)| {
+                let kind = if slots.write {
+                    StorageAccessKind::Write
+                } else {
+                    StorageAccessKind::Read
+                };
+
+                StorageAccess::new(
+                    kind,
+                    slots.contract,
+                    StorageEntry::State {
+                        key,
+                        value: value.map(|v| v.into_owned()),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Balances and code the instruction is about to access.
+    ///
+    /// As for the state slots, the keys are read before the execution.
+    fn storage_entries(
+        &self,
+        instruction: &Instruction,
+    ) -> Vec<(StorageAccessKind, ContractId, EntryKey)> {
+        use StorageAccessKind::{
+            Read,
+            Write,
+        };
+
+        let r = |id: RegId| self.registers[id];
+        let read = |address: Word| {
+            CheckedMemConstLen::<{ Bytes32::LEN }>::new(address)
+                .ok()
+                .map(|range| range.read(&self.memory))
+        };
+        let contract = |address| read(address).map(ContractId::from);
+        let asset_id = |address| read(address).map(AssetId::from);
+        let internal = self.internal_contract().ok();
+        let sub_asset_id = |address| {
+            internal
+                .zip(read(address))
+                .map(|(contract, sub_id)| contract.asset_id(&sub_id.into()))
+        };
+
+        let entries = match instruction {
+            Instruction::BAL(bal) => {
+                let (_, b, c) = bal.unpack();
+                vec![(Read, contract(r(c)), asset_id(r(b)).map(EntryKey::Balance))]
+            }
+            Instruction::TR(tr) => {
+                let (a, _, c) = tr.unpack();
+                let asset_id = asset_id(r(c));
+                vec![
+                    (Write, internal, asset_id.map(EntryKey::Balance)),
+                    (Write, contract(r(a)), asset_id.map(EntryKey::Balance)),
+                ]
+            }
+            Instruction::TRO(tro) => {
+                let (_, _, _, d) = tro.unpack();
+                vec![(Write, internal, asset_id(r(d)).map(EntryKey::Balance))]
+            }
+            Instruction::SMO(_) => {
+                vec![(Write, internal, Some(EntryKey::Balance(AssetId::BASE)))]
+            }
+            Instruction::MINT(mint) => {
+                let (_, b) = mint.unpack();
+                vec![(Write, internal, sub_asset_id(r(b)).map(EntryKey::Balance))]
+            }
+            Instruction::BURN(burn) => {
+                let (_, b) = burn.unpack();
+                vec![(Write, internal, sub_asset_id(r(b)).map(EntryKey::Balance))]
+            }
+            Instruction::CALL(call) => {
+                let (a, _, c, _) = call.unpack();
+                let asset_id = asset_id(r(c));
+                vec![
+                    (Write, internal, asset_id.map(EntryKey::Balance)),
+                    (Write, contract(r(a)), asset_id.map(EntryKey::Balance)),
+                    (Read, contract(r(a)), Some(EntryKey::Code)),
+                ]
+            }
+            Instruction::CCP(ccp) => {
+                let (_, b, _, _) = ccp.unpack();
+                vec![(Read, contract(r(b)), Some(EntryKey::Code))]
+            }
+            Instruction::CSIZ(csiz) => {
+                let (_, b) = csiz.unpack();
+                vec![(Read, contract(r(b)), Some(EntryKey::Code))]
+            }
+            Instruction::LDC(ldc) => {
+                let (a, _, _) = ldc.unpack();
+                vec![(Read, contract(r(a)), Some(EntryKey::Code))]
+            }
+            Instruction::CROO(croo) => {
+                let (_, b) = croo.unpack();
+                vec![(Read, contract(r(b)), Some(EntryKey::CodeRoot))]
+            }
+            _ => vec![],
+        };
+
+        // Entries of the internal contract are skipped in an external context
+        entries
+            .into_iter()
+            .filter_map(|(kind, contract, key)| Some((kind, contract?, key?)))
+            .collect()
+    }
+
+    /// Value of the entry after the execution of the instruction.
+    fn storage_entry(&self, contract: &ContractId, key: EntryKey) -> StorageEntry {
+        match key {
+            EntryKey::Balance(asset_id) => StorageEntry::Balance {
+                asset_id,
+                value: self
+                    .storage
+                    .merkle_contract_asset_id_balance(contract, &asset_id)
+                    .ok()
+                    .flatten(),
+            },
+            EntryKey::Code => StorageEntry::Code {
+                len: self
+                    .storage
+                    .storage_contract_size(contract)
+                    .ok()
+                    .flatten()
+                    .map(|len| len as Word),
+            },
+            EntryKey::CodeRoot => StorageEntry::CodeRoot {
+                root: self
+                    .storage
+                    .storage_contract_root(contract)
+                    .ok()
+                    .flatten()
+                    .map(|info| info.1),
+            },
+        }
+    }
+}
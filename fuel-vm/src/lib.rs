@@ -6,7 +6,9 @@
 
 pub mod arith;
 pub mod backtrace;
+pub mod block_executor;
 pub mod call;
+pub mod chain_config;
 pub mod checked_transaction;
 pub mod constraints;
 pub mod consts;
@@ -16,15 +18,22 @@ pub mod error;
 pub mod gas;
 pub mod interpreter;
 pub mod memory_client;
+pub mod policy;
 pub mod predicate;
 pub mod state;
 pub mod storage;
 pub mod transactor;
 pub mod util;
 
+#[cfg(feature = "dap")]
+pub mod dap;
+
 #[cfg(feature = "profile-any")]
 pub mod profiler;
 
+#[cfg(feature = "trace")]
+pub mod tracer;
+
 #[cfg(test)]
 mod tests;
 
@@ -114,10 +123,16 @@ pub mod prelude {
 
     pub use crate::{
         backtrace::Backtrace,
+        block_executor::{
+            BlockExecution,
+            BlockExecutor,
+            BlockTransaction,
+        },
         call::{
             Call,
             CallFrame,
         },
+        chain_config::ChainConfig,
         context::Context,
         error::{
             Bug,
@@ -130,17 +145,29 @@ pub mod prelude {
         gas::{
             GasCosts,
             GasCostsValues,
+            Schedule,
         },
         interpreter::{
+            EcalContext,
+            EcalHandler,
             ExecutableTransaction,
+            FrameInfo,
+            GasLimitEstimate,
             Interpreter,
             MemoryRange,
+            ReceiptSink,
         },
         memory_client::MemoryClient,
+        policy::{
+            OpcodePolicy,
+            PolicyScope,
+        },
         predicate::RuntimePredicate,
         state::{
             Debugger,
+            ExecutionSlice,
             ProgramState,
+            StateDiff,
             StateTransition,
             StateTransitionRef,
         },
@@ -156,6 +183,8 @@ pub mod prelude {
     pub use crate::state::{
         Breakpoint,
         DebugEval,
+        RegisterCondition,
+        Watchpoint,
     };
 
     #[cfg(any(test, feature = "test-helpers"))]
@@ -173,6 +202,8 @@ pub mod prelude {
     pub use crate::profiler::Profiler;
     #[cfg(feature = "profile-any")]
     pub use crate::profiler::{
+        CallStack,
+        CallStackIter,
         CoverageProfilingData,
         GasProfilingData,
         InstructionLocation,
@@ -183,4 +214,21 @@ pub mod prelude {
         ProfilingData,
         StderrReceiver,
     };
+    #[cfg(feature = "profile-coverage")]
+    pub use crate::profiler::{
+        ContextCoverage,
+        SourceLocation,
+        SourceMap,
+    };
+
+    #[cfg(feature = "trace")]
+    pub use crate::tracer::{
+        MemoryWrite,
+        RegisterChange,
+        StorageAccess,
+        StorageAccessKind,
+        StorageEntry,
+        TraceEntry,
+        Tracer,
+    };
 }
@@ -3,7 +3,10 @@
 use crate::{
     backtrace::Backtrace,
     checked_transaction::Checked,
-    gas::GasCosts,
+    gas::{
+        GasCosts,
+        Schedule,
+    },
     state::StateTransitionRef,
     storage::MemoryStorage,
     transactor::Transactor,
@@ -46,6 +49,14 @@ impl MemoryClient {
         }
     }
 
+    /// Create a new instance of the memory client out of a provided storage,
+    /// following the gas costs and consensus parameters of `schedule`.
+    pub fn with_schedule(storage: MemoryStorage, schedule: Schedule) -> Self {
+        Self {
+            transactor: Transactor::with_schedule(storage, schedule),
+        }
+    }
+
     /// Create a new instance of the memory client out of a provided storage.
     pub fn from_txtor(transactor: Transactor<MemoryStorage, Script>) -> Self {
         Self { transactor }
@@ -0,0 +1,114 @@
+//! Restrictions of the instructions executable by an interpreter.
+//!
+//! An [`OpcodePolicy`] denies opcodes on top of the consensus rules, by kind
+//! of context. A denied instruction panics with
+//! [`PanicReason::InstructionNotAllowed`](fuel_asm::PanicReason::InstructionNotAllowed).
+
+#[cfg(test)]
+mod tests;
+
+use fuel_asm::Opcode;
+
+use crate::context::Context;
+
+/// Kind of context an [`OpcodePolicy`] rule applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PolicyScope {
+    /// Predicate estimation and verification.
+    Predicate,
+    /// Script execution, outside of any call.
+    Script,
+    /// Execution under a `CALL` scope.
+    Call,
+}
+
+impl PolicyScope {
+    /// All the scopes.
+    pub const ALL: [Self; 3] = [Self::Predicate, Self::Script, Self::Call];
+
+    /// Scope of the context, `None` if it isn't initialized.
+    pub const fn of(context: &Context) -> Option<Self> {
+        match context {
+            Context::PredicateEstimation { .. }
+            | Context::PredicateVerification { .. } => Some(Self::Predicate),
+            Context::Script { .. } => Some(Self::Script),
+            Context::Call { .. } => Some(Self::Call),
+            Context::NotInitialized => None,
+        }
+    }
+
+    const fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Set of opcodes, one bit per opcode.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+struct OpcodeSet([u64; 4]);
+
+impl OpcodeSet {
+    const fn contains(&self, opcode: Opcode) -> bool {
+        let opcode = opcode as usize;
+        self.0[opcode / 64] & (1 << (opcode % 64)) != 0
+    }
+
+    fn set(&mut self, opcode: Opcode, present: bool) {
+        let opcode = opcode as usize;
+        if present {
+            self.0[opcode / 64] |= 1 << (opcode % 64);
+        } else {
+            self.0[opcode / 64] &= !(1 << (opcode % 64));
+        }
+    }
+}
+
+/// Opcodes denied by an interpreter, by kind of context.
+///
+/// The default policy denies nothing, so only the consensus rules apply.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OpcodePolicy {
+    denied: [OpcodeSet; 3],
+}
+
+impl OpcodePolicy {
+    /// Policy that denies nothing.
+    pub const fn new() -> Self {
+        Self {
+            denied: [OpcodeSet([0; 4]); 3],
+        }
+    }
+
+    /// Deny `opcode` in the contexts of `scope`.
+    pub fn deny(&mut self, scope: PolicyScope, opcode: Opcode) -> &mut Self {
+        self.denied[scope.index()].set(opcode, true);
+        self
+    }
+
+    /// Deny `opcode` in all the contexts.
+    pub fn deny_everywhere(&mut self, opcode: Opcode) -> &mut Self {
+        for scope in PolicyScope::ALL {
+            self.deny(scope, opcode);
+        }
+        self
+    }
+
+    /// Allow again `opcode` in the contexts of `scope`.
+    pub fn allow(&mut self, scope: PolicyScope, opcode: Opcode) -> &mut Self {
+        self.denied[scope.index()].set(opcode, false);
+        self
+    }
+
+    /// Check if `opcode` is denied in the contexts of `scope`.
+    pub const fn is_denied(&self, scope: PolicyScope, opcode: Opcode) -> bool {
+        self.denied[scope.index()].contains(opcode)
+    }
+
+    /// Check if `opcode` may be executed in `context`.
+    pub const fn is_allowed(&self, context: &Context, opcode: Opcode) -> bool {
+        match PolicyScope::of(context) {
+            Some(scope) => !self.is_denied(scope, opcode),
+            None => true,
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use fuel_asm::{
+    op,
+    Opcode,
+    PanicReason,
+    RegId,
+};
+use fuel_tx::{
+    ConsensusParameters,
+    Finalizable,
+    Input,
+    Receipt,
+    Script,
+    TransactionBuilder,
+};
+use fuel_types::ChainId;
+
+use crate::{
+    checked_transaction::{
+        EstimatePredicates,
+        IntoChecked,
+    },
+    context::Context,
+    policy::{
+        OpcodePolicy,
+        PolicyScope,
+    },
+    prelude::*,
+};
+
+/// Reason of the panic of a script that gets the block height.
+fn script_panic(policy: OpcodePolicy) -> Option<PanicReason> {
+    let mut vm = Interpreter::<_, Script>::with_memory_storage();
+    vm.with_opcode_policy(policy);
+
+    let script = vec![op::bhei(0x10), op::ret(RegId::ONE)];
+    let tx = TransactionBuilder::script(script.into_iter().collect(), vec![])
+        .gas_limit(1_000_000)
+        .add_random_fee_input()
+        .finalize()
+        .into_checked(
+            Default::default(),
+            &ConsensusParameters::default(),
+            vm.gas_costs(),
+        )
+        .expect("failed to generate checked tx");
+
+    vm.transact(tx)
+        .expect("Failed to transact")
+        .receipts()
+        .iter()
+        .find_map(|receipt| match receipt {
+            Receipt::Panic { reason, .. } => Some(*reason.reason()),
+            _ => None,
+        })
+}
+
+#[test]
+fn policy_denies_by_scope() {
+    let mut policy = OpcodePolicy::new();
+    policy
+        .deny(PolicyScope::Call, Opcode::LDC)
+        .deny_everywhere(Opcode::TIME)
+        .allow(PolicyScope::Predicate, Opcode::TIME);
+
+    assert!(policy.is_denied(PolicyScope::Call, Opcode::LDC));
+    assert!(!policy.is_denied(PolicyScope::Script, Opcode::LDC));
+    assert!(policy.is_denied(PolicyScope::Script, Opcode::TIME));
+    assert!(!policy.is_denied(PolicyScope::Predicate, Opcode::TIME));
+
+    let script = Context::Script {
+        block_height: Default::default(),
+    };
+    let call = Context::Call {
+        block_height: Default::default(),
+    };
+    assert!(policy.is_allowed(&script, Opcode::LDC));
+    assert!(!policy.is_allowed(&call, Opcode::LDC));
+    assert!(policy.is_allowed(&Context::NotInitialized, Opcode::TIME));
+    assert_eq!(OpcodePolicy::default(), OpcodePolicy::new());
+}
+
+#[test]
+fn denied_instructions_panic() {
+    assert_eq!(script_panic(OpcodePolicy::default()), None);
+
+    let mut policy = OpcodePolicy::new();
+    policy.deny(PolicyScope::Call, Opcode::BHEI);
+    assert_eq!(script_panic(policy), None);
+
+    policy.deny(PolicyScope::Script, Opcode::BHEI);
+    assert_eq!(
+        script_panic(policy),
+        Some(PanicReason::InstructionNotAllowed)
+    );
+}
+
+#[test]
+fn predicates_are_checked_with_the_policy() {
+    let predicate: Vec<u8> = vec![op::movi(0x10, 1), op::ret(0x10)].into_iter().collect();
+    let owner = Input::predicate_owner(&predicate, &ChainId::default());
+    let params = ConsensusParameters::default();
+    let mut tx = TransactionBuilder::script(vec![], vec![])
+        .gas_limit(1_000_000)
+        .add_input(Input::coin_predicate(
+            Default::default(),
+            owner,
+            1_000,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            0,
+            predicate,
+            vec![],
+        ))
+        .finalize();
+    tx.estimate_predicates(&params, &GasCosts::default())
+        .expect("Failed to estimate predicates");
+    let checked = tx
+        .into_checked_basic(Default::default(), &params)
+        .expect("failed to generate checked tx");
+
+    let mut policy = OpcodePolicy::new();
+    policy.deny(PolicyScope::Script, Opcode::MOVI);
+    assert!(
+        Interpreter::<PredicateStorage>::check_predicates_with_policy(
+            &checked,
+            params,
+            GasCosts::default(),
+            policy,
+        )
+        .is_ok()
+    );
+
+    policy.deny(PolicyScope::Predicate, Opcode::MOVI);
+    assert!(
+        Interpreter::<PredicateStorage>::check_predicates_with_policy(
+            &checked,
+            params,
+            GasCosts::default(),
+            policy,
+        )
+        .is_err()
+    );
+}
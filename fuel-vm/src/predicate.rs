@@ -16,6 +16,12 @@ pub struct RuntimePredicate {
 }
 
 impl RuntimePredicate {
+    /// Runtime predicate of the program at `program`, mapped to the input `idx`.
+    #[cfg(feature = "debug")]
+    pub(crate) const fn new(program: MemoryRange, idx: usize) -> Self {
+        Self { program, idx }
+    }
+
     /// Memory slice with the program representation of the predicate
     pub const fn program(&self) -> &MemoryRange {
         &self.program
@@ -152,9 +158,9 @@ fn from_tx_works() {
             .collect();
 
         let program = runtime.program();
-        let program = &interpreter.memory()[program.usizes()];
+        let program = interpreter.paged_memory().read(program.usizes());
 
         // assert the program in the vm memory is the same of the input
-        assert_eq!(program, &padded_predicate);
+        assert_eq!(&*program, &padded_predicate);
     }
 }
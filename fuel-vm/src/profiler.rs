@@ -7,107 +7,27 @@ use std::{
 
 use dyn_clone::DynClone;
 
-use fuel_types::ContractId;
-
 use crate::prelude::*;
 
 pub use crate::constraints::InstructionLocation;
 
-#[cfg(feature = "serde")]
-impl serde::Serialize for InstructionLocation {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        if let Some(ctx) = self.context {
-            serializer.serialize_str(&format!("{}:{}", ctx, self.offset))
-        } else {
-            serializer.serialize_str(&format!("{}", self.offset))
-        }
-    }
-}
-
-#[cfg(feature = "serde")]
-struct InstructionLocationVisitor;
-
-#[cfg(feature = "serde")]
-impl<'de> serde::de::Visitor<'de> for InstructionLocationVisitor {
-    type Value = InstructionLocation;
-
-    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("A valid instruction location")
-    }
-
-    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
-    where
-        E: serde::de::Error,
-    {
-        use std::str::FromStr;
-
-        Ok(if let Some((l, r)) = value.split_once(':') {
-            let context = Some(ContractId::from_str(l).map_err(|_| {
-                serde::de::Error::custom("Invalid ContractId in InstructionLocation")
-            })?);
-            let offset = r.parse().unwrap();
-            InstructionLocation { context, offset }
-        } else {
-            let offset = value.parse().unwrap();
-            InstructionLocation {
-                context: None,
-                offset,
-            }
-        })
-    }
-}
+mod pprof;
+#[cfg(feature = "profile-coverage")]
+mod source_map;
 
-#[cfg(feature = "serde")]
-impl<'de> serde::Deserialize<'de> for InstructionLocation {
-    fn deserialize<D>(deserializer: D) -> Result<InstructionLocation, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        deserializer.deserialize_str(InstructionLocationVisitor)
-    }
-}
-
-impl InstructionLocation {
-    /// New location from context and offset
-    pub const fn new(context: Option<ContractId>, offset: u64) -> Self {
-        Self { context, offset }
-    }
-
-    /// Context, i.e. current contract
-    pub const fn context(&self) -> Option<ContractId> {
-        self.context
-    }
-
-    /// Offset from the IS register
-    pub const fn offset(&self) -> u64 {
-        self.offset
-    }
-}
-
-impl fmt::Display for InstructionLocation {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "Location({}, offset={})",
-            self.context
-                .map(|contract_id| format!(
-                    "contract_id={}",
-                    contract_id
-                        .iter()
-                        .map(|b| format!("{b:02x?}"))
-                        .collect::<String>()
-                ),)
-                .unwrap_or_else(|| "script".to_string()),
-            self.offset
-        )
-    }
-}
+#[cfg(feature = "profile-coverage")]
+pub use source_map::{
+    ContextCoverage,
+    SourceLocation,
+    SourceMap,
+};
 
 type PerLocation<T> = HashMap<InstructionLocation, T>;
 
+/// Locations of the active contract calls, from the outermost one, followed by
+/// the location of the executed instruction
+pub type CallStack = Vec<InstructionLocation>;
+
 /// Iterates through location (key, value) pairs
 pub struct PerLocationIter<'a, T>(
     std::collections::hash_map::Iter<'a, InstructionLocation, T>,
@@ -216,6 +136,12 @@ impl Profiler {
     pub fn add_gas(&mut self, location: InstructionLocation, gas_use: u64) {
         self.data_mut().gas_mut().add(location, gas_use);
     }
+
+    /// Add gas to the current call stack.
+    #[cfg(feature = "profile-gas")]
+    pub fn add_call_stack_gas(&mut self, stack: CallStack, gas_use: u64) {
+        self.data_mut().gas_mut().add_call_stack(stack, gas_use);
+    }
 }
 
 impl fmt::Debug for Profiler {
@@ -291,6 +217,12 @@ impl<'a> CoverageProfilingData {
     pub fn iter(&'a self) -> PerLocationKeys<'a, ()> {
         PerLocationKeys(self.executed.keys())
     }
+
+    /// Add the locations executed in `other`, e.g. to combine several runs
+    pub fn merge(&mut self, other: &Self) {
+        self.executed
+            .extend(other.executed.keys().map(|location| (*location, ())));
+    }
 }
 
 impl fmt::Display for CoverageProfilingData {
@@ -301,11 +233,13 @@ impl fmt::Display for CoverageProfilingData {
     }
 }
 
-/// Used gas per memory address
+/// Used gas per memory address and per call stack
 #[derive(Debug, Clone, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GasProfilingData {
     gas_use: PerLocation<u64>,
+    #[cfg_attr(feature = "serde", serde(default, with = "call_stacks_serde"))]
+    call_stacks: HashMap<CallStack, u64>,
 }
 
 impl<'a> GasProfilingData {
@@ -334,6 +268,100 @@ impl<'a> GasProfilingData {
     pub fn values(&'a self) -> PerLocationValues<'a, u64> {
         PerLocationValues(self.gas_use.values())
     }
+
+    /// Get total gas used with the given call stack
+    pub fn get_call_stack(&self, stack: &[InstructionLocation]) -> u64 {
+        self.call_stacks.get(stack).copied().unwrap_or(0)
+    }
+
+    /// Increase gas used with the given call stack
+    pub fn add_call_stack(&mut self, stack: CallStack, amount: u64) {
+        *self.call_stacks.entry(stack).or_insert(0) += amount;
+    }
+
+    /// Iterate through call stacks and gas values
+    pub fn call_stacks(&'a self) -> CallStackIter<'a> {
+        CallStackIter(self.call_stacks.iter())
+    }
+
+    /// Export the call stacks in the folded format consumed by flamegraph tools.
+    ///
+    /// Each line holds the `;` separated frames of a stack, from the outermost
+    /// call, followed by the gas used with it. Frames are named
+    /// `script:<offset>` or `<contract id>:<offset>`.
+    pub fn folded(&self) -> String {
+        let mut lines: Vec<_> = self
+            .call_stacks
+            .iter()
+            .map(|(stack, gas)| {
+                let frames: Vec<_> = stack.iter().map(frame_name).collect();
+                format!("{} {gas}", frames.join(";"))
+            })
+            .collect();
+        lines.sort();
+
+        lines.into_iter().map(|line| line + "\n").collect()
+    }
+
+    /// Export the call stacks as an uncompressed pprof protobuf profile.
+    ///
+    /// Every contract, and the script, is a function, with the instruction
+    /// offsets as its line numbers.
+    pub fn pprof(&self) -> Vec<u8> {
+        pprof::encode(self)
+    }
+}
+
+/// Name of the `location` frame in exported stacks
+fn frame_name(location: &InstructionLocation) -> String {
+    match location.context {
+        Some(contract) => format!("{contract}:{}", location.offset),
+        None => format!("script:{}", location.offset),
+    }
+}
+
+/// Iterates through call stacks and gas values
+pub struct CallStackIter<'a>(std::collections::hash_map::Iter<'a, CallStack, u64>);
+
+impl<'a> Iterator for CallStackIter<'a> {
+    type Item = (&'a [InstructionLocation], u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(stack, gas)| (stack.as_slice(), *gas))
+    }
+}
+
+/// Call stacks are serialized as a list of pairs, since they can't be used as
+/// keys of a JSON object.
+#[cfg(feature = "serde")]
+mod call_stacks_serde {
+    use super::CallStack;
+    use serde::{
+        Deserialize,
+        Deserializer,
+        Serializer,
+    };
+    use std::collections::HashMap;
+
+    pub fn serialize<S>(
+        call_stacks: &HashMap<CallStack, u64>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(call_stacks.iter())
+    }
+
+    pub fn deserialize<'de, D>(
+        deserializer: D,
+    ) -> Result<HashMap<CallStack, u64>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Vec::<(CallStack, u64)>::deserialize(deserializer)
+            .map(|call_stacks| call_stacks.into_iter().collect())
+    }
 }
 
 impl fmt::Display for GasProfilingData {
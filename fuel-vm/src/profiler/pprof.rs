@@ -0,0 +1,192 @@
+//! Minimal encoder for the pprof `Profile` protobuf message.
+//!
+//! See <https://github.com/google/pprof/blob/main/proto/profile.proto>.
+
+use std::collections::HashMap;
+
+use fuel_types::ContractId;
+
+use super::{
+    GasProfilingData,
+    InstructionLocation,
+};
+
+// Field numbers of the `Profile` message
+const PROFILE_SAMPLE_TYPE: u32 = 1;
+const PROFILE_SAMPLE: u32 = 2;
+const PROFILE_LOCATION: u32 = 4;
+const PROFILE_FUNCTION: u32 = 5;
+const PROFILE_STRING_TABLE: u32 = 6;
+
+// Field numbers of the nested messages
+const VALUE_TYPE_TYPE: u32 = 1;
+const VALUE_TYPE_UNIT: u32 = 2;
+const SAMPLE_LOCATION_ID: u32 = 1;
+const SAMPLE_VALUE: u32 = 2;
+const LOCATION_ID: u32 = 1;
+const LOCATION_LINE: u32 = 4;
+const LINE_FUNCTION_ID: u32 = 1;
+const LINE_LINE: u32 = 2;
+const FUNCTION_ID: u32 = 1;
+const FUNCTION_NAME: u32 = 2;
+const FUNCTION_SYSTEM_NAME: u32 = 3;
+
+/// Protobuf wire types
+const WIRE_VARINT: u32 = 0;
+const WIRE_LEN: u32 = 2;
+
+/// Encode the call stacks of `data` as a pprof profile with a single `gas`
+/// sample type.
+pub(super) fn encode(data: &GasProfilingData) -> Vec<u8> {
+    let mut profile = Profile::default();
+
+    let sample_type = {
+        let mut value_type = Message::default();
+        value_type.varint(VALUE_TYPE_TYPE, profile.string("gas"));
+        value_type.varint(VALUE_TYPE_UNIT, profile.string("units"));
+        value_type
+    };
+    profile.message.bytes(PROFILE_SAMPLE_TYPE, &sample_type.0);
+
+    // Sort the stacks to keep the output deterministic
+    let mut call_stacks: Vec<_> = data.call_stacks().collect();
+    call_stacks.sort();
+
+    for (stack, gas) in call_stacks {
+        // pprof expects the leaf location first
+        let location_ids: Vec<_> = stack
+            .iter()
+            .rev()
+            .map(|location| profile.location(location))
+            .collect();
+
+        let mut sample = Message::default();
+        sample.packed(SAMPLE_LOCATION_ID, &location_ids);
+        sample.packed(SAMPLE_VALUE, &[gas]);
+        profile.message.bytes(PROFILE_SAMPLE, &sample.0);
+    }
+
+    profile.finish()
+}
+
+/// Protobuf message being encoded
+#[derive(Default)]
+struct Message(Vec<u8>);
+
+impl Message {
+    fn key(&mut self, field: u32, wire_type: u32) {
+        self.raw_varint(((field << 3) | wire_type) as u64);
+    }
+
+    fn raw_varint(&mut self, mut value: u64) {
+        while value >= 0x80 {
+            self.0.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        self.0.push(value as u8);
+    }
+
+    fn varint(&mut self, field: u32, value: u64) {
+        self.key(field, WIRE_VARINT);
+        self.raw_varint(value);
+    }
+
+    fn bytes(&mut self, field: u32, bytes: &[u8]) {
+        self.key(field, WIRE_LEN);
+        self.raw_varint(bytes.len() as u64);
+        self.0.extend_from_slice(bytes);
+    }
+
+    fn packed(&mut self, field: u32, values: &[u64]) {
+        let mut packed = Message::default();
+        values.iter().for_each(|value| packed.raw_varint(*value));
+        self.bytes(field, &packed.0);
+    }
+}
+
+/// Profile under construction, deduplicating strings, functions and locations
+struct Profile {
+    message: Message,
+    strings: HashMap<String, u64>,
+    string_table: Vec<String>,
+    functions: HashMap<Option<ContractId>, u64>,
+    locations: HashMap<InstructionLocation, u64>,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        // The first entry of the string table must be the empty string
+        Self {
+            message: Message::default(),
+            strings: HashMap::from([(String::new(), 0)]),
+            string_table: vec![String::new()],
+            functions: HashMap::new(),
+            locations: HashMap::new(),
+        }
+    }
+}
+
+impl Profile {
+    /// Index of `value` in the string table
+    fn string(&mut self, value: &str) -> u64 {
+        if let Some(index) = self.strings.get(value) {
+            return *index
+        }
+
+        let index = self.string_table.len() as u64;
+        self.strings.insert(value.to_string(), index);
+        self.string_table.push(value.to_string());
+        index
+    }
+
+    /// Id of the function for the script or a contract
+    fn function(&mut self, context: Option<ContractId>) -> u64 {
+        if let Some(id) = self.functions.get(&context) {
+            return *id
+        }
+
+        let id = self.functions.len() as u64 + 1;
+        let name = match context {
+            Some(contract) => self.string(&contract.to_string()),
+            None => self.string("script"),
+        };
+
+        let mut function = Message::default();
+        function.varint(FUNCTION_ID, id);
+        function.varint(FUNCTION_NAME, name);
+        function.varint(FUNCTION_SYSTEM_NAME, name);
+        self.message.bytes(PROFILE_FUNCTION, &function.0);
+
+        self.functions.insert(context, id);
+        id
+    }
+
+    /// Id of the location of an instruction
+    fn location(&mut self, location: &InstructionLocation) -> u64 {
+        if let Some(id) = self.locations.get(location) {
+            return *id
+        }
+
+        let id = self.locations.len() as u64 + 1;
+        let function_id = self.function(location.context);
+
+        let mut line = Message::default();
+        line.varint(LINE_FUNCTION_ID, function_id);
+        line.varint(LINE_LINE, location.offset);
+
+        let mut message = Message::default();
+        message.varint(LOCATION_ID, id);
+        message.bytes(LOCATION_LINE, &line.0);
+        self.message.bytes(PROFILE_LOCATION, &message.0);
+
+        self.locations.insert(*location, id);
+        id
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        for value in core::mem::take(&mut self.string_table) {
+            self.message.bytes(PROFILE_STRING_TABLE, value.as_bytes());
+        }
+        self.message.0
+    }
+}
@@ -0,0 +1,168 @@
+//! Mapping of instruction locations to source code, used to export coverage
+//! reports
+
+use std::collections::{
+    BTreeMap,
+    HashMap,
+};
+
+use fuel_types::ContractId;
+
+use super::{
+    CoverageProfilingData,
+    InstructionLocation,
+};
+
+/// Position in a source file
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SourceLocation {
+    /// Path of the source file
+    pub file: String,
+    /// Line number, starting from 1
+    pub line: u32,
+    /// Column number, starting from 1
+    pub column: u32,
+}
+
+impl SourceLocation {
+    /// New source location
+    pub fn new(file: impl Into<String>, line: u32, column: u32) -> Self {
+        Self {
+            file: file.into(),
+            line,
+            column,
+        }
+    }
+}
+
+/// Source location of the instructions of the script and contracts.
+///
+/// With the `serde` feature, it is (de)serialized as a map keyed by the
+/// instruction location, i.e. `"<offset>"` for the script and
+/// `"<contract id>:<offset>"` for contracts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct SourceMap {
+    locations: HashMap<InstructionLocation, SourceLocation>,
+}
+
+impl SourceMap {
+    /// Map the instruction at `location` to `source`
+    pub fn insert(&mut self, location: InstructionLocation, source: SourceLocation) {
+        self.locations.insert(location, source);
+    }
+
+    /// Source location of the instruction at `location`, if mapped
+    pub fn get(&self, location: &InstructionLocation) -> Option<&SourceLocation> {
+        self.locations.get(location)
+    }
+
+    /// Number of mapped instructions
+    pub fn len(&self) -> usize {
+        self.locations.len()
+    }
+
+    /// Check if no instruction is mapped
+    pub fn is_empty(&self) -> bool {
+        self.locations.is_empty()
+    }
+}
+
+impl FromIterator<(InstructionLocation, SourceLocation)> for SourceMap {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (InstructionLocation, SourceLocation)>,
+    {
+        Self {
+            locations: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Coverage of the mapped instructions of the script or a contract
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ContextCoverage {
+    /// Contract, or `None` for the script
+    pub contract: Option<ContractId>,
+    /// Number of mapped instructions
+    pub instructions: u64,
+    /// Number of mapped instructions that were executed
+    pub executed: u64,
+    /// Number of source lines with mapped instructions
+    pub lines: u64,
+    /// Number of source lines with at least one executed instruction
+    pub lines_hit: u64,
+    /// Source locations of the instructions that were never executed, sorted
+    pub uncovered: Vec<SourceLocation>,
+}
+
+impl CoverageProfilingData {
+    /// Export the coverage of the instructions in `source_map` as an LCOV
+    /// tracefile.
+    ///
+    /// Executions aren't counted, so a line is reported as hit once if any of
+    /// its instructions was executed. Executed instructions missing from the
+    /// source map are ignored.
+    pub fn lcov(&self, source_map: &SourceMap) -> String {
+        let mut files: BTreeMap<&str, BTreeMap<u32, bool>> = BTreeMap::new();
+        for (location, source) in &source_map.locations {
+            *files
+                .entry(source.file.as_str())
+                .or_default()
+                .entry(source.line)
+                .or_default() |= self.get(location);
+        }
+
+        let mut lcov = String::new();
+        for (file, lines) in files {
+            lcov.push_str(&format!("TN:\nSF:{file}\n"));
+            for (line, hit) in &lines {
+                lcov.push_str(&format!("DA:{line},{}\n", u8::from(*hit)));
+            }
+            let hit = lines.values().filter(|hit| **hit).count();
+            lcov.push_str(&format!("LF:{}\nLH:{hit}\nend_of_record\n", lines.len()));
+        }
+        lcov
+    }
+
+    /// Summarize the coverage of the instructions in `source_map` per
+    /// context, with the script first, followed by the contracts in order.
+    pub fn summary(&self, source_map: &SourceMap) -> Vec<ContextCoverage> {
+        let mut contexts: BTreeMap<Option<ContractId>, Vec<_>> = BTreeMap::new();
+        for (location, source) in &source_map.locations {
+            contexts
+                .entry(location.context)
+                .or_default()
+                .push((source, self.get(location)));
+        }
+
+        contexts
+            .into_iter()
+            .map(|(contract, instructions)| {
+                let mut lines: BTreeMap<(&str, u32), bool> = BTreeMap::new();
+                for (source, hit) in &instructions {
+                    *lines.entry((&source.file, source.line)).or_default() |= hit;
+                }
+
+                let mut uncovered: Vec<_> = instructions
+                    .iter()
+                    .filter(|(_, hit)| !hit)
+                    .map(|(source, _)| (*source).clone())
+                    .collect();
+                uncovered.sort();
+
+                ContextCoverage {
+                    contract,
+                    instructions: instructions.len() as u64,
+                    executed: instructions.iter().filter(|(_, hit)| *hit).count() as u64,
+                    lines: lines.len() as u64,
+                    lines_hit: lines.values().filter(|hit| **hit).count() as u64,
+                    uncovered,
+                }
+            })
+            .collect()
+    }
+}
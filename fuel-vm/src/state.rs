@@ -6,6 +6,10 @@ use fuel_types::{
     Word,
 };
 
+use crate::storage::ReadWriteSet;
+
+mod diff;
+
 #[cfg(feature = "debug")]
 mod debug;
 
@@ -16,11 +20,22 @@ mod debugger;
 pub use debug::{
     Breakpoint,
     DebugEval,
+    RegisterCondition,
+    Watchpoint,
 };
 
 #[cfg(feature = "debug")]
 pub use debugger::Debugger;
 
+pub use diff::{
+    BalanceChange,
+    CoinProduced,
+    MessageProduced,
+    SlotChange,
+    StateDiff,
+    VariableOutput,
+};
+
 #[cfg(not(feature = "debug"))]
 /// Fallback functionless implementation if `debug` feature isn't enabled.
 pub type Debugger = ();
@@ -91,6 +106,31 @@ pub enum ProgramState {
     VerifyPredicate(DebugEval),
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Progress of an execution bounded by an instruction budget.
+pub enum ExecutionSlice<T> {
+    /// The budget was exhausted before the end of the program. The execution
+    /// continues with [`crate::interpreter::Interpreter::resume_bounded`].
+    Yielded,
+    /// The execution is over.
+    Completed(T),
+}
+
+impl<T> ExecutionSlice<T> {
+    /// Return `true` if the execution yielded.
+    pub const fn is_yielded(&self) -> bool {
+        matches!(self, Self::Yielded)
+    }
+
+    /// Result of the execution, if it is over.
+    pub fn completed(self) -> Option<T> {
+        match self {
+            Self::Yielded => None,
+            Self::Completed(t) => Some(t),
+        }
+    }
+}
+
 #[cfg(feature = "debug")]
 impl PartialEq<Breakpoint> for ProgramState {
     fn eq(&self, other: &Breakpoint) -> bool {
@@ -125,6 +165,8 @@ pub struct StateTransition<Tx> {
     state: ProgramState,
     tx: Tx,
     receipts: Vec<Receipt>,
+    read_write_set: Option<ReadWriteSet>,
+    state_diff: Option<StateDiff>,
 }
 
 impl<Tx> StateTransition<Tx> {
@@ -134,9 +176,23 @@ impl<Tx> StateTransition<Tx> {
             state,
             tx,
             receipts,
+            read_write_set: None,
+            state_diff: None,
         }
     }
 
+    /// Attach the storage keys accessed by the transaction.
+    pub fn with_read_write_set(mut self, read_write_set: Option<ReadWriteSet>) -> Self {
+        self.read_write_set = read_write_set;
+        self
+    }
+
+    /// Attach the changes made by the transaction.
+    pub fn with_state_diff(mut self, state_diff: Option<StateDiff>) -> Self {
+        self.state_diff = state_diff;
+        self
+    }
+
     /// Program state representation.
     pub const fn state(&self) -> &ProgramState {
         &self.state
@@ -159,6 +215,22 @@ impl<Tx> StateTransition<Tx> {
         self.receipts.as_slice()
     }
 
+    /// Storage keys accessed by the transaction.
+    ///
+    /// Only available if the storage records them, e.g. with
+    /// [`ReadWriteRecorder`](crate::storage::ReadWriteRecorder).
+    pub fn read_write_set(&self) -> Option<&ReadWriteSet> {
+        self.read_write_set.as_ref()
+    }
+
+    /// Changes made by the transaction.
+    ///
+    /// Only available if the storage records them, e.g. with
+    /// [`ChangeRecorder`](crate::storage::ChangeRecorder).
+    pub fn state_diff(&self) -> Option<&StateDiff> {
+        self.state_diff.as_ref()
+    }
+
     /// Convert this instance into its internal attributes.
     pub fn into_inner(self) -> (ProgramState, Tx, Vec<Receipt>) {
         (self.state, self.tx, self.receipts)
@@ -178,6 +250,8 @@ pub struct StateTransitionRef<'a, Tx> {
     state: ProgramState,
     tx: &'a Tx,
     receipts: &'a [Receipt],
+    read_write_set: Option<&'a ReadWriteSet>,
+    state_diff: Option<&'a StateDiff>,
 }
 
 impl<'a, Tx> StateTransitionRef<'a, Tx> {
@@ -187,9 +261,26 @@ impl<'a, Tx> StateTransitionRef<'a, Tx> {
             state,
             tx,
             receipts,
+            read_write_set: None,
+            state_diff: None,
         }
     }
 
+    /// Attach the storage keys accessed by the transaction.
+    pub const fn with_read_write_set(
+        mut self,
+        read_write_set: Option<&'a ReadWriteSet>,
+    ) -> Self {
+        self.read_write_set = read_write_set;
+        self
+    }
+
+    /// Attach the changes made by the transaction.
+    pub const fn with_state_diff(mut self, state_diff: Option<&'a StateDiff>) -> Self {
+        self.state_diff = state_diff;
+        self
+    }
+
     /// Program state representation.
     pub const fn state(&self) -> &ProgramState {
         &self.state
@@ -205,6 +296,22 @@ impl<'a, Tx> StateTransitionRef<'a, Tx> {
         self.receipts
     }
 
+    /// Storage keys accessed by the transaction.
+    ///
+    /// Only available if the storage records them, e.g. with
+    /// [`ReadWriteRecorder`](crate::storage::ReadWriteRecorder).
+    pub const fn read_write_set(&self) -> Option<&ReadWriteSet> {
+        self.read_write_set
+    }
+
+    /// Changes made by the transaction.
+    ///
+    /// Only available if the storage records them, e.g. with
+    /// [`ChangeRecorder`](crate::storage::ChangeRecorder).
+    pub const fn state_diff(&self) -> Option<&StateDiff> {
+        self.state_diff
+    }
+
     /// Flag whether the client should revert after execution.
     pub fn should_revert(&self) -> bool {
         self.receipts
@@ -219,6 +326,8 @@ impl<'a, Tx> From<&'a StateTransition<Tx>> for StateTransitionRef<'a, Tx> {
             state: *t.state(),
             tx: t.tx(),
             receipts: t.receipts(),
+            read_write_set: t.read_write_set(),
+            state_diff: t.state_diff(),
         }
     }
 }
@@ -229,6 +338,8 @@ impl<'a, Tx: Clone> From<StateTransitionRef<'a, Tx>> for StateTransition<Tx> {
             state: *t.state(),
             tx: t.tx().clone(),
             receipts: t.receipts().to_vec(),
+            read_write_set: t.read_write_set().cloned(),
+            state_diff: t.state_diff().cloned(),
         }
     }
 }
@@ -1,9 +1,14 @@
 use fuel_asm::Instruction;
+use fuel_tx::ReceiptRepr;
 use fuel_types::{
+    Bytes32,
     ContractId,
+    RegisterId,
     Word,
 };
 
+use crate::interpreter::MemoryRange;
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Breakpoint description that binds a tuple `(contract, $pc)` to a debugger
@@ -54,6 +59,95 @@ impl Breakpoint {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Condition on the value of a register.
+pub enum RegisterCondition {
+    /// The value is equal to the operand.
+    Equal(Word),
+    /// The value is not equal to the operand.
+    NotEqual(Word),
+    /// The value is less than the operand.
+    Less(Word),
+    /// The value is less than or equal to the operand.
+    LessOrEqual(Word),
+    /// The value is greater than the operand.
+    Greater(Word),
+    /// The value is greater than or equal to the operand.
+    GreaterOrEqual(Word),
+}
+
+impl RegisterCondition {
+    /// Check if the provided value satisfies the condition.
+    pub const fn matches(&self, value: Word) -> bool {
+        match *self {
+            Self::Equal(w) => value == w,
+            Self::NotEqual(w) => value != w,
+            Self::Less(w) => value < w,
+            Self::LessOrEqual(w) => value <= w,
+            Self::Greater(w) => value > w,
+            Self::GreaterOrEqual(w) => value >= w,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Watchpoint description, evaluated after every executed instruction.
+///
+/// Unlike a [`Breakpoint`], a watchpoint breaks the program after the
+/// instruction that triggered it was executed.
+pub enum Watchpoint {
+    /// Break when the value of the register starts satisfying the condition.
+    Register {
+        /// Watched register.
+        register: RegisterId,
+        /// Condition to be satisfied by the register value.
+        condition: RegisterCondition,
+    },
+    /// Break when any byte of the memory range `[start, end[` is written.
+    Memory {
+        /// Start of the watched range.
+        start: usize,
+        /// End of the watched range, exclusive.
+        end: usize,
+    },
+    /// Break when a state slot of a contract is written with `SWW` or `SWWQ`.
+    Storage {
+        /// Contract owning the slot.
+        contract: ContractId,
+        /// Key of the slot.
+        key: Bytes32,
+    },
+    /// Break when a receipt of the given kind is emitted.
+    Receipt(ReceiptRepr),
+    /// Break when the remaining global gas drops below the threshold.
+    GasBelow(Word),
+}
+
+impl Watchpoint {
+    /// Create a new register watchpoint.
+    pub const fn register(register: RegisterId, condition: RegisterCondition) -> Self {
+        Self::Register {
+            register,
+            condition,
+        }
+    }
+
+    /// Create a new watchpoint on writes to the provided memory range.
+    pub fn memory(range: &MemoryRange) -> Self {
+        Self::Memory {
+            start: range.start,
+            end: range.end,
+        }
+    }
+
+    /// Create a new watchpoint on writes to a contract state slot.
+    pub const fn storage(contract: ContractId, key: Bytes32) -> Self {
+        Self::Storage { contract, key }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// State evaluation of the interpreter that will describe if a program should
@@ -62,6 +156,14 @@ pub enum DebugEval {
     /// This evaluation should break the program in the location described in
     /// `Breakpoint`.
     Breakpoint(Breakpoint),
+    /// This evaluation should break the program because the watchpoint was
+    /// triggered by the instruction in `location`.
+    Watchpoint {
+        /// Location of the instruction that triggered the watchpoint.
+        location: Breakpoint,
+        /// Triggered watchpoint.
+        watchpoint: Watchpoint,
+    },
     /// This evaluation should not break the program.
     Continue,
 }
@@ -92,4 +194,23 @@ impl DebugEval {
             _ => None,
         }
     }
+
+    /// Return the triggered watchpoint if the current evaluation should break
+    /// because of it; return `None` otherwise.
+    pub const fn watchpoint(&self) -> Option<&Watchpoint> {
+        match self {
+            Self::Watchpoint { watchpoint, .. } => Some(watchpoint),
+            _ => None,
+        }
+    }
+
+    /// Return the location where the program should break, if any.
+    pub const fn location(&self) -> Option<&Breakpoint> {
+        match self {
+            Self::Breakpoint(location) | Self::Watchpoint { location, .. } => {
+                Some(location)
+            }
+            Self::Continue => None,
+        }
+    }
 }
@@ -1,7 +1,12 @@
-use crate::state::{
-    Breakpoint,
-    DebugEval,
-    ProgramState,
+use crate::{
+    consts::DEBUG_HISTORY_MAX_BYTES,
+    interpreter::diff::RecordedStep,
+    state::{
+        Breakpoint,
+        DebugEval,
+        ProgramState,
+        Watchpoint,
+    },
 };
 
 use fuel_types::{
@@ -12,9 +17,10 @@ use fuel_types::{
 use std::collections::{
     HashMap,
     HashSet,
+    VecDeque,
 };
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone)]
 /// Debugger implementation for the VM.
 ///
 /// Required features:
@@ -23,7 +29,31 @@ pub struct Debugger {
     /// Single-stepping mode triggers a breakpoint after each instruction
     single_stepping: bool,
     breakpoints: HashMap<ContractId, HashSet<Word>>,
+    watchpoints: Vec<Watchpoint>,
     last_state: Option<ProgramState>,
+    /// Maximum amount of recorded steps, `0` disables the recording
+    history_limit: usize,
+    /// Maximum amount of bytes held by the recorded steps
+    history_bytes_limit: usize,
+    /// Amount of bytes held by the recorded steps
+    history_bytes: usize,
+    /// Recorded steps, oldest first
+    history: VecDeque<RecordedStep>,
+}
+
+impl Default for Debugger {
+    fn default() -> Self {
+        Self {
+            single_stepping: false,
+            breakpoints: Default::default(),
+            watchpoints: Default::default(),
+            last_state: None,
+            history_limit: 0,
+            history_bytes_limit: DEBUG_HISTORY_MAX_BYTES,
+            history_bytes: 0,
+            history: Default::default(),
+        }
+    }
 }
 
 impl Debugger {
@@ -62,6 +92,30 @@ impl Debugger {
             .map(|set| set.remove(&breakpoint.pc()));
     }
 
+    /// Breakpoints currently set, in no particular order.
+    pub fn breakpoints(&self) -> impl Iterator<Item = Breakpoint> + '_ {
+        self.breakpoints.iter().flat_map(|(contract, set)| {
+            set.iter().map(|pc| Breakpoint::raw(*contract, *pc))
+        })
+    }
+
+    /// Set a new watchpoint, if not already set.
+    pub fn set_watchpoint(&mut self, watchpoint: Watchpoint) {
+        if !self.watchpoints.contains(&watchpoint) {
+            self.watchpoints.push(watchpoint);
+        }
+    }
+
+    /// Remove a watchpoint, if existent.
+    pub fn remove_watchpoint(&mut self, watchpoint: &Watchpoint) {
+        self.watchpoints.retain(|w| w != watchpoint);
+    }
+
+    /// Watchpoints currently set, in insertion order.
+    pub fn watchpoints(&self) -> &[Watchpoint] {
+        &self.watchpoints
+    }
+
     /// Evaluate the current state of the interpreter whether or not a
     /// breakpoint was reached.
     pub fn eval_state(&mut self, contract: Option<&ContractId>, pc: Word) -> DebugEval {
@@ -88,6 +142,20 @@ impl Debugger {
             .unwrap_or_default()
     }
 
+    /// Check if the state was interrupted by a breakpoint set with
+    /// [`Self::set_breakpoint`], rather than by single-stepping or a watchpoint.
+    pub fn is_breakpoint(&self, state: &ProgramState) -> bool {
+        let breakpoint = match state.debug_ref().and_then(DebugEval::breakpoint) {
+            Some(breakpoint) => breakpoint,
+            None => return false,
+        };
+
+        self.breakpoints
+            .get(breakpoint.contract())
+            .into_iter()
+            .any(|set| set.contains(&breakpoint.pc()))
+    }
+
     /// Overwrite the last known state of the VM.
     pub fn set_last_state(&mut self, state: ProgramState) {
         self.last_state.replace(state);
@@ -98,4 +166,90 @@ impl Debugger {
     pub const fn last_state(&self) -> &Option<ProgramState> {
         &self.last_state
     }
+
+    /// Set the maximum amount of steps recorded to be reverted, discarding the
+    /// oldest ones if the history is longer. `0` disables the recording.
+    pub fn set_history_limit(&mut self, limit: usize) {
+        self.history_limit = limit;
+        while self.history.len() > limit {
+            self.pop_oldest();
+        }
+    }
+
+    /// Maximum amount of steps recorded to be reverted.
+    pub const fn history_limit(&self) -> usize {
+        self.history_limit
+    }
+
+    /// Set the maximum amount of bytes held by the recorded steps, discarding
+    /// the oldest ones if they hold more.
+    ///
+    /// The size of a step is approximated by the memory and contract code it
+    /// restores. Defaults to [`DEBUG_HISTORY_MAX_BYTES`].
+    pub fn set_history_bytes_limit(&mut self, limit: usize) {
+        self.history_bytes_limit = limit;
+        while self.history_bytes > limit {
+            self.pop_oldest();
+        }
+    }
+
+    /// Maximum amount of bytes held by the recorded steps.
+    pub const fn history_bytes_limit(&self) -> usize {
+        self.history_bytes_limit
+    }
+
+    /// Amount of recorded steps that can currently be reverted.
+    pub fn history_len(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Amount of bytes held by the recorded steps.
+    pub const fn history_bytes(&self) -> usize {
+        self.history_bytes
+    }
+
+    /// Record a step, if the recording is enabled.
+    ///
+    /// A step larger than the bytes limit can't be recorded, and the older
+    /// steps can't be reverted without it, so the history is discarded.
+    pub(crate) fn push_history(&mut self, step: RecordedStep) {
+        if self.history_limit == 0 {
+            return
+        }
+
+        let size = step.size();
+        if size > self.history_bytes_limit {
+            self.clear_history();
+            return
+        }
+
+        while self.history.len() >= self.history_limit
+            || self.history_bytes + size > self.history_bytes_limit
+        {
+            self.pop_oldest();
+        }
+
+        self.history_bytes += size;
+        self.history.push_back(step);
+    }
+
+    /// Take the most recently recorded step.
+    pub(crate) fn pop_history(&mut self) -> Option<RecordedStep> {
+        let step = self.history.pop_back()?;
+        self.history_bytes -= step.size();
+        Some(step)
+    }
+
+    /// Discard the oldest recorded step.
+    fn pop_oldest(&mut self) {
+        if let Some(step) = self.history.pop_front() {
+            self.history_bytes -= step.size();
+        }
+    }
+
+    /// Discard the recorded steps.
+    pub(crate) fn clear_history(&mut self) {
+        self.history.clear();
+        self.history_bytes = 0;
+    }
 }
@@ -0,0 +1,243 @@
+use fuel_tx::{
+    Output,
+    Receipt,
+    TxId,
+    UtxoId,
+};
+use fuel_types::{
+    Address,
+    AssetId,
+    Bytes32,
+    ContractId,
+    Nonce,
+    Word,
+};
+
+use crate::storage::RecordedChanges;
+
+/// Change of a contract state slot. `None` is an unset slot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SlotChange {
+    /// Contract owning the slot.
+    pub contract_id: ContractId,
+    /// Key of the slot.
+    pub key: Bytes32,
+    /// Value before the transaction.
+    pub old: Option<Bytes32>,
+    /// Value after the transaction.
+    pub new: Option<Bytes32>,
+}
+
+/// Change of the balance of a contract for an asset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BalanceChange {
+    /// Contract owning the balance.
+    pub contract_id: ContractId,
+    /// Asset of the balance.
+    pub asset_id: AssetId,
+    /// Balance before the transaction.
+    pub old: Word,
+    /// Balance after the transaction.
+    pub new: Word,
+}
+
+/// Coin produced by an output of the transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CoinProduced {
+    /// Id of the coin, made of the transaction id and the output index.
+    pub utxo_id: UtxoId,
+    /// Owner of the coin.
+    pub owner: Address,
+    /// Amount of the coin.
+    pub amount: Word,
+    /// Asset of the coin.
+    pub asset_id: AssetId,
+}
+
+/// Message sent by the transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MessageProduced {
+    /// Sender of the message.
+    pub sender: Address,
+    /// Recipient of the message.
+    pub recipient: Address,
+    /// Nonce of the message.
+    pub nonce: Nonce,
+    /// Amount of base asset sent with the message.
+    pub amount: Word,
+    /// Data of the message.
+    pub data: Vec<u8>,
+}
+
+/// Variable output as resolved by the execution.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VariableOutput {
+    /// Index of the output in the transaction.
+    pub index: u8,
+    /// Recipient of the output, zeroed if it wasn't set.
+    pub to: Address,
+    /// Amount of the output, zero if it wasn't set.
+    pub amount: Word,
+    /// Asset of the output.
+    pub asset_id: AssetId,
+}
+
+/// Changes to the chain state made by a transaction.
+///
+/// The storage changes are only available if the storage records them, e.g.
+/// with [`ChangeRecorder`](crate::storage::ChangeRecorder). If the transaction
+/// reverted, the client discards its storage changes and messages, so they
+/// aren't reported.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StateDiff {
+    /// Contracts deployed by the transaction.
+    pub contracts_created: Vec<ContractId>,
+    /// Contract state slots whose value changed, ordered by contract and key.
+    pub state_changes: Vec<SlotChange>,
+    /// Contract balances that changed, ordered by contract and asset.
+    pub balance_changes: Vec<BalanceChange>,
+    /// Coins produced by the outputs.
+    pub coins: Vec<CoinProduced>,
+    /// Messages sent to the base layer.
+    pub messages: Vec<MessageProduced>,
+    /// Variable outputs of the transaction.
+    pub variable_outputs: Vec<VariableOutput>,
+}
+
+impl StateDiff {
+    pub(crate) fn new(
+        changes: &RecordedChanges,
+        tx_id: &TxId,
+        outputs: &[Output],
+        receipts: &[Receipt],
+        reverted: bool,
+    ) -> Self {
+        let mut diff = Self::default();
+
+        for (index, output) in outputs.iter().enumerate() {
+            let index = index as u8;
+            if let Output::Variable {
+                to,
+                amount,
+                asset_id,
+            } = *output
+            {
+                diff.variable_outputs.push(VariableOutput {
+                    index,
+                    to,
+                    amount,
+                    asset_id,
+                });
+            }
+
+            match *output {
+                Output::Coin {
+                    to,
+                    amount,
+                    asset_id,
+                }
+                | Output::Change {
+                    to,
+                    amount,
+                    asset_id,
+                }
+                | Output::Variable {
+                    to,
+                    amount,
+                    asset_id,
+                } if amount > 0 => diff.coins.push(CoinProduced {
+                    utxo_id: UtxoId::new(*tx_id, index),
+                    owner: to,
+                    amount,
+                    asset_id,
+                }),
+                _ => (),
+            }
+        }
+
+        if reverted {
+            return diff
+        }
+
+        diff.messages = receipts
+            .iter()
+            .filter_map(|receipt| match receipt {
+                Receipt::MessageOut {
+                    sender,
+                    recipient,
+                    amount,
+                    nonce,
+                    data,
+                    ..
+                } => Some(MessageProduced {
+                    sender: *sender,
+                    recipient: *recipient,
+                    nonce: *nonce,
+                    amount: *amount,
+                    data: data.clone().unwrap_or_default(),
+                }),
+                _ => None,
+            })
+            .collect();
+
+        let (before, after) = (changes.before(), changes.after());
+
+        diff.contracts_created = after
+            .contracts_raw_code()
+            .iter()
+            .filter(|(id, code)| {
+                code.is_some()
+                    && matches!(before.contracts_raw_code().get(*id), Some(None))
+            })
+            .map(|(id, _)| *id)
+            .collect();
+
+        diff.state_changes = after
+            .contracts_state()
+            .iter()
+            .filter_map(|(key, new)| {
+                let old = before.contracts_state().get(key).copied().flatten();
+                (old != *new).then(|| SlotChange {
+                    contract_id: *key.contract_id(),
+                    key: *key.state_key(),
+                    old,
+                    new: *new,
+                })
+            })
+            .collect();
+
+        diff.balance_changes = after
+            .contracts_assets()
+            .iter()
+            .filter_map(|(key, new)| {
+                let old = before.contracts_assets().get(key).copied().flatten();
+                let (old, new) = (old.unwrap_or_default(), new.unwrap_or_default());
+                (old != new).then(|| BalanceChange {
+                    contract_id: *key.contract_id(),
+                    asset_id: *key.asset_id(),
+                    old,
+                    new,
+                })
+            })
+            .collect();
+
+        diff
+    }
+
+    /// Check if the transaction changed nothing.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Encode the diff as JSON.
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
@@ -9,17 +9,47 @@ use fuel_types::{
     Salt,
     Word,
 };
+use primitive_types::U256;
 
+mod change_recorder;
+mod changes;
+mod file;
 mod interpreter;
 mod memory;
+mod overrides;
 mod predicate;
+mod recorder;
+mod snapshot;
+mod transaction;
 
+pub use change_recorder::{
+    ChangeRecorder,
+    RecordedChanges,
+};
+pub use changes::{
+    ChangeSet,
+    ChangeSetTable,
+};
+pub use file::FileStorage;
 pub use interpreter::{
     ContractsAssetsStorage,
     InterpreterStorage,
 };
 pub use memory::MemoryStorage;
+pub use overrides::StateOverrides;
 pub use predicate::PredicateStorage;
+pub use recorder::{
+    ReadWriteRecorder,
+    ReadWriteSet,
+    RecordedTable,
+    TableAccess,
+};
+pub use snapshot::{
+    ContractSnapshot,
+    SnapshotError,
+    StorageSnapshot,
+};
+pub use transaction::StorageTransaction;
 
 /// The storage table for contract's raw byte code.
 pub struct ContractsRawCode;
@@ -189,3 +219,14 @@ double_key!(
     Bytes32,
     state_key
 );
+
+/// Consecutive state keys starting at `start_key`, up to the last possible key.
+pub(crate) fn state_keys(start_key: &Bytes32) -> impl Iterator<Item = Bytes32> {
+    let start = U256::from_big_endian(start_key.as_ref());
+
+    (0u64..).map_while(move |i| {
+        let mut key = Bytes32::zeroed();
+        start.checked_add(i.into())?.to_big_endian(key.as_mut());
+        Some(key)
+    })
+}
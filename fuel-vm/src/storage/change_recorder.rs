@@ -0,0 +1,256 @@
+//! Storage wrapper recording the values changed by transactions.
+
+use std::{
+    borrow::Cow,
+    mem,
+};
+
+use fuel_storage::{
+    MerkleRoot,
+    MerkleRootStorage,
+    StorageInspect,
+    StorageMutate,
+    StorageRead,
+    StorageSize,
+};
+use fuel_types::{
+    Address,
+    BlockHeight,
+    Bytes32,
+    ContractId,
+    Word,
+};
+
+use crate::storage::{
+    changes::{
+        insert_state_range,
+        remove_state_range,
+    },
+    ChangeSet,
+    ChangeSetTable,
+    ContractsAssetsStorage,
+    InterpreterStorage,
+    ReadWriteSet,
+};
+
+/// Values of the keys written by a transaction, before and after its
+/// execution.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RecordedChanges {
+    before: ChangeSet,
+    after: ChangeSet,
+}
+
+impl RecordedChanges {
+    /// Values of the written keys before the first write.
+    pub const fn before(&self) -> &ChangeSet {
+        &self.before
+    }
+
+    /// Values of the written keys after the last write.
+    pub const fn after(&self) -> &ChangeSet {
+        &self.after
+    }
+
+    /// Check if no key was written.
+    pub fn is_empty(&self) -> bool {
+        self.after.is_empty()
+    }
+
+    fn record<Type: ChangeSetTable>(
+        &mut self,
+        key: &Type::Key,
+        previous: &Option<Type::OwnedValue>,
+        value: Option<Type::OwnedValue>,
+    ) where
+        Type::OwnedValue: Clone,
+    {
+        if Type::change(&self.before, key).is_none() {
+            Type::set_change(&mut self.before, key, previous.clone());
+        }
+        Type::set_change(&mut self.after, key, value);
+    }
+}
+
+/// Storage wrapper recording the values written in the underlying storage,
+/// along with the values they replaced.
+///
+/// The interpreter reports the changes of each transaction as the
+/// [`StateDiff`](crate::state::StateDiff) of its
+/// [`StateTransition`](crate::state::StateTransition).
+#[derive(Debug, Default, Clone)]
+pub struct ChangeRecorder<S> {
+    storage: S,
+    changes: RecordedChanges,
+}
+
+impl<S> ChangeRecorder<S> {
+    /// Record the changes to `storage`.
+    pub fn new(storage: S) -> Self {
+        Self {
+            storage,
+            changes: RecordedChanges::default(),
+        }
+    }
+
+    /// Changes recorded since the last transaction started.
+    pub const fn changes(&self) -> &RecordedChanges {
+        &self.changes
+    }
+
+    /// Underlying storage.
+    pub const fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// Underlying storage, mutable. Changes through it aren't recorded.
+    pub fn storage_mut(&mut self) -> &mut S {
+        &mut self.storage
+    }
+
+    /// Remove the wrapper.
+    pub fn into_inner(self) -> S {
+        self.storage
+    }
+}
+
+impl<Type: ChangeSetTable, S> StorageInspect<Type> for ChangeRecorder<S>
+where
+    S: StorageInspect<Type>,
+{
+    type Error = S::Error;
+
+    fn get(
+        &self,
+        key: &Type::Key,
+    ) -> Result<Option<Cow<'_, Type::OwnedValue>>, Self::Error> {
+        self.storage.get(key)
+    }
+
+    fn contains_key(&self, key: &Type::Key) -> Result<bool, Self::Error> {
+        self.storage.contains_key(key)
+    }
+}
+
+impl<Type: ChangeSetTable, S> StorageMutate<Type> for ChangeRecorder<S>
+where
+    S: StorageMutate<Type>,
+    Type::Value: ToOwned,
+    Type::OwnedValue: Clone + From<<Type::Value as ToOwned>::Owned>,
+{
+    fn insert(
+        &mut self,
+        key: &Type::Key,
+        value: &Type::Value,
+    ) -> Result<Option<Type::OwnedValue>, Self::Error> {
+        let previous = self.storage.insert(key, value)?;
+        self.changes
+            .record::<Type>(key, &previous, Some(value.to_owned().into()));
+        Ok(previous)
+    }
+
+    fn remove(
+        &mut self,
+        key: &Type::Key,
+    ) -> Result<Option<Type::OwnedValue>, Self::Error> {
+        let previous = self.storage.remove(key)?;
+        self.changes.record::<Type>(key, &previous, None);
+        Ok(previous)
+    }
+}
+
+impl<Type: ChangeSetTable, S> StorageSize<Type> for ChangeRecorder<S>
+where
+    S: StorageSize<Type>,
+{
+    fn size_of_value(&self, key: &Type::Key) -> Result<Option<usize>, Self::Error> {
+        self.storage.size_of_value(key)
+    }
+}
+
+impl<Type: ChangeSetTable, S> StorageRead<Type> for ChangeRecorder<S>
+where
+    S: StorageRead<Type>,
+{
+    fn read(
+        &self,
+        key: &Type::Key,
+        buf: &mut [u8],
+    ) -> Result<Option<usize>, Self::Error> {
+        self.storage.read(key, buf)
+    }
+
+    fn read_alloc(&self, key: &Type::Key) -> Result<Option<Vec<u8>>, Self::Error> {
+        self.storage.read_alloc(key)
+    }
+}
+
+impl<Key, Type: ChangeSetTable, S> MerkleRootStorage<Key, Type> for ChangeRecorder<S>
+where
+    S: MerkleRootStorage<Key, Type>,
+{
+    fn root(&self, key: &Key) -> Result<MerkleRoot, Self::Error> {
+        self.storage.root(key)
+    }
+}
+
+impl<S: ContractsAssetsStorage> ContractsAssetsStorage for ChangeRecorder<S> {}
+
+impl<S> InterpreterStorage for ChangeRecorder<S>
+where
+    S: InterpreterStorage,
+{
+    type DataError = S::DataError;
+
+    fn block_height(&self) -> Result<BlockHeight, Self::DataError> {
+        self.storage.block_height()
+    }
+
+    fn timestamp(&self, height: BlockHeight) -> Result<Word, Self::DataError> {
+        self.storage.timestamp(height)
+    }
+
+    fn block_hash(&self, block_height: BlockHeight) -> Result<Bytes32, Self::DataError> {
+        self.storage.block_hash(block_height)
+    }
+
+    fn coinbase(&self) -> Result<Address, Self::DataError> {
+        self.storage.coinbase()
+    }
+
+    fn merkle_contract_state_range(
+        &self,
+        id: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> Result<Vec<Option<Cow<'_, Bytes32>>>, Self::DataError> {
+        self.storage
+            .merkle_contract_state_range(id, start_key, range)
+    }
+
+    fn merkle_contract_state_insert_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        values: &[Bytes32],
+    ) -> Result<Option<()>, Self::DataError> {
+        insert_state_range(self, contract, start_key, values)
+    }
+
+    fn merkle_contract_state_remove_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> Result<Option<()>, Self::DataError> {
+        remove_state_range(self, contract, start_key, range)
+    }
+
+    fn take_read_write_set(&mut self) -> Option<ReadWriteSet> {
+        self.storage.take_read_write_set()
+    }
+
+    fn take_changes(&mut self) -> Option<RecordedChanges> {
+        Some(mem::take(&mut self.changes))
+    }
+}
@@ -0,0 +1,237 @@
+//! Buffered changes to the contract tables.
+
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+};
+
+use fuel_storage::{
+    Mappable,
+    StorageAsRef,
+    StorageInspect,
+    StorageMutate,
+};
+use fuel_tx::Contract;
+use fuel_types::{
+    Bytes32,
+    ContractId,
+    Salt,
+    Word,
+};
+
+use crate::storage::{
+    state_keys,
+    ContractsAssetKey,
+    ContractsAssets,
+    ContractsInfo,
+    ContractsRawCode,
+    ContractsState,
+    ContractsStateKey,
+    InterpreterStorage,
+};
+
+/// Values written to the contract tables on top of a storage. `None` marks a
+/// removed key.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ChangeSet {
+    contracts_raw_code: BTreeMap<ContractId, Option<Contract>>,
+    contracts_info: BTreeMap<ContractId, Option<(Salt, Bytes32)>>,
+    contracts_state: BTreeMap<ContractsStateKey, Option<Bytes32>>,
+    contracts_assets: BTreeMap<ContractsAssetKey, Option<Word>>,
+}
+
+impl ChangeSet {
+    /// Changes to the contracts code.
+    pub const fn contracts_raw_code(&self) -> &BTreeMap<ContractId, Option<Contract>> {
+        &self.contracts_raw_code
+    }
+
+    /// Changes to the contracts salt and code root.
+    pub const fn contracts_info(&self) -> &BTreeMap<ContractId, Option<(Salt, Bytes32)>> {
+        &self.contracts_info
+    }
+
+    /// Changes to the contracts storage slots.
+    pub const fn contracts_state(&self) -> &BTreeMap<ContractsStateKey, Option<Bytes32>> {
+        &self.contracts_state
+    }
+
+    /// Changes to the contracts balances.
+    pub const fn contracts_assets(&self) -> &BTreeMap<ContractsAssetKey, Option<Word>> {
+        &self.contracts_assets
+    }
+
+    /// Number of changed keys, over all tables.
+    pub fn len(&self) -> usize {
+        self.contracts_raw_code.len()
+            + self.contracts_info.len()
+            + self.contracts_state.len()
+            + self.contracts_assets.len()
+    }
+
+    /// Whether no key was changed.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Apply `other` on top of these changes.
+    pub fn extend(&mut self, other: Self) {
+        self.contracts_raw_code.extend(other.contracts_raw_code);
+        self.contracts_info.extend(other.contracts_info);
+        self.contracts_state.extend(other.contracts_state);
+        self.contracts_assets.extend(other.contracts_assets);
+    }
+
+    /// Write the changes to `storage`, key by key.
+    ///
+    /// If the storage fails, the changes already written aren't reverted.
+    pub(crate) fn apply<S>(&self, storage: &mut S) -> Result<(), S::DataError>
+    where
+        S: InterpreterStorage,
+    {
+        apply::<ContractsRawCode, _>(
+            storage,
+            self.contracts_raw_code
+                .iter()
+                .map(|(key, value)| (key, value.as_ref().map(AsRef::as_ref))),
+        )?;
+        apply::<ContractsInfo, _>(
+            storage,
+            self.contracts_info
+                .iter()
+                .map(|(key, value)| (key, value.as_ref())),
+        )?;
+        apply::<ContractsState, _>(
+            storage,
+            self.contracts_state
+                .iter()
+                .map(|(key, value)| (key, value.as_ref())),
+        )?;
+        apply::<ContractsAssets, _>(
+            storage,
+            self.contracts_assets
+                .iter()
+                .map(|(key, value)| (key, value.as_ref())),
+        )
+    }
+}
+
+/// Write the changes of a table to `storage`; `None` removes the key.
+fn apply<'a, Type, S>(
+    storage: &mut S,
+    changes: impl Iterator<Item = (&'a Type::Key, Option<&'a Type::Value>)>,
+) -> Result<(), S::Error>
+where
+    Type: Mappable,
+    Type::Key: 'a,
+    Type::Value: 'a,
+    S: StorageMutate<Type>,
+{
+    for (key, value) in changes {
+        match value {
+            Some(value) => storage.insert(key, value)?,
+            None => storage.remove(key)?,
+        };
+    }
+    Ok(())
+}
+
+/// Contract table whose changes are tracked in a [`ChangeSet`].
+pub trait ChangeSetTable: Mappable {
+    /// Change of `key`, if any.
+    fn change<'a>(
+        changes: &'a ChangeSet,
+        key: &Self::Key,
+    ) -> Option<&'a Option<Self::OwnedValue>>;
+
+    /// Record the change of `key`.
+    fn set_change(
+        changes: &mut ChangeSet,
+        key: &Self::Key,
+        value: Option<Self::OwnedValue>,
+    );
+}
+
+macro_rules! change_set_table {
+    ($table:ident, $field:ident) => {
+        impl ChangeSetTable for $table {
+            fn change<'a>(
+                changes: &'a ChangeSet,
+                key: &Self::Key,
+            ) -> Option<&'a Option<Self::OwnedValue>> {
+                changes.$field.get(key)
+            }
+
+            fn set_change(
+                changes: &mut ChangeSet,
+                key: &Self::Key,
+                value: Option<Self::OwnedValue>,
+            ) {
+                changes.$field.insert(*key, value);
+            }
+        }
+    };
+}
+
+change_set_table!(ContractsRawCode, contracts_raw_code);
+change_set_table!(ContractsInfo, contracts_info);
+change_set_table!(ContractsState, contracts_state);
+change_set_table!(ContractsAssets, contracts_assets);
+
+/// Read `range` slots of `contract` from `start_key`, through the storage
+/// traits of `storage`.
+pub(crate) fn state_range<'a, S>(
+    storage: &'a S,
+    contract: &ContractId,
+    start_key: &Bytes32,
+    range: Word,
+) -> Result<Vec<Option<Cow<'a, Bytes32>>>, S::Error>
+where
+    S: StorageInspect<ContractsState>,
+{
+    state_keys(start_key)
+        .take(range as usize)
+        .map(|key| {
+            let key: ContractsStateKey = (contract, &key).into();
+            storage.storage::<ContractsState>().get(&key)
+        })
+        .collect()
+}
+
+/// Write `values` to the slots of `contract` from `start_key`. Returns `None`
+/// if any slot was unset.
+pub(crate) fn insert_state_range<S>(
+    storage: &mut S,
+    contract: &ContractId,
+    start_key: &Bytes32,
+    values: &[Bytes32],
+) -> Result<Option<()>, S::Error>
+where
+    S: StorageMutate<ContractsState>,
+{
+    let mut all_set_key = true;
+    for (key, value) in state_keys(start_key).zip(values) {
+        let key: ContractsStateKey = (contract, &key).into();
+        all_set_key &= storage.insert(&key, value)?.is_some();
+    }
+    Ok(all_set_key.then_some(()))
+}
+
+/// Remove `range` slots of `contract` from `start_key`. Returns `None` if any
+/// slot was unset.
+pub(crate) fn remove_state_range<S>(
+    storage: &mut S,
+    contract: &ContractId,
+    start_key: &Bytes32,
+    range: Word,
+) -> Result<Option<()>, S::Error>
+where
+    S: StorageMutate<ContractsState>,
+{
+    let mut all_set_key = true;
+    for key in state_keys(start_key).take(range as usize) {
+        let key: ContractsStateKey = (contract, &key).into();
+        all_set_key &= storage.remove(&key)?.is_some();
+    }
+    Ok(all_set_key.then_some(()))
+}
@@ -0,0 +1,753 @@
+//! File-backed storage persisting the chain state to a directory.
+//!
+//! The state is kept in memory and every write is first appended to a log
+//! file. Each log record is framed with its length and a checksum, so a
+//! record torn by a crash is detected and dropped when the storage is opened
+//! again. [`FileStorage::compact`] folds the log into a snapshot file, which
+//! is written to a temporary file and atomically renamed.
+
+#[cfg(test)]
+mod tests;
+
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    fs::{
+        self,
+        File,
+        OpenOptions,
+    },
+    io::{
+        self,
+        Write,
+    },
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+
+use fuel_crypto::Hasher;
+use fuel_storage::{
+    Mappable,
+    MerkleRoot,
+    MerkleRootStorage,
+    StorageAsMut,
+    StorageAsRef,
+    StorageInspect,
+    StorageMutate,
+    StorageRead,
+    StorageSize,
+};
+use fuel_tx::Contract;
+use fuel_types::{
+    Address,
+    BlockHeight,
+    Bytes32,
+    ContractId,
+    Salt,
+    Word,
+};
+
+use crate::{
+    error::Infallible,
+    storage::{
+        changes::{
+            insert_state_range,
+            remove_state_range,
+        },
+        ContractsAssetKey,
+        ContractsAssets,
+        ContractsAssetsStorage,
+        ContractsInfo,
+        ContractsRawCode,
+        ContractsState,
+        ContractsStateKey,
+        InterpreterStorage,
+        MemoryStorage,
+        ReadWriteSet,
+        RecordedChanges,
+    },
+};
+
+/// Header of the snapshot and log files, ending with the format version.
+const MAGIC: [u8; 8] = *b"FUELVM\x00\x01";
+const SNAPSHOT: &str = "snapshot";
+const LOG: &str = "log";
+const CHECKSUM_LEN: usize = 8;
+
+/// A write to the persisted state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Record {
+    Contract(ContractId, Option<Contract>),
+    ContractInfo(ContractId, Option<(Salt, Bytes32)>),
+    ContractState(ContractsStateKey, Option<Bytes32>),
+    ContractBalance(ContractsAssetKey, Option<Word>),
+    BlockHeight(BlockHeight),
+    Coinbase(Address),
+    Block(BlockHeight, Word, Bytes32),
+}
+
+impl Record {
+    fn encode(&self) -> Vec<u8> {
+        let mut buf = vec![];
+        match self {
+            Self::Contract(id, Some(contract)) => {
+                buf.push(0);
+                buf.extend_from_slice(id.as_ref());
+                buf.extend_from_slice(contract.as_ref());
+            }
+            Self::Contract(id, None) => {
+                buf.push(1);
+                buf.extend_from_slice(id.as_ref());
+            }
+            Self::ContractInfo(id, Some((salt, root))) => {
+                buf.push(2);
+                buf.extend_from_slice(id.as_ref());
+                buf.extend_from_slice(salt.as_ref());
+                buf.extend_from_slice(root.as_ref());
+            }
+            Self::ContractInfo(id, None) => {
+                buf.push(3);
+                buf.extend_from_slice(id.as_ref());
+            }
+            Self::ContractState(key, Some(value)) => {
+                buf.push(4);
+                buf.extend_from_slice(key.as_ref());
+                buf.extend_from_slice(value.as_ref());
+            }
+            Self::ContractState(key, None) => {
+                buf.push(5);
+                buf.extend_from_slice(key.as_ref());
+            }
+            Self::ContractBalance(key, Some(balance)) => {
+                buf.push(6);
+                buf.extend_from_slice(key.as_ref());
+                buf.extend_from_slice(&balance.to_be_bytes());
+            }
+            Self::ContractBalance(key, None) => {
+                buf.push(7);
+                buf.extend_from_slice(key.as_ref());
+            }
+            Self::BlockHeight(height) => {
+                buf.push(8);
+                buf.extend_from_slice(&height.to_bytes());
+            }
+            Self::Coinbase(coinbase) => {
+                buf.push(9);
+                buf.extend_from_slice(coinbase.as_ref());
+            }
+            Self::Block(height, timestamp, hash) => {
+                buf.push(10);
+                buf.extend_from_slice(&height.to_bytes());
+                buf.extend_from_slice(&timestamp.to_be_bytes());
+                buf.extend_from_slice(hash.as_ref());
+            }
+        }
+        buf
+    }
+
+    fn decode(buf: &[u8]) -> Option<Self> {
+        let (tag, buf) = buf.split_first()?;
+        let record = match tag {
+            0 => {
+                let (id, code) = split::<32>(buf)?;
+                Self::Contract(id.into(), Some(code.into()))
+            }
+            1 => Self::Contract(exact::<32>(buf)?.into(), None),
+            2 => {
+                let (id, buf) = split::<32>(buf)?;
+                let (salt, root) = split::<32>(buf)?;
+                let root = exact::<32>(root)?;
+                Self::ContractInfo(id.into(), Some((salt.into(), root.into())))
+            }
+            3 => Self::ContractInfo(exact::<32>(buf)?.into(), None),
+            4 => {
+                let (key, value) = split::<64>(buf)?;
+                let value = exact::<32>(value)?;
+                Self::ContractState(
+                    ContractsStateKey::from_array(key),
+                    Some(value.into()),
+                )
+            }
+            5 => Self::ContractState(ContractsStateKey::from_array(exact(buf)?), None),
+            6 => {
+                let (key, balance) = split::<64>(buf)?;
+                let balance = Word::from_be_bytes(exact(balance)?);
+                Self::ContractBalance(ContractsAssetKey::from_array(key), Some(balance))
+            }
+            7 => Self::ContractBalance(ContractsAssetKey::from_array(exact(buf)?), None),
+            8 => Self::BlockHeight(u32::from_be_bytes(exact(buf)?).into()),
+            9 => Self::Coinbase(exact::<32>(buf)?.into()),
+            10 => {
+                let (height, buf) = split::<4>(buf)?;
+                let (timestamp, hash) = split::<8>(buf)?;
+                let hash = exact::<32>(hash)?;
+                Self::Block(
+                    u32::from_be_bytes(height).into(),
+                    Word::from_be_bytes(timestamp),
+                    hash.into(),
+                )
+            }
+            _ => return None,
+        };
+        Some(record)
+    }
+
+    /// Length, payload and checksum of the record.
+    fn frame(&self) -> Vec<u8> {
+        let payload = self.encode();
+        let mut frame = Vec::with_capacity(4 + payload.len() + CHECKSUM_LEN);
+        frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        frame.extend_from_slice(&payload);
+        frame.extend_from_slice(&checksum(&payload));
+        frame
+    }
+}
+
+fn split<const N: usize>(buf: &[u8]) -> Option<([u8; N], &[u8])> {
+    let (head, tail) = (buf.get(..N)?, &buf[N..]);
+    Some((head.try_into().ok()?, tail))
+}
+
+fn exact<const N: usize>(buf: &[u8]) -> Option<[u8; N]> {
+    buf.try_into().ok()
+}
+
+fn checksum(payload: &[u8]) -> [u8; CHECKSUM_LEN] {
+    let hash = Hasher::hash(payload);
+    let mut checksum = [0u8; CHECKSUM_LEN];
+    checksum.copy_from_slice(&hash[..CHECKSUM_LEN]);
+    checksum
+}
+
+/// Decode the framed records following the header of `file`.
+///
+/// Returns the records and the length of the valid prefix of the file; the
+/// decoding stops at the first torn or corrupted record.
+fn read_records(file: &[u8]) -> io::Result<(Vec<Record>, usize)> {
+    if file.get(..MAGIC.len()) != Some(&MAGIC[..]) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Unknown storage file format",
+        ))
+    }
+
+    let mut records = vec![];
+    let mut offset = MAGIC.len();
+    while let Some((len, rest)) = split::<4>(&file[offset..]) {
+        let len = u32::from_be_bytes(len) as usize;
+        let Some((payload, stored)) = rest
+            .get(..len + CHECKSUM_LEN)
+            .map(|frame| frame.split_at(len))
+        else {
+            break
+        };
+        if stored != checksum(payload) {
+            break
+        }
+        let Some(record) = Record::decode(payload) else {
+            break
+        };
+        records.push(record);
+        offset += 4 + len + CHECKSUM_LEN;
+    }
+
+    Ok((records, offset))
+}
+
+/// Write `records` to a new file at `path`, atomically replacing it.
+fn write_file(path: &Path, records: impl Iterator<Item = Record>) -> io::Result<()> {
+    let tmp = path.with_extension("tmp");
+    let mut file = File::create(&tmp)?;
+    let mut buf = MAGIC.to_vec();
+    for record in records {
+        buf.extend(record.frame());
+    }
+    file.write_all(&buf)?;
+    file.sync_all()?;
+    fs::rename(&tmp, path)?;
+
+    #[cfg(unix)]
+    File::open(path.parent().unwrap_or(Path::new(".")))?.sync_all()?;
+
+    Ok(())
+}
+
+/// Append-only file the records are written to.
+trait Log: Write {
+    /// Truncate the file to `len` bytes.
+    fn set_len(&mut self, len: u64) -> io::Result<()>;
+}
+
+impl Log for File {
+    fn set_len(&mut self, len: u64) -> io::Result<()> {
+        File::set_len(self, len)
+    }
+}
+
+/// Append `frame` to `log`, which is `log_len` bytes long.
+///
+/// A failed write is rolled back, since the records appended after a torn
+/// frame would be dropped when the storage is opened again. If the rollback
+/// fails too, `log_len` is cleared and the next appends are refused.
+fn append(log: &mut impl Log, log_len: &mut Option<u64>, frame: &[u8]) -> io::Result<()> {
+    let len = log_len.take().ok_or_else(|| {
+        io::Error::other(
+            "The storage log holds a torn record, the storage must be reopened",
+        )
+    })?;
+
+    match log.write_all(frame) {
+        Ok(()) => {
+            *log_len = Some(len + frame.len() as u64);
+            Ok(())
+        }
+        Err(e) => {
+            if log.set_len(len).is_ok() {
+                *log_len = Some(len);
+            }
+            Err(e)
+        }
+    }
+}
+
+/// File-backed implementation of the interpreter storage.
+///
+/// Every write is appended to a log in the storage directory before it is
+/// applied, so the state survives a crash of the process and
+/// [`FileStorage::open`] continues at the stored block height. The log is
+/// flushed to the disk with [`FileStorage::sync`] or when it is compacted.
+///
+/// The timestamps and hashes of the blocks that weren't inserted with
+/// [`FileStorage::insert_block`] are derived from the height, as in
+/// [`MemoryStorage`].
+#[derive(Debug)]
+pub struct FileStorage {
+    dir: PathBuf,
+    log: File,
+    /// Length of the log, `None` if a failed write couldn't be rolled back.
+    log_len: Option<u64>,
+    memory: MemoryStorage,
+    block_height: BlockHeight,
+    coinbase: Address,
+    blocks: BTreeMap<BlockHeight, (Word, Bytes32)>,
+}
+
+impl FileStorage {
+    /// Open the storage persisted in `dir`, creating it if it doesn't exist.
+    pub fn open(dir: impl AsRef<Path>) -> io::Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir)?;
+
+        let memory = MemoryStorage::default();
+        let block_height = memory.block_height().map_err(io::Error::from)?;
+        let coinbase = memory.coinbase().map_err(io::Error::from)?;
+
+        let snapshot = dir.join(SNAPSHOT);
+        let snapshot = if snapshot.exists() {
+            let file = fs::read(&snapshot)?;
+            let (records, len) = read_records(&file)?;
+            if len != file.len() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "Corrupted storage snapshot",
+                ))
+            }
+            records
+        } else {
+            vec![]
+        };
+
+        let path = dir.join(LOG);
+        if !path.exists() {
+            write_file(&path, std::iter::empty())?;
+        }
+        let file = fs::read(&path)?;
+        let (log, len) = read_records(&file)?;
+        let log_file = OpenOptions::new().append(true).open(&path)?;
+        if len != file.len() {
+            // Drop the record torn by a crash
+            log_file.set_len(len as u64)?;
+            log_file.sync_all()?;
+        }
+
+        let mut storage = Self {
+            dir,
+            log: log_file,
+            log_len: Some(len as u64),
+            memory,
+            block_height,
+            coinbase,
+            blocks: BTreeMap::new(),
+        };
+        snapshot
+            .into_iter()
+            .chain(log)
+            .for_each(|record| storage.apply(record));
+
+        Ok(storage)
+    }
+
+    /// Directory of the storage.
+    pub fn path(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Set the block height of the chain.
+    pub fn set_block_height(&mut self, block_height: BlockHeight) -> io::Result<()> {
+        self.write(Record::BlockHeight(block_height))
+    }
+
+    /// Set the coinbase address.
+    pub fn set_coinbase(&mut self, coinbase: Address) -> io::Result<()> {
+        self.write(Record::Coinbase(coinbase))
+    }
+
+    /// Store the timestamp and hash of the block at `block_height`.
+    pub fn insert_block(
+        &mut self,
+        block_height: BlockHeight,
+        timestamp: Word,
+        block_hash: Bytes32,
+    ) -> io::Result<()> {
+        self.write(Record::Block(block_height, timestamp, block_hash))
+    }
+
+    /// Flush the log to the disk.
+    pub fn sync(&self) -> io::Result<()> {
+        self.log.sync_data()
+    }
+
+    /// Write the whole state to a new snapshot and clear the log.
+    pub fn compact(&mut self) -> io::Result<()> {
+        let blocks = self
+            .blocks
+            .iter()
+            .map(|(height, (timestamp, hash))| Record::Block(*height, *timestamp, *hash));
+        let contracts = self
+            .memory
+            .all_contracts()
+            .map(|(id, code)| Record::Contract(*id, Some(code.clone())));
+        let info = self
+            .memory
+            .all_contract_info()
+            .map(|(id, info)| Record::ContractInfo(*id, Some(*info)));
+        let state = self
+            .memory
+            .all_contract_state()
+            .map(|(key, value)| Record::ContractState(*key, Some(*value)));
+        let balances = self
+            .memory
+            .all_contract_balances()
+            .map(|(key, balance)| Record::ContractBalance(*key, Some(*balance)));
+
+        let records = [
+            Record::BlockHeight(self.block_height),
+            Record::Coinbase(self.coinbase),
+        ]
+        .into_iter()
+        .chain(blocks)
+        .chain(contracts)
+        .chain(info)
+        .chain(state)
+        .chain(balances);
+        write_file(&self.dir.join(SNAPSHOT), records)?;
+
+        // Replaying the previous log over the new snapshot yields the same state,
+        // so a crash before the log is cleared is harmless
+        let path = self.dir.join(LOG);
+        write_file(&path, std::iter::empty())?;
+        self.log = OpenOptions::new().append(true).open(&path)?;
+        self.log_len = Some(MAGIC.len() as u64);
+
+        Ok(())
+    }
+
+    fn write(&mut self, record: Record) -> io::Result<()> {
+        append(&mut self.log, &mut self.log_len, &record.frame())?;
+        self.apply(record);
+        Ok(())
+    }
+
+    fn apply(&mut self, record: Record) {
+        match record {
+            Record::Contract(id, Some(contract)) => {
+                self.memory
+                    .storage_as_mut::<ContractsRawCode>()
+                    .insert(&id, contract.as_ref())
+                    .expect("Infallible");
+            }
+            Record::Contract(id, None) => {
+                self.memory
+                    .storage_as_mut::<ContractsRawCode>()
+                    .remove(&id)
+                    .expect("Infallible");
+            }
+            Record::ContractInfo(id, Some(info)) => {
+                self.memory
+                    .storage_as_mut::<ContractsInfo>()
+                    .insert(&id, &info)
+                    .expect("Infallible");
+            }
+            Record::ContractInfo(id, None) => {
+                self.memory
+                    .storage_as_mut::<ContractsInfo>()
+                    .remove(&id)
+                    .expect("Infallible");
+            }
+            Record::ContractState(key, Some(value)) => {
+                self.memory
+                    .storage_as_mut::<ContractsState>()
+                    .insert(&key, &value)
+                    .expect("Infallible");
+            }
+            Record::ContractState(key, None) => {
+                self.memory
+                    .storage_as_mut::<ContractsState>()
+                    .remove(&key)
+                    .expect("Infallible");
+            }
+            Record::ContractBalance(key, Some(balance)) => {
+                self.memory
+                    .storage_as_mut::<ContractsAssets>()
+                    .insert(&key, &balance)
+                    .expect("Infallible");
+            }
+            Record::ContractBalance(key, None) => {
+                self.memory
+                    .storage_as_mut::<ContractsAssets>()
+                    .remove(&key)
+                    .expect("Infallible");
+            }
+            Record::BlockHeight(block_height) => self.block_height = block_height,
+            Record::Coinbase(coinbase) => self.coinbase = coinbase,
+            Record::Block(block_height, timestamp, hash) => {
+                self.blocks.insert(block_height, (timestamp, hash));
+            }
+        }
+    }
+}
+
+impl<Type: Mappable> StorageInspect<Type> for FileStorage
+where
+    MemoryStorage: StorageInspect<Type, Error = Infallible>,
+{
+    type Error = io::Error;
+
+    fn get(
+        &self,
+        key: &Type::Key,
+    ) -> Result<Option<Cow<'_, Type::OwnedValue>>, Self::Error> {
+        Ok(self.memory.get(key)?)
+    }
+
+    fn contains_key(&self, key: &Type::Key) -> Result<bool, Self::Error> {
+        Ok(self.memory.contains_key(key)?)
+    }
+}
+
+impl StorageMutate<ContractsRawCode> for FileStorage {
+    fn insert(
+        &mut self,
+        key: &ContractId,
+        value: &[u8],
+    ) -> Result<Option<Contract>, io::Error> {
+        let previous = self
+            .memory
+            .storage::<ContractsRawCode>()
+            .get(key)?
+            .map(Cow::into_owned);
+        self.write(Record::Contract(*key, Some(value.into())))?;
+        Ok(previous)
+    }
+
+    fn remove(&mut self, key: &ContractId) -> Result<Option<Contract>, io::Error> {
+        let previous = self
+            .memory
+            .storage::<ContractsRawCode>()
+            .get(key)?
+            .map(Cow::into_owned);
+        self.write(Record::Contract(*key, None))?;
+        Ok(previous)
+    }
+}
+
+impl StorageMutate<ContractsInfo> for FileStorage {
+    fn insert(
+        &mut self,
+        key: &ContractId,
+        value: &(Salt, Bytes32),
+    ) -> Result<Option<(Salt, Bytes32)>, io::Error> {
+        let previous = self
+            .memory
+            .storage::<ContractsInfo>()
+            .get(key)?
+            .map(Cow::into_owned);
+        self.write(Record::ContractInfo(*key, Some(*value)))?;
+        Ok(previous)
+    }
+
+    fn remove(&mut self, key: &ContractId) -> Result<Option<(Salt, Bytes32)>, io::Error> {
+        let previous = self
+            .memory
+            .storage::<ContractsInfo>()
+            .get(key)?
+            .map(Cow::into_owned);
+        self.write(Record::ContractInfo(*key, None))?;
+        Ok(previous)
+    }
+}
+
+impl StorageMutate<ContractsState> for FileStorage {
+    fn insert(
+        &mut self,
+        key: &ContractsStateKey,
+        value: &Bytes32,
+    ) -> Result<Option<Bytes32>, io::Error> {
+        let previous = self
+            .memory
+            .storage::<ContractsState>()
+            .get(key)?
+            .map(Cow::into_owned);
+        self.write(Record::ContractState(*key, Some(*value)))?;
+        Ok(previous)
+    }
+
+    fn remove(&mut self, key: &ContractsStateKey) -> Result<Option<Bytes32>, io::Error> {
+        let previous = self
+            .memory
+            .storage::<ContractsState>()
+            .get(key)?
+            .map(Cow::into_owned);
+        self.write(Record::ContractState(*key, None))?;
+        Ok(previous)
+    }
+}
+
+impl StorageMutate<ContractsAssets> for FileStorage {
+    fn insert(
+        &mut self,
+        key: &ContractsAssetKey,
+        value: &Word,
+    ) -> Result<Option<Word>, io::Error> {
+        let previous = self
+            .memory
+            .storage::<ContractsAssets>()
+            .get(key)?
+            .map(Cow::into_owned);
+        self.write(Record::ContractBalance(*key, Some(*value)))?;
+        Ok(previous)
+    }
+
+    fn remove(&mut self, key: &ContractsAssetKey) -> Result<Option<Word>, io::Error> {
+        let previous = self
+            .memory
+            .storage::<ContractsAssets>()
+            .get(key)?
+            .map(Cow::into_owned);
+        self.write(Record::ContractBalance(*key, None))?;
+        Ok(previous)
+    }
+}
+
+impl<Type: Mappable> StorageSize<Type> for FileStorage
+where
+    MemoryStorage: StorageSize<Type, Error = Infallible>,
+{
+    fn size_of_value(&self, key: &Type::Key) -> Result<Option<usize>, Self::Error> {
+        Ok(self.memory.size_of_value(key)?)
+    }
+}
+
+impl<Type: Mappable> StorageRead<Type> for FileStorage
+where
+    MemoryStorage: StorageRead<Type, Error = Infallible>,
+{
+    fn read(
+        &self,
+        key: &Type::Key,
+        buf: &mut [u8],
+    ) -> Result<Option<usize>, Self::Error> {
+        Ok(self.memory.read(key, buf)?)
+    }
+
+    fn read_alloc(&self, key: &Type::Key) -> Result<Option<Vec<u8>>, Self::Error> {
+        Ok(self.memory.read_alloc(key)?)
+    }
+}
+
+impl<Key, Type: Mappable> MerkleRootStorage<Key, Type> for FileStorage
+where
+    Self: StorageMutate<Type, Error = io::Error>,
+    MemoryStorage: MerkleRootStorage<Key, Type, Error = Infallible>,
+{
+    fn root(&self, key: &Key) -> Result<MerkleRoot, Self::Error> {
+        Ok(self.memory.root(key)?)
+    }
+}
+
+impl ContractsAssetsStorage for FileStorage {}
+
+impl InterpreterStorage for FileStorage {
+    type DataError = io::Error;
+
+    fn block_height(&self) -> Result<BlockHeight, io::Error> {
+        Ok(self.block_height)
+    }
+
+    fn timestamp(&self, height: BlockHeight) -> Result<Word, io::Error> {
+        match self.blocks.get(&height) {
+            Some((timestamp, _)) => Ok(*timestamp),
+            None => Ok(self.memory.timestamp(height)?),
+        }
+    }
+
+    fn block_hash(&self, block_height: BlockHeight) -> Result<Bytes32, io::Error> {
+        match self.blocks.get(&block_height) {
+            Some((_, hash)) => Ok(*hash),
+            None => Ok(self.memory.block_hash(block_height)?),
+        }
+    }
+
+    fn coinbase(&self) -> Result<Address, io::Error> {
+        Ok(self.coinbase)
+    }
+
+    fn merkle_contract_state_range(
+        &self,
+        id: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> Result<Vec<Option<Cow<'_, Bytes32>>>, io::Error> {
+        Ok(self
+            .memory
+            .merkle_contract_state_range(id, start_key, range)?)
+    }
+
+    fn merkle_contract_state_insert_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        values: &[Bytes32],
+    ) -> Result<Option<()>, io::Error> {
+        insert_state_range(self, contract, start_key, values)
+    }
+
+    fn merkle_contract_state_remove_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> Result<Option<()>, io::Error> {
+        remove_state_range(self, contract, start_key, range)
+    }
+
+    fn take_read_write_set(&mut self) -> Option<ReadWriteSet> {
+        self.memory.take_read_write_set()
+    }
+
+    fn take_changes(&mut self) -> Option<RecordedChanges> {
+        self.memory.take_changes()
+    }
+}
@@ -0,0 +1,264 @@
+use std::{
+    fs::{
+        self,
+        OpenOptions,
+    },
+    io::{
+        self,
+        Write,
+    },
+    path::PathBuf,
+};
+
+use fuel_storage::StorageAsRef;
+use fuel_tx::Contract;
+use fuel_types::{
+    Address,
+    AssetId,
+    BlockHeight,
+    Bytes32,
+    ContractId,
+    Salt,
+};
+
+use super::{
+    append,
+    Log,
+    LOG,
+};
+use crate::storage::{
+    ContractsAssetsStorage,
+    ContractsState,
+    FileStorage,
+    InterpreterStorage,
+};
+
+/// Empty directory for the storage of `test`.
+fn storage_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "fuel-vm-file-storage-{}-{test}",
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+fn populate(storage: &mut FileStorage) -> ContractId {
+    let contract = ContractId::from([1; 32]);
+    let code = Contract::from(vec![0x12; 16]);
+    let root = code.root();
+
+    storage
+        .deploy_contract_with_id(&Salt::zeroed(), &[], &code, &root, &contract)
+        .unwrap();
+    storage
+        .merkle_contract_state_insert(&contract, &Bytes32::zeroed(), &[7; 32].into())
+        .unwrap();
+    storage
+        .merkle_contract_state_insert(&contract, &[1; 32].into(), &[8; 32].into())
+        .unwrap();
+    storage
+        .merkle_contract_state_remove(&contract, &[1; 32].into())
+        .unwrap();
+    storage
+        .merkle_contract_asset_id_balance_insert(&contract, &AssetId::zeroed(), 100)
+        .unwrap();
+    storage.set_block_height(BlockHeight::new(42)).unwrap();
+    storage.set_coinbase(Address::from([0xcb; 32])).unwrap();
+    storage
+        .insert_block(BlockHeight::new(41), 1234, [0xab; 32].into())
+        .unwrap();
+
+    contract
+}
+
+fn assert_populated(storage: &FileStorage, contract: &ContractId) {
+    let code = Contract::from(vec![0x12; 16]);
+
+    assert_eq!(
+        storage
+            .storage_contract(contract)
+            .unwrap()
+            .map(|code| code.into_owned()),
+        Some(code.clone())
+    );
+    assert_eq!(
+        storage
+            .storage_contract_root(contract)
+            .unwrap()
+            .map(|info| info.into_owned()),
+        Some((Salt::zeroed(), code.root()))
+    );
+    assert_eq!(
+        storage
+            .merkle_contract_state(contract, &Bytes32::zeroed())
+            .unwrap()
+            .map(|value| value.into_owned()),
+        Some([7; 32].into())
+    );
+    assert_eq!(
+        storage
+            .merkle_contract_state(contract, &[1; 32].into())
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        storage
+            .merkle_contract_asset_id_balance(contract, &AssetId::zeroed())
+            .unwrap(),
+        Some(100)
+    );
+    assert_eq!(storage.block_height().unwrap(), BlockHeight::new(42));
+    assert_eq!(storage.coinbase().unwrap(), Address::from([0xcb; 32]));
+    assert_eq!(storage.timestamp(BlockHeight::new(41)).unwrap(), 1234);
+    assert_eq!(
+        storage.block_hash(BlockHeight::new(41)).unwrap(),
+        Bytes32::from([0xab; 32])
+    );
+}
+
+#[test]
+fn reopen_restores_the_state() {
+    let dir = storage_dir("reopen");
+
+    let mut storage = FileStorage::open(&dir).unwrap();
+    let contract = populate(&mut storage);
+    let root = storage.storage::<ContractsState>().root(&contract).unwrap();
+    storage.sync().unwrap();
+    drop(storage);
+
+    let storage = FileStorage::open(&dir).unwrap();
+    assert_populated(&storage, &contract);
+    assert_eq!(
+        storage.storage::<ContractsState>().root(&contract).unwrap(),
+        root
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn compact_folds_the_log_into_the_snapshot() {
+    let dir = storage_dir("compact");
+
+    let mut storage = FileStorage::open(&dir).unwrap();
+    let contract = populate(&mut storage);
+    let log_len = fs::metadata(dir.join(LOG)).unwrap().len();
+    storage.compact().unwrap();
+    assert!(fs::metadata(dir.join(LOG)).unwrap().len() < log_len);
+    assert_populated(&storage, &contract);
+    drop(storage);
+
+    let mut storage = FileStorage::open(&dir).unwrap();
+    assert_populated(&storage, &contract);
+
+    storage
+        .merkle_contract_asset_id_balance_insert(&contract, &AssetId::zeroed(), 5)
+        .unwrap();
+    drop(storage);
+
+    let storage = FileStorage::open(&dir).unwrap();
+    assert_eq!(
+        storage
+            .merkle_contract_asset_id_balance(&contract, &AssetId::zeroed())
+            .unwrap(),
+        Some(5)
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn torn_log_record_is_dropped() {
+    let dir = storage_dir("torn");
+
+    let mut storage = FileStorage::open(&dir).unwrap();
+    let contract = populate(&mut storage);
+    drop(storage);
+
+    // A record whose write was interrupted
+    let mut log = OpenOptions::new().append(true).open(dir.join(LOG)).unwrap();
+    log.write_all(&[0, 0, 0, 65, 4, 1, 2, 3]).unwrap();
+    drop(log);
+
+    let mut storage = FileStorage::open(&dir).unwrap();
+    assert_populated(&storage, &contract);
+
+    storage.set_block_height(BlockHeight::new(43)).unwrap();
+    drop(storage);
+
+    let storage = FileStorage::open(&dir).unwrap();
+    assert_eq!(storage.block_height().unwrap(), BlockHeight::new(43));
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+/// Log accepting `capacity` bytes, as a full disk would.
+struct FullLog {
+    bytes: Vec<u8>,
+    capacity: usize,
+    truncate: bool,
+}
+
+impl Write for FullLog {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = buf.len().min(self.capacity - self.bytes.len());
+        if len == 0 {
+            return Err(io::Error::other("No space left"))
+        }
+        self.bytes.extend_from_slice(&buf[..len]);
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Log for FullLog {
+    fn set_len(&mut self, len: u64) -> io::Result<()> {
+        if !self.truncate {
+            return Err(io::Error::other("I/O error"))
+        }
+        self.bytes.truncate(len as usize);
+        Ok(())
+    }
+}
+
+#[test]
+fn failed_append_is_rolled_back() {
+    let mut log = FullLog {
+        bytes: vec![1, 2, 3],
+        capacity: 8,
+        truncate: true,
+    };
+    let mut len = Some(3);
+
+    append(&mut log, &mut len, &[4, 5, 6, 7, 8, 9, 10])
+        .expect_err("Expected a short write");
+    assert_eq!(log.bytes, vec![1, 2, 3]);
+    assert_eq!(len, Some(3));
+
+    append(&mut log, &mut len, &[4, 5]).expect("Failed to append");
+    assert_eq!(log.bytes, vec![1, 2, 3, 4, 5]);
+    assert_eq!(len, Some(5));
+}
+
+#[test]
+fn append_is_refused_after_a_failed_rollback() {
+    let mut log = FullLog {
+        bytes: vec![1, 2, 3],
+        capacity: 8,
+        truncate: false,
+    };
+    let mut len = Some(3);
+
+    append(&mut log, &mut len, &[4, 5, 6, 7, 8, 9, 10])
+        .expect_err("Expected a short write");
+    assert_eq!(len, None);
+
+    // The torn frame is still in the log, so nothing may be appended after it
+    log.capacity = 64;
+    append(&mut log, &mut len, &[4, 5]).expect_err("Expected a refused append");
+    assert_eq!(log.bytes, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+}
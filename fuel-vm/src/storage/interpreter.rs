@@ -27,6 +27,8 @@ use crate::storage::{
     ContractsInfo,
     ContractsRawCode,
     ContractsState,
+    ReadWriteSet,
+    RecordedChanges,
 };
 use std::{
     borrow::Cow,
@@ -205,6 +207,25 @@ pub trait InterpreterStorage:
         start_key: &Bytes32,
         range: Word,
     ) -> Result<Option<()>, Self::DataError>;
+
+    /// Take the keys accessed since the last call, if the storage records them.
+    ///
+    /// The interpreter calls it when a transaction starts and ends, to report
+    /// the keys accessed by the transaction. See
+    /// [`ReadWriteRecorder`](crate::storage::ReadWriteRecorder).
+    fn take_read_write_set(&mut self) -> Option<ReadWriteSet> {
+        None
+    }
+
+    /// Take the values changed since the last call, if the storage records
+    /// them.
+    ///
+    /// The interpreter calls it when a transaction starts and ends, to report
+    /// the state diff of the transaction. See
+    /// [`ChangeRecorder`](crate::storage::ChangeRecorder).
+    fn take_changes(&mut self) -> Option<RecordedChanges> {
+        None
+    }
 }
 
 /// Storage operations for contract assets.
@@ -318,4 +339,12 @@ where
             range,
         )
     }
+
+    fn take_read_write_set(&mut self) -> Option<ReadWriteSet> {
+        <S as InterpreterStorage>::take_read_write_set(self.deref_mut())
+    }
+
+    fn take_changes(&mut self) -> Option<RecordedChanges> {
+        <S as InterpreterStorage>::take_changes(self.deref_mut())
+    }
 }
@@ -1,7 +1,10 @@
 use crate::{
-    crypto,
     error::Infallible,
     storage::{
+        changes::{
+            insert_state_range,
+            remove_state_range,
+        },
         ContractsAssetKey,
         ContractsAssets,
         ContractsInfo,
@@ -13,6 +16,11 @@ use crate::{
 };
 
 use fuel_crypto::Hasher;
+use fuel_merkle::sparse::{
+    self,
+    in_memory::MerkleTree,
+    MerkleTreeKey,
+};
 use fuel_storage::{
     Mappable,
     MerkleRoot,
@@ -33,7 +41,6 @@ use fuel_types::{
     Salt,
     Word,
 };
-use itertools::Itertools;
 use tai64::Tai64;
 
 use std::{
@@ -44,12 +51,49 @@ use std::{
 
 use super::interpreter::ContractsAssetsStorage;
 
-#[derive(Debug, Default, Clone, PartialEq, Eq)]
+/// Seconds between two blocks.
+const BLOCK_INTERVAL: Word = 10;
+
+#[derive(Debug, Default, Clone)]
 struct MemoryStorageInner {
     contracts: BTreeMap<ContractId, Contract>,
     balances: BTreeMap<ContractsAssetKey, Word>,
     contract_state: BTreeMap<ContractsStateKey, Bytes32>,
     contract_code_root: BTreeMap<ContractId, (Salt, Bytes32)>,
+    /// Sparse merkle trees of `balances`, per contract, keyed by asset id.
+    balances_trees: BTreeMap<ContractId, MerkleTree>,
+    /// Sparse merkle trees of `contract_state`, per contract, keyed by state key.
+    contract_state_trees: BTreeMap<ContractId, MerkleTree>,
+}
+
+/// Write `data` at `leaf` in the tree of `contract`, or remove the leaf if
+/// `data` is `None`.
+fn update_tree(
+    trees: &mut BTreeMap<ContractId, MerkleTree>,
+    contract: &ContractId,
+    leaf: &[u8],
+    data: Option<&[u8]>,
+) {
+    let key = MerkleTreeKey::new(leaf);
+    match data {
+        Some(data) => trees.entry(*contract).or_default().update(key, data),
+        None => {
+            if let Some(tree) = trees.get_mut(contract) {
+                tree.delete(key);
+            }
+        }
+    }
+}
+
+/// Root of the tree of `contract`, or the empty root if it has no leaves.
+fn tree_root(
+    trees: &BTreeMap<ContractId, MerkleTree>,
+    contract: &ContractId,
+) -> MerkleRoot {
+    trees
+        .get(contract)
+        .map(MerkleTree::root)
+        .unwrap_or(*sparse::empty_sum())
 }
 
 #[derive(Debug, Clone)]
@@ -63,6 +107,9 @@ struct MemoryStorageInner {
 pub struct MemoryStorage {
     block_height: BlockHeight,
     coinbase: Address,
+    /// A block height and its timestamp; the other blocks are
+    /// `BLOCK_INTERVAL` seconds apart.
+    timestamp: (BlockHeight, Word),
     memory: MemoryStorageInner,
     transacted: MemoryStorageInner,
     persisted: MemoryStorageInner,
@@ -74,6 +121,7 @@ impl MemoryStorage {
         Self {
             block_height,
             coinbase,
+            timestamp: (0.into(), Tai64::UNIX_EPOCH.0),
             memory: Default::default(),
             transacted: Default::default(),
             persisted: Default::default(),
@@ -87,6 +135,25 @@ impl MemoryStorage {
         self.memory.contract_state.iter()
     }
 
+    /// Iterate over all contract code in storage
+    pub fn all_contracts(&self) -> impl Iterator<Item = (&ContractId, &Contract)> {
+        self.memory.contracts.iter()
+    }
+
+    /// Iterate over all contract salts and code roots in storage
+    pub fn all_contract_info(
+        &self,
+    ) -> impl Iterator<Item = (&ContractId, &(Salt, Bytes32))> {
+        self.memory.contract_code_root.iter()
+    }
+
+    /// Iterate over all contract balances in storage
+    pub fn all_contract_balances(
+        &self,
+    ) -> impl Iterator<Item = (&ContractsAssetKey, &Word)> {
+        self.memory.balances.iter()
+    }
+
     /// Fetch a mapping from the contract state.
     pub fn contract_state(
         &self,
@@ -123,6 +190,13 @@ impl MemoryStorage {
         self.persisted = self.transacted.clone();
     }
 
+    /// Set the timestamp of the current block.
+    ///
+    /// The timestamps of the other blocks follow, 10 seconds apart.
+    pub fn set_block_timestamp(&mut self, timestamp: Word) {
+        self.timestamp = (self.block_height, timestamp);
+    }
+
     #[cfg(feature = "test-helpers")]
     /// Set the block height of the chain
     pub fn set_block_height(&mut self, block_height: BlockHeight) {
@@ -273,6 +347,12 @@ impl StorageMutate<ContractsAssets> for MemoryStorage {
         key: &<ContractsAssets as Mappable>::Key,
         value: &Word,
     ) -> Result<Option<Word>, Infallible> {
+        update_tree(
+            &mut self.memory.balances_trees,
+            key.contract_id(),
+            key.asset_id().as_ref(),
+            Some(&value.to_be_bytes()),
+        );
         Ok(self.memory.balances.insert(*key, *value))
     }
 
@@ -280,24 +360,19 @@ impl StorageMutate<ContractsAssets> for MemoryStorage {
         &mut self,
         key: &<ContractsAssets as Mappable>::Key,
     ) -> Result<Option<Word>, Infallible> {
+        update_tree(
+            &mut self.memory.balances_trees,
+            key.contract_id(),
+            key.asset_id().as_ref(),
+            None,
+        );
         Ok(self.memory.balances.remove(key))
     }
 }
 
 impl MerkleRootStorage<ContractId, ContractsAssets> for MemoryStorage {
     fn root(&self, parent: &ContractId) -> Result<MerkleRoot, Infallible> {
-        let root = self
-            .memory
-            .balances
-            .iter()
-            .filter_map(|(key, balance)| {
-                (key.contract_id() == parent).then_some((key.asset_id(), balance))
-            })
-            .sorted_by_key(|t| t.0)
-            .map(|(_, &balance)| balance)
-            .map(Word::to_be_bytes);
-
-        Ok(crypto::ephemeral_merkle_root(root).into())
+        Ok(tree_root(&self.memory.balances_trees, parent))
     }
 }
 
@@ -325,6 +400,12 @@ impl StorageMutate<ContractsState> for MemoryStorage {
         key: &<ContractsState as Mappable>::Key,
         value: &Bytes32,
     ) -> Result<Option<Bytes32>, Infallible> {
+        update_tree(
+            &mut self.memory.contract_state_trees,
+            key.contract_id(),
+            key.state_key().as_ref(),
+            Some(value.as_ref()),
+        );
         Ok(self.memory.contract_state.insert(*key, *value))
     }
 
@@ -332,23 +413,19 @@ impl StorageMutate<ContractsState> for MemoryStorage {
         &mut self,
         key: &<ContractsState as Mappable>::Key,
     ) -> Result<Option<Bytes32>, Infallible> {
+        update_tree(
+            &mut self.memory.contract_state_trees,
+            key.contract_id(),
+            key.state_key().as_ref(),
+            None,
+        );
         Ok(self.memory.contract_state.remove(key))
     }
 }
 
 impl MerkleRootStorage<ContractId, ContractsState> for MemoryStorage {
     fn root(&self, parent: &ContractId) -> Result<MerkleRoot, Infallible> {
-        let root = self
-            .memory
-            .contract_state
-            .iter()
-            .filter_map(|(key, value)| {
-                (key.contract_id() == parent).then_some((key.state_key(), value))
-            })
-            .sorted_by_key(|t| t.0)
-            .map(|(_, value)| value);
-
-        Ok(crypto::ephemeral_merkle_root(root).into())
+        Ok(tree_root(&self.memory.contract_state_trees, parent))
     }
 }
 
@@ -362,10 +439,16 @@ impl InterpreterStorage for MemoryStorage {
     }
 
     fn timestamp(&self, height: BlockHeight) -> Result<Word, Self::DataError> {
-        const GENESIS: Tai64 = Tai64::UNIX_EPOCH;
-        const INTERVAL: Word = 10;
+        let (anchor, timestamp) = self.timestamp;
+        let timestamp = if height >= anchor {
+            let blocks = (*height - *anchor) as Word;
+            timestamp.saturating_add(blocks * BLOCK_INTERVAL)
+        } else {
+            let blocks = (*anchor - *height) as Word;
+            timestamp.saturating_sub(blocks * BLOCK_INTERVAL)
+        };
 
-        Ok((GENESIS + (*height as Word * INTERVAL)).0)
+        Ok(timestamp)
     }
 
     fn block_hash(&self, block_height: BlockHeight) -> Result<Bytes32, Infallible> {
@@ -419,24 +502,7 @@ impl InterpreterStorage for MemoryStorage {
         start_key: &Bytes32,
         values: &[Bytes32],
     ) -> Result<Option<()>, Self::DataError> {
-        let mut any_unset_key = false;
-        let values: Vec<_> = std::iter::successors(Some(**start_key), |n| {
-            let mut n = *n;
-            if add_one(&mut n) {
-                None
-            } else {
-                Some(n)
-            }
-        })
-        .zip(values)
-        .map(|(key, value)| {
-            let key = (contract, &Bytes32::from(key)).into();
-            any_unset_key |= !self.memory.contract_state.contains_key(&key);
-            (key, *value)
-        })
-        .collect();
-        self.memory.contract_state.extend(values);
-        Ok((!any_unset_key).then_some(()))
+        insert_state_range(self, contract, start_key, values)
     }
 
     fn merkle_contract_state_remove_range(
@@ -445,26 +511,7 @@ impl InterpreterStorage for MemoryStorage {
         start_key: &Bytes32,
         range: Word,
     ) -> Result<Option<()>, Self::DataError> {
-        let mut all_set_key = true;
-        let mut values: std::collections::HashSet<_> =
-            std::iter::successors(Some(**start_key), |n| {
-                let mut n = *n;
-                if add_one(&mut n) {
-                    None
-                } else {
-                    Some(n)
-                }
-            })
-            .take(range as usize)
-            .collect();
-        self.memory.contract_state.retain(|key, _| {
-            let c = key.contract_id();
-            let k = key.state_key();
-            let r = values.remove(&**k);
-            all_set_key &= c == contract && r;
-            c != contract || !r
-        });
-        Ok((all_set_key && values.is_empty()).then_some(()))
+        remove_state_range(self, contract, start_key, range)
     }
 }
 
@@ -484,6 +531,7 @@ fn add_one(a: &mut [u8; 32]) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use fuel_types::AssetId;
     use test_case::test_case;
 
     const fn key(k: u8) -> [u8; 32] {
@@ -518,4 +566,104 @@ mod tests {
             .map(|v| v.map(|v| v.into_owned()))
             .collect()
     }
+
+    #[test]
+    fn state_root_matches_initial_state_root() {
+        use fuel_tx::StorageSlot;
+
+        let contract = ContractId::from([1; 32]);
+        let slots = vec![
+            StorageSlot::new(key(1).into(), [10; 32].into()),
+            StorageSlot::new(key(2).into(), [20; 32].into()),
+        ];
+        let mut mem = MemoryStorage::default();
+        let empty = mem.storage::<ContractsState>().root(&contract).unwrap();
+        assert_eq!(Bytes32::from(empty), Contract::default_state_root());
+
+        for slot in &slots {
+            mem.merkle_contract_state_insert(&contract, slot.key(), slot.value())
+                .unwrap();
+        }
+        mem.merkle_contract_state_insert(&contract, &key(3).into(), &[30; 32].into())
+            .unwrap();
+        mem.commit();
+        mem.merkle_contract_state_remove(&contract, &key(3).into())
+            .unwrap();
+
+        let root = mem.storage::<ContractsState>().root(&contract).unwrap();
+        assert_eq!(
+            Bytes32::from(root),
+            Contract::initial_state_root(slots.iter())
+        );
+
+        mem.revert();
+        let root = mem.storage::<ContractsState>().root(&contract).unwrap();
+        assert_ne!(
+            Bytes32::from(root),
+            Contract::initial_state_root(slots.iter())
+        );
+    }
+
+    #[test]
+    fn state_root_tracks_range_updates() {
+        use fuel_tx::StorageSlot;
+
+        let contract = ContractId::from([1; 32]);
+        let slots = [
+            StorageSlot::new(key(0).into(), [7; 32].into()),
+            StorageSlot::new(key(1).into(), [8; 32].into()),
+        ];
+        let mut mem = MemoryStorage::default();
+        mem.merkle_contract_state_insert_range(
+            &contract,
+            &key(0).into(),
+            &[[7; 32].into(), [8; 32].into()],
+        )
+        .unwrap();
+
+        let root = mem.storage::<ContractsState>().root(&contract).unwrap();
+        assert_eq!(
+            Bytes32::from(root),
+            Contract::initial_state_root(slots.iter())
+        );
+
+        mem.merkle_contract_state_remove_range(&contract, &key(1).into(), 1)
+            .unwrap();
+        let root = mem.storage::<ContractsState>().root(&contract).unwrap();
+        assert_eq!(
+            Bytes32::from(root),
+            Contract::initial_state_root(slots[..1].iter())
+        );
+
+        mem.merkle_contract_state_remove_range(&contract, &key(0).into(), 2)
+            .unwrap();
+        let root = mem.storage::<ContractsState>().root(&contract).unwrap();
+        assert_eq!(Bytes32::from(root), Contract::default_state_root());
+    }
+
+    #[test]
+    fn balance_root_is_sparse_merkle_root() {
+        let contract = ContractId::from([1; 32]);
+        let mut mem = MemoryStorage::default();
+        mem.merkle_contract_asset_id_balance_insert(&contract, &AssetId::zeroed(), 5)
+            .unwrap();
+        mem.merkle_contract_asset_id_balance_insert(&contract, &[1; 32].into(), 7)
+            .unwrap();
+        mem.merkle_contract_asset_id_balance_insert(
+            &ContractId::zeroed(),
+            &AssetId::zeroed(),
+            9,
+        )
+        .unwrap();
+
+        let expected = MerkleTree::root_from_set(
+            [(AssetId::zeroed(), 5u64), ([1; 32].into(), 7)]
+                .into_iter()
+                .map(|(asset, balance)| {
+                    (MerkleTreeKey::new(asset), balance.to_be_bytes())
+                }),
+        );
+        let root = mem.storage::<ContractsAssets>().root(&contract).unwrap();
+        assert_eq!(root, expected);
+    }
 }
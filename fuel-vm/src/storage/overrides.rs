@@ -0,0 +1,357 @@
+//! Storage wrapper overriding the state seen by a dry run.
+
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+};
+
+use fuel_storage::{
+    MerkleRoot,
+    MerkleRootStorage,
+    StorageAsRef,
+    StorageInspect,
+    StorageMutate,
+    StorageRead,
+    StorageSize,
+};
+use fuel_tx::Contract;
+use fuel_types::{
+    Address,
+    AssetId,
+    BlockHeight,
+    Bytes32,
+    ContractId,
+    Salt,
+    Word,
+};
+
+use crate::storage::{
+    changes::{
+        insert_state_range,
+        remove_state_range,
+        state_range,
+    },
+    ChangeSet,
+    ChangeSetTable,
+    ContractsAssets,
+    ContractsAssetsStorage,
+    ContractsInfo,
+    ContractsRawCode,
+    ContractsState,
+    InterpreterStorage,
+    ReadWriteSet,
+    RecordedChanges,
+};
+
+/// Storage wrapper layering contract and block overrides over any
+/// [`InterpreterStorage`].
+///
+/// Reads check the overrides and the values written by the execution before
+/// falling back to the underlying storage. Writes are kept in the wrapper and
+/// never reach the underlying storage, so a transaction can be dry-run against
+/// hypothetical state, e.g. with `StateOverrides::new(&mut storage)`.
+///
+/// The merkle roots are the ones of the underlying storage and don't reflect
+/// the overrides. Likewise, an underlying
+/// [`ReadWriteRecorder`](crate::storage::ReadWriteRecorder) only records the
+/// reads of the keys that aren't overridden or written, and an underlying
+/// [`ChangeRecorder`](crate::storage::ChangeRecorder) records no change.
+#[derive(Debug, Default, Clone)]
+pub struct StateOverrides<S> {
+    storage: S,
+    changes: ChangeSet,
+    block_height: Option<BlockHeight>,
+    timestamps: BTreeMap<BlockHeight, Word>,
+    block_hashes: BTreeMap<BlockHeight, Bytes32>,
+    coinbase: Option<Address>,
+}
+
+impl<S> StateOverrides<S> {
+    /// Layer overrides over `storage`.
+    pub fn new(storage: S) -> Self {
+        Self {
+            storage,
+            changes: ChangeSet::default(),
+            block_height: None,
+            timestamps: BTreeMap::new(),
+            block_hashes: BTreeMap::new(),
+            coinbase: None,
+        }
+    }
+
+    /// Override the current block height.
+    pub fn with_block_height(&mut self, block_height: BlockHeight) -> &mut Self {
+        self.block_height.replace(block_height);
+        self
+    }
+
+    /// Override the timestamp of the block at `block_height`.
+    pub fn with_timestamp(
+        &mut self,
+        block_height: BlockHeight,
+        timestamp: Word,
+    ) -> &mut Self {
+        self.timestamps.insert(block_height, timestamp);
+        self
+    }
+
+    /// Override the hash of the block at `block_height`.
+    pub fn with_block_hash(
+        &mut self,
+        block_height: BlockHeight,
+        block_hash: Bytes32,
+    ) -> &mut Self {
+        self.block_hashes.insert(block_height, block_hash);
+        self
+    }
+
+    /// Override the coinbase address.
+    pub fn with_coinbase(&mut self, coinbase: Address) -> &mut Self {
+        self.coinbase.replace(coinbase);
+        self
+    }
+
+    /// Set the value of a storage slot of `contract`.
+    pub fn with_contract_state(
+        &mut self,
+        contract: &ContractId,
+        key: &Bytes32,
+        value: Bytes32,
+    ) -> &mut Self {
+        ContractsState::set_change(
+            &mut self.changes,
+            &(contract, key).into(),
+            Some(value),
+        );
+        self
+    }
+
+    /// Set the balance of `asset_id` owned by `contract`.
+    pub fn with_contract_balance(
+        &mut self,
+        contract: &ContractId,
+        asset_id: &AssetId,
+        balance: Word,
+    ) -> &mut Self {
+        ContractsAssets::set_change(
+            &mut self.changes,
+            &(contract, asset_id).into(),
+            Some(balance),
+        );
+        self
+    }
+
+    /// Overrides and values written by the execution.
+    pub const fn changes(&self) -> &ChangeSet {
+        &self.changes
+    }
+
+    /// Underlying storage.
+    pub const fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// Remove the wrapper, discarding the overrides and the writes.
+    pub fn into_inner(self) -> S {
+        self.storage
+    }
+}
+
+impl<S> StateOverrides<S>
+where
+    S: StorageInspect<ContractsInfo>,
+{
+    /// Replace the code of `contract`, deploying it if it doesn't exist.
+    ///
+    /// The code root of the contract is updated as well; its salt is kept, or
+    /// zeroed for a new contract.
+    pub fn with_contract_code(
+        &mut self,
+        contract: &ContractId,
+        code: Contract,
+    ) -> Result<&mut Self, S::Error> {
+        let salt = StorageAsRef::storage::<ContractsInfo>(self)
+            .get(contract)?
+            .map(|info| info.0)
+            .unwrap_or_else(Salt::zeroed);
+        let root = code.root();
+
+        ContractsInfo::set_change(&mut self.changes, contract, Some((salt, root)));
+        ContractsRawCode::set_change(&mut self.changes, contract, Some(code));
+
+        Ok(self)
+    }
+}
+
+impl<Type: ChangeSetTable, S> StorageInspect<Type> for StateOverrides<S>
+where
+    S: StorageInspect<Type>,
+{
+    type Error = S::Error;
+
+    fn get(
+        &self,
+        key: &Type::Key,
+    ) -> Result<Option<Cow<'_, Type::OwnedValue>>, Self::Error> {
+        match Type::change(&self.changes, key) {
+            Some(value) => Ok(value.as_ref().map(Cow::Borrowed)),
+            None => self.storage.get(key),
+        }
+    }
+
+    fn contains_key(&self, key: &Type::Key) -> Result<bool, Self::Error> {
+        match Type::change(&self.changes, key) {
+            Some(value) => Ok(value.is_some()),
+            None => self.storage.contains_key(key),
+        }
+    }
+}
+
+impl<Type: ChangeSetTable, S> StorageMutate<Type> for StateOverrides<S>
+where
+    S: StorageInspect<Type>,
+    Type::Value: ToOwned,
+    Type::OwnedValue: From<<Type::Value as ToOwned>::Owned>,
+{
+    fn insert(
+        &mut self,
+        key: &Type::Key,
+        value: &Type::Value,
+    ) -> Result<Option<Type::OwnedValue>, Self::Error> {
+        let previous = self.get(key)?.map(Cow::into_owned);
+        Type::set_change(&mut self.changes, key, Some(value.to_owned().into()));
+        Ok(previous)
+    }
+
+    fn remove(
+        &mut self,
+        key: &Type::Key,
+    ) -> Result<Option<Type::OwnedValue>, Self::Error> {
+        let previous = self.get(key)?.map(Cow::into_owned);
+        Type::set_change(&mut self.changes, key, None);
+        Ok(previous)
+    }
+}
+
+impl<Type: ChangeSetTable, S> StorageSize<Type> for StateOverrides<S>
+where
+    S: StorageSize<Type>,
+    Type::OwnedValue: AsRef<[u8]>,
+{
+    fn size_of_value(&self, key: &Type::Key) -> Result<Option<usize>, Self::Error> {
+        match Type::change(&self.changes, key) {
+            Some(value) => Ok(value.as_ref().map(|v| v.as_ref().len())),
+            None => self.storage.size_of_value(key),
+        }
+    }
+}
+
+impl<Type: ChangeSetTable, S> StorageRead<Type> for StateOverrides<S>
+where
+    S: StorageRead<Type>,
+    Type::OwnedValue: AsRef<[u8]>,
+{
+    fn read(
+        &self,
+        key: &Type::Key,
+        buf: &mut [u8],
+    ) -> Result<Option<usize>, Self::Error> {
+        match Type::change(&self.changes, key) {
+            Some(value) => Ok(value.as_ref().map(|v| {
+                let bytes = v.as_ref();
+                let len = bytes.len().min(buf.len());
+                buf[..len].copy_from_slice(&bytes[..len]);
+                len
+            })),
+            None => self.storage.read(key, buf),
+        }
+    }
+
+    fn read_alloc(&self, key: &Type::Key) -> Result<Option<Vec<u8>>, Self::Error> {
+        match Type::change(&self.changes, key) {
+            Some(value) => Ok(value.as_ref().map(|v| v.as_ref().to_vec())),
+            None => self.storage.read_alloc(key),
+        }
+    }
+}
+
+impl<Key, Type: ChangeSetTable, S> MerkleRootStorage<Key, Type> for StateOverrides<S>
+where
+    S: MerkleRootStorage<Key, Type>,
+{
+    fn root(&self, key: &Key) -> Result<MerkleRoot, Self::Error> {
+        self.storage.root(key)
+    }
+}
+
+impl<S: ContractsAssetsStorage> ContractsAssetsStorage for StateOverrides<S> {}
+
+impl<S> InterpreterStorage for StateOverrides<S>
+where
+    S: InterpreterStorage,
+{
+    type DataError = S::DataError;
+
+    fn block_height(&self) -> Result<BlockHeight, Self::DataError> {
+        match self.block_height {
+            Some(block_height) => Ok(block_height),
+            None => self.storage.block_height(),
+        }
+    }
+
+    fn timestamp(&self, height: BlockHeight) -> Result<Word, Self::DataError> {
+        match self.timestamps.get(&height) {
+            Some(timestamp) => Ok(*timestamp),
+            None => self.storage.timestamp(height),
+        }
+    }
+
+    fn block_hash(&self, block_height: BlockHeight) -> Result<Bytes32, Self::DataError> {
+        match self.block_hashes.get(&block_height) {
+            Some(block_hash) => Ok(*block_hash),
+            None => self.storage.block_hash(block_height),
+        }
+    }
+
+    fn coinbase(&self) -> Result<Address, Self::DataError> {
+        match self.coinbase {
+            Some(coinbase) => Ok(coinbase),
+            None => self.storage.coinbase(),
+        }
+    }
+
+    fn merkle_contract_state_range(
+        &self,
+        id: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> Result<Vec<Option<Cow<'_, Bytes32>>>, Self::DataError> {
+        state_range(self, id, start_key, range)
+    }
+
+    fn merkle_contract_state_insert_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        values: &[Bytes32],
+    ) -> Result<Option<()>, Self::DataError> {
+        insert_state_range(self, contract, start_key, values)
+    }
+
+    fn merkle_contract_state_remove_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> Result<Option<()>, Self::DataError> {
+        remove_state_range(self, contract, start_key, range)
+    }
+
+    fn take_read_write_set(&mut self) -> Option<ReadWriteSet> {
+        self.storage.take_read_write_set()
+    }
+
+    fn take_changes(&mut self) -> Option<RecordedChanges> {
+        self.storage.take_changes()
+    }
+}
@@ -0,0 +1,406 @@
+//! Storage wrapper recording the keys accessed by transactions.
+
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    collections::BTreeSet,
+};
+
+use fuel_storage::{
+    Mappable,
+    MerkleRoot,
+    MerkleRootStorage,
+    StorageInspect,
+    StorageMutate,
+    StorageRead,
+    StorageSize,
+};
+use fuel_types::{
+    Address,
+    BlockHeight,
+    Bytes32,
+    ContractId,
+    Word,
+};
+
+use crate::storage::{
+    state_keys,
+    ContractsAssetKey,
+    ContractsAssets,
+    ContractsAssetsStorage,
+    ContractsInfo,
+    ContractsRawCode,
+    ContractsState,
+    ContractsStateKey,
+    InterpreterStorage,
+    RecordedChanges,
+};
+
+/// Keys of a storage table that were read or written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableAccess<Key> {
+    reads: BTreeSet<Key>,
+    writes: BTreeSet<Key>,
+}
+
+impl<Key> Default for TableAccess<Key> {
+    fn default() -> Self {
+        Self {
+            reads: BTreeSet::new(),
+            writes: BTreeSet::new(),
+        }
+    }
+}
+
+impl<Key: Ord> TableAccess<Key> {
+    /// Keys that were read, including the ones checked for existence.
+    pub const fn reads(&self) -> &BTreeSet<Key> {
+        &self.reads
+    }
+
+    /// Keys that were inserted or removed.
+    pub const fn writes(&self) -> &BTreeSet<Key> {
+        &self.writes
+    }
+
+    /// Check if no key was accessed.
+    pub fn is_empty(&self) -> bool {
+        self.reads.is_empty() && self.writes.is_empty()
+    }
+
+    /// Check if a key written by either access is accessed by the other.
+    pub fn conflicts_with(&self, other: &Self) -> bool {
+        !self.writes.is_disjoint(&other.reads)
+            || !self.writes.is_disjoint(&other.writes)
+            || !self.reads.is_disjoint(&other.writes)
+    }
+
+    fn record_read(&mut self, key: Key) {
+        self.reads.insert(key);
+    }
+
+    fn extend_writes(&mut self, other: &Self)
+    where
+        Key: Clone,
+    {
+        self.writes.extend(other.writes.iter().cloned());
+    }
+
+    fn record_write(&mut self, key: Key) {
+        self.writes.insert(key);
+    }
+}
+
+/// Storage keys accessed by a transaction, per table.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReadWriteSet {
+    contracts_raw_code: TableAccess<ContractId>,
+    contracts_info: TableAccess<ContractId>,
+    contracts_state: TableAccess<ContractsStateKey>,
+    contracts_assets: TableAccess<ContractsAssetKey>,
+}
+
+impl ReadWriteSet {
+    /// Contract codes loaded or deployed.
+    pub const fn contracts_raw_code(&self) -> &TableAccess<ContractId> {
+        &self.contracts_raw_code
+    }
+
+    /// Contract salt and code root entries.
+    pub const fn contracts_info(&self) -> &TableAccess<ContractId> {
+        &self.contracts_info
+    }
+
+    /// Contract state slots.
+    pub const fn contracts_state(&self) -> &TableAccess<ContractsStateKey> {
+        &self.contracts_state
+    }
+
+    /// Contract asset balances.
+    pub const fn contracts_assets(&self) -> &TableAccess<ContractsAssetKey> {
+        &self.contracts_assets
+    }
+
+    /// Check if no key was accessed.
+    pub fn is_empty(&self) -> bool {
+        self.contracts_raw_code.is_empty()
+            && self.contracts_info.is_empty()
+            && self.contracts_state.is_empty()
+            && self.contracts_assets.is_empty()
+    }
+
+    /// Add the keys written in `other` to the written keys.
+    pub(crate) fn extend_writes(&mut self, other: &Self) {
+        self.contracts_raw_code
+            .extend_writes(&other.contracts_raw_code);
+        self.contracts_info.extend_writes(&other.contracts_info);
+        self.contracts_state.extend_writes(&other.contracts_state);
+        self.contracts_assets.extend_writes(&other.contracts_assets);
+    }
+
+    /// Check if the transactions that accessed both sets depend on their
+    /// execution order, i.e. one of them writes a key the other accesses.
+    pub fn conflicts_with(&self, other: &Self) -> bool {
+        self.contracts_raw_code
+            .conflicts_with(&other.contracts_raw_code)
+            || self.contracts_info.conflicts_with(&other.contracts_info)
+            || self.contracts_state.conflicts_with(&other.contracts_state)
+            || self
+                .contracts_assets
+                .conflicts_with(&other.contracts_assets)
+    }
+}
+
+/// Storage table whose accesses are recorded by [`ReadWriteRecorder`].
+pub trait RecordedTable: Mappable {
+    /// Accesses to this table in `set`.
+    fn table(set: &mut ReadWriteSet) -> &mut TableAccess<Self::OwnedKey>;
+}
+
+impl RecordedTable for ContractsRawCode {
+    fn table(set: &mut ReadWriteSet) -> &mut TableAccess<ContractId> {
+        &mut set.contracts_raw_code
+    }
+}
+
+impl RecordedTable for ContractsInfo {
+    fn table(set: &mut ReadWriteSet) -> &mut TableAccess<ContractId> {
+        &mut set.contracts_info
+    }
+}
+
+impl RecordedTable for ContractsState {
+    fn table(set: &mut ReadWriteSet) -> &mut TableAccess<ContractsStateKey> {
+        &mut set.contracts_state
+    }
+}
+
+impl RecordedTable for ContractsAssets {
+    fn table(set: &mut ReadWriteSet) -> &mut TableAccess<ContractsAssetKey> {
+        &mut set.contracts_assets
+    }
+}
+
+/// Storage wrapper recording the keys read and written in the underlying
+/// storage.
+///
+/// The interpreter reports the keys accessed by each transaction in its
+/// [`StateTransition`](crate::state::StateTransition).
+#[derive(Debug, Default, Clone)]
+pub struct ReadWriteRecorder<S> {
+    storage: S,
+    set: RefCell<ReadWriteSet>,
+}
+
+impl<S> ReadWriteRecorder<S> {
+    /// Record the accesses to `storage`.
+    pub fn new(storage: S) -> Self {
+        Self {
+            storage,
+            set: RefCell::new(ReadWriteSet::default()),
+        }
+    }
+
+    /// Keys accessed since the last transaction started.
+    pub fn read_write_set(&self) -> ReadWriteSet {
+        self.set.borrow().clone()
+    }
+
+    /// Underlying storage.
+    pub const fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// Underlying storage, mutable. Accesses through it aren't recorded.
+    pub fn storage_mut(&mut self) -> &mut S {
+        &mut self.storage
+    }
+
+    /// Remove the wrapper.
+    pub fn into_inner(self) -> S {
+        self.storage
+    }
+
+    fn record_read<Type: RecordedTable>(&self, key: &Type::Key)
+    where
+        Type::OwnedKey: Ord,
+    {
+        Type::table(&mut self.set.borrow_mut()).record_read(key.to_owned().into());
+    }
+
+    fn record_write<Type: RecordedTable>(&self, key: &Type::Key)
+    where
+        Type::OwnedKey: Ord,
+    {
+        Type::table(&mut self.set.borrow_mut()).record_write(key.to_owned().into());
+    }
+
+    fn record_state_range(
+        &self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+        write: bool,
+    ) {
+        let mut set = self.set.borrow_mut();
+        for key in state_keys(start_key).take(range as usize) {
+            let key = (contract, &key).into();
+            if write {
+                set.contracts_state.record_write(key);
+            } else {
+                set.contracts_state.record_read(key);
+            }
+        }
+    }
+}
+
+impl<Type: RecordedTable, S> StorageInspect<Type> for ReadWriteRecorder<S>
+where
+    S: StorageInspect<Type>,
+    Type::OwnedKey: Ord,
+{
+    type Error = S::Error;
+
+    fn get(
+        &self,
+        key: &Type::Key,
+    ) -> Result<Option<Cow<'_, Type::OwnedValue>>, Self::Error> {
+        self.record_read::<Type>(key);
+        self.storage.get(key)
+    }
+
+    fn contains_key(&self, key: &Type::Key) -> Result<bool, Self::Error> {
+        self.record_read::<Type>(key);
+        self.storage.contains_key(key)
+    }
+}
+
+impl<Type: RecordedTable, S> StorageMutate<Type> for ReadWriteRecorder<S>
+where
+    S: StorageMutate<Type>,
+    Type::OwnedKey: Ord,
+{
+    fn insert(
+        &mut self,
+        key: &Type::Key,
+        value: &Type::Value,
+    ) -> Result<Option<Type::OwnedValue>, Self::Error> {
+        self.record_write::<Type>(key);
+        self.storage.insert(key, value)
+    }
+
+    fn remove(
+        &mut self,
+        key: &Type::Key,
+    ) -> Result<Option<Type::OwnedValue>, Self::Error> {
+        self.record_write::<Type>(key);
+        self.storage.remove(key)
+    }
+}
+
+impl<Type: RecordedTable, S> StorageSize<Type> for ReadWriteRecorder<S>
+where
+    S: StorageSize<Type>,
+    Type::OwnedKey: Ord,
+{
+    fn size_of_value(&self, key: &Type::Key) -> Result<Option<usize>, Self::Error> {
+        self.record_read::<Type>(key);
+        self.storage.size_of_value(key)
+    }
+}
+
+impl<Type: RecordedTable, S> StorageRead<Type> for ReadWriteRecorder<S>
+where
+    S: StorageRead<Type>,
+    Type::OwnedKey: Ord,
+{
+    fn read(
+        &self,
+        key: &Type::Key,
+        buf: &mut [u8],
+    ) -> Result<Option<usize>, Self::Error> {
+        self.record_read::<Type>(key);
+        self.storage.read(key, buf)
+    }
+
+    fn read_alloc(&self, key: &Type::Key) -> Result<Option<Vec<u8>>, Self::Error> {
+        self.record_read::<Type>(key);
+        self.storage.read_alloc(key)
+    }
+}
+
+impl<Key, Type: RecordedTable, S> MerkleRootStorage<Key, Type> for ReadWriteRecorder<S>
+where
+    S: MerkleRootStorage<Key, Type>,
+    Type::OwnedKey: Ord,
+{
+    fn root(&self, key: &Key) -> Result<MerkleRoot, Self::Error> {
+        self.storage.root(key)
+    }
+}
+
+impl<S: ContractsAssetsStorage> ContractsAssetsStorage for ReadWriteRecorder<S> {}
+
+impl<S> InterpreterStorage for ReadWriteRecorder<S>
+where
+    S: InterpreterStorage,
+{
+    type DataError = S::DataError;
+
+    fn block_height(&self) -> Result<BlockHeight, Self::DataError> {
+        self.storage.block_height()
+    }
+
+    fn timestamp(&self, height: BlockHeight) -> Result<Word, Self::DataError> {
+        self.storage.timestamp(height)
+    }
+
+    fn block_hash(&self, block_height: BlockHeight) -> Result<Bytes32, Self::DataError> {
+        self.storage.block_hash(block_height)
+    }
+
+    fn coinbase(&self) -> Result<Address, Self::DataError> {
+        self.storage.coinbase()
+    }
+
+    fn merkle_contract_state_range(
+        &self,
+        id: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> Result<Vec<Option<Cow<'_, Bytes32>>>, Self::DataError> {
+        self.record_state_range(id, start_key, range, false);
+        self.storage
+            .merkle_contract_state_range(id, start_key, range)
+    }
+
+    fn merkle_contract_state_insert_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        values: &[Bytes32],
+    ) -> Result<Option<()>, Self::DataError> {
+        self.record_state_range(contract, start_key, values.len() as Word, true);
+        self.storage
+            .merkle_contract_state_insert_range(contract, start_key, values)
+    }
+
+    fn merkle_contract_state_remove_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> Result<Option<()>, Self::DataError> {
+        self.record_state_range(contract, start_key, range, true);
+        self.storage
+            .merkle_contract_state_remove_range(contract, start_key, range)
+    }
+
+    fn take_read_write_set(&mut self) -> Option<ReadWriteSet> {
+        Some(self.set.take())
+    }
+
+    fn take_changes(&mut self) -> Option<RecordedChanges> {
+        self.storage.take_changes()
+    }
+}
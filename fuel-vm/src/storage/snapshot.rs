@@ -0,0 +1,353 @@
+//! Versioned snapshots of the chain state, for test fixtures and bug
+//! reproductions.
+//!
+//! A [`StorageSnapshot`] is encoded either as JSON, with the `json` feature, or
+//! in a compact binary form. Both carry the format version, and loading a
+//! snapshot of another version fails with
+//! [`SnapshotError::UnsupportedVersion`].
+
+#[cfg(test)]
+mod tests;
+
+use std::collections::BTreeMap;
+
+use fuel_tx::{
+    Contract,
+    StorageSlot,
+};
+use fuel_types::{
+    Address,
+    AssetId,
+    BlockHeight,
+    Bytes32,
+    ContractId,
+    Salt,
+    Word,
+};
+
+use crate::storage::{
+    InterpreterStorage,
+    MemoryStorage,
+};
+
+/// Header of the binary form.
+const MAGIC: [u8; 8] = *b"FUELSNAP";
+
+/// Failure to decode a snapshot.
+#[derive(Debug, thiserror::Error)]
+pub enum SnapshotError {
+    /// The snapshot was written in another version of the format.
+    #[error("Unsupported snapshot version {0}")]
+    UnsupportedVersion(u32),
+    /// The binary form is truncated or corrupted.
+    #[error("Malformed snapshot")]
+    Malformed,
+    /// The JSON form is invalid.
+    #[cfg(feature = "json")]
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+/// State of a contract in a [`StorageSnapshot`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ContractSnapshot {
+    /// Id of the contract.
+    pub id: ContractId,
+    /// Bytecode, if the contract is deployed.
+    pub code: Option<Contract>,
+    /// Salt and code root, if the contract is deployed.
+    pub info: Option<(Salt, Bytes32)>,
+    /// State slots, ordered by key.
+    pub state: Vec<StorageSlot>,
+    /// Asset balances, ordered by asset id.
+    pub balances: Vec<(AssetId, Word)>,
+}
+
+/// Full chain state of a [`MemoryStorage`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StorageSnapshot {
+    version: u32,
+    /// Current block height.
+    pub block_height: BlockHeight,
+    /// Coinbase of the current block.
+    pub coinbase: Address,
+    /// Contracts with code, state or balances, ordered by id.
+    pub contracts: Vec<ContractSnapshot>,
+}
+
+impl StorageSnapshot {
+    /// Version of the snapshot format.
+    pub const VERSION: u32 = 1;
+
+    /// Empty snapshot at `block_height`.
+    pub const fn new(block_height: BlockHeight, coinbase: Address) -> Self {
+        Self {
+            version: Self::VERSION,
+            block_height,
+            coinbase,
+            contracts: vec![],
+        }
+    }
+
+    /// Snapshot of the current state of `storage`.
+    pub fn from_storage(storage: &MemoryStorage) -> Self {
+        let block_height = storage.block_height().expect("Infallible");
+        let coinbase = storage.coinbase().expect("Infallible");
+
+        fn contract<'a>(
+            contracts: &'a mut BTreeMap<ContractId, ContractSnapshot>,
+            id: &ContractId,
+        ) -> &'a mut ContractSnapshot {
+            contracts.entry(*id).or_insert_with(|| ContractSnapshot {
+                id: *id,
+                ..Default::default()
+            })
+        }
+
+        let mut contracts = BTreeMap::new();
+        for (id, code) in storage.all_contracts() {
+            contract(&mut contracts, id).code = Some(code.clone());
+        }
+        for (id, info) in storage.all_contract_info() {
+            contract(&mut contracts, id).info = Some(*info);
+        }
+        for (key, value) in storage.all_contract_state() {
+            contract(&mut contracts, key.contract_id())
+                .state
+                .push(StorageSlot::new(*key.state_key(), *value));
+        }
+        for (key, balance) in storage.all_contract_balances() {
+            contract(&mut contracts, key.contract_id())
+                .balances
+                .push((*key.asset_id(), *balance));
+        }
+
+        Self {
+            contracts: contracts.into_values().collect(),
+            ..Self::new(block_height, coinbase)
+        }
+    }
+
+    /// Version of the format the snapshot was decoded from.
+    pub const fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Build a [`MemoryStorage`] with the snapshot state, committed and
+    /// persisted.
+    pub fn to_memory_storage(&self) -> MemoryStorage {
+        let mut storage = MemoryStorage::new(self.block_height, self.coinbase);
+        self.load_into(&mut storage).expect("Infallible");
+        storage.commit();
+        storage.persist();
+        storage
+    }
+
+    /// Write the contracts of the snapshot to `storage`.
+    ///
+    /// The deployed contracts are written with
+    /// [`InterpreterStorage::deploy_contract_with_id`]. The block height and
+    /// coinbase are left to the caller, since the storage defines them.
+    pub fn load_into<S>(&self, storage: &mut S) -> Result<(), S::DataError>
+    where
+        S: InterpreterStorage,
+    {
+        for contract in &self.contracts {
+            match (&contract.code, &contract.info) {
+                (Some(code), Some((salt, root))) => storage.deploy_contract_with_id(
+                    salt,
+                    &contract.state,
+                    code,
+                    root,
+                    &contract.id,
+                )?,
+                (code, info) => {
+                    if let Some(code) = code {
+                        storage.storage_contract_insert(&contract.id, code)?;
+                    }
+                    if let Some((salt, root)) = info {
+                        storage.storage_contract_root_insert(&contract.id, salt, root)?;
+                    }
+                    for slot in &contract.state {
+                        storage.merkle_contract_state_insert(
+                            &contract.id,
+                            slot.key(),
+                            slot.value(),
+                        )?;
+                    }
+                }
+            }
+            for (asset, balance) in &contract.balances {
+                storage.merkle_contract_asset_id_balance_insert(
+                    &contract.id,
+                    asset,
+                    *balance,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Encode the snapshot in the compact binary form.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = MAGIC.to_vec();
+        buf.extend_from_slice(&self.version.to_be_bytes());
+        buf.extend_from_slice(&self.block_height.to_bytes());
+        buf.extend_from_slice(self.coinbase.as_ref());
+        put_len(&mut buf, self.contracts.len());
+
+        for contract in &self.contracts {
+            buf.extend_from_slice(contract.id.as_ref());
+            match &contract.code {
+                Some(code) => {
+                    buf.push(1);
+                    put_len(&mut buf, code.as_ref().len());
+                    buf.extend_from_slice(code.as_ref());
+                }
+                None => buf.push(0),
+            }
+            match &contract.info {
+                Some((salt, root)) => {
+                    buf.push(1);
+                    buf.extend_from_slice(salt.as_ref());
+                    buf.extend_from_slice(root.as_ref());
+                }
+                None => buf.push(0),
+            }
+            put_len(&mut buf, contract.state.len());
+            for slot in &contract.state {
+                buf.extend_from_slice(slot.key().as_ref());
+                buf.extend_from_slice(slot.value().as_ref());
+            }
+            put_len(&mut buf, contract.balances.len());
+            for (asset, balance) in &contract.balances {
+                buf.extend_from_slice(asset.as_ref());
+                buf.extend_from_slice(&balance.to_be_bytes());
+            }
+        }
+
+        buf
+    }
+
+    /// Decode a snapshot from the compact binary form.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SnapshotError> {
+        let mut reader = Reader(bytes);
+        if reader.array()? != MAGIC {
+            return Err(SnapshotError::Malformed)
+        }
+        let version = u32::from_be_bytes(reader.array()?);
+        if version != Self::VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version))
+        }
+        let block_height = u32::from_be_bytes(reader.array()?).into();
+        let coinbase = reader.array()?.into();
+
+        let mut contracts = vec![];
+        for _ in 0..reader.count()? {
+            let id = reader.array()?.into();
+            let code = match reader.flag()? {
+                true => {
+                    let len = reader.count()?;
+                    Some(reader.bytes(len)?.into())
+                }
+                false => None,
+            };
+            let info = match reader.flag()? {
+                true => Some((reader.array()?.into(), reader.array()?.into())),
+                false => None,
+            };
+            let state = (0..reader.count()?)
+                .map(|_| {
+                    Ok(StorageSlot::new(
+                        reader.array()?.into(),
+                        reader.array()?.into(),
+                    ))
+                })
+                .collect::<Result<_, SnapshotError>>()?;
+            let balances = (0..reader.count()?)
+                .map(|_| {
+                    Ok((reader.array()?.into(), Word::from_be_bytes(reader.array()?)))
+                })
+                .collect::<Result<_, SnapshotError>>()?;
+
+            contracts.push(ContractSnapshot {
+                id,
+                code,
+                info,
+                state,
+                balances,
+            });
+        }
+        if !reader.0.is_empty() {
+            return Err(SnapshotError::Malformed)
+        }
+
+        Ok(Self {
+            version,
+            block_height,
+            coinbase,
+            contracts,
+        })
+    }
+
+    /// Encode the snapshot as JSON.
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> Result<String, SnapshotError> {
+        serde_json::to_string_pretty(self).map_err(Into::into)
+    }
+
+    /// Decode a snapshot from JSON.
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &str) -> Result<Self, SnapshotError> {
+        let snapshot: Self = serde_json::from_str(json)?;
+        if snapshot.version != Self::VERSION {
+            return Err(SnapshotError::UnsupportedVersion(snapshot.version))
+        }
+        Ok(snapshot)
+    }
+}
+
+impl From<&MemoryStorage> for StorageSnapshot {
+    fn from(storage: &MemoryStorage) -> Self {
+        Self::from_storage(storage)
+    }
+}
+
+fn put_len(buf: &mut Vec<u8>, len: usize) {
+    buf.extend_from_slice(&(len as u32).to_be_bytes());
+}
+
+/// Cursor over the binary form.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], SnapshotError> {
+        if self.0.len() < len {
+            return Err(SnapshotError::Malformed)
+        }
+        let (head, tail) = self.0.split_at(len);
+        self.0 = tail;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], SnapshotError> {
+        self.bytes(N)?
+            .try_into()
+            .map_err(|_| SnapshotError::Malformed)
+    }
+
+    fn count(&mut self) -> Result<usize, SnapshotError> {
+        Ok(u32::from_be_bytes(self.array()?) as usize)
+    }
+
+    fn flag(&mut self) -> Result<bool, SnapshotError> {
+        match self.array::<1>()? {
+            [0] => Ok(false),
+            [1] => Ok(true),
+            _ => Err(SnapshotError::Malformed),
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use fuel_storage::StorageAsRef;
+use fuel_tx::Contract;
+use fuel_types::{
+    Address,
+    AssetId,
+    BlockHeight,
+    Bytes32,
+    ContractId,
+    Salt,
+};
+
+use crate::storage::{
+    ContractsAssets,
+    ContractsAssetsStorage,
+    ContractsState,
+    InterpreterStorage,
+    MemoryStorage,
+    SnapshotError,
+    StorageSnapshot,
+    StorageTransaction,
+};
+
+fn populated() -> MemoryStorage {
+    let contract = ContractId::from([1; 32]);
+    let code = Contract::from(vec![0x12; 16]);
+    let root = code.root();
+    let mut storage = MemoryStorage::new(BlockHeight::new(7), Address::from([0xcb; 32]));
+
+    storage
+        .deploy_contract_with_id(&Salt::from([3; 32]), &[], &code, &root, &contract)
+        .unwrap();
+    storage
+        .merkle_contract_state_insert(&contract, &Bytes32::zeroed(), &[7; 32].into())
+        .unwrap();
+    storage
+        .merkle_contract_asset_id_balance_insert(&contract, &AssetId::zeroed(), 100)
+        .unwrap();
+    // Balance of a contract that isn't deployed
+    storage
+        .merkle_contract_asset_id_balance_insert(
+            &ContractId::from([2; 32]),
+            &AssetId::from([4; 32]),
+            5,
+        )
+        .unwrap();
+
+    storage
+}
+
+fn assert_same_state(a: &MemoryStorage, b: &MemoryStorage) {
+    assert_eq!(
+        a.all_contracts().collect::<Vec<_>>(),
+        b.all_contracts().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        a.all_contract_info().collect::<Vec<_>>(),
+        b.all_contract_info().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        a.all_contract_state().collect::<Vec<_>>(),
+        b.all_contract_state().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        a.all_contract_balances().collect::<Vec<_>>(),
+        b.all_contract_balances().collect::<Vec<_>>()
+    );
+    assert_eq!(a.block_height().unwrap(), b.block_height().unwrap());
+    assert_eq!(a.coinbase().unwrap(), b.coinbase().unwrap());
+
+    for id in [[1; 32], [2; 32]].map(ContractId::from) {
+        assert_eq!(
+            a.storage::<ContractsState>().root(&id).unwrap(),
+            b.storage::<ContractsState>().root(&id).unwrap()
+        );
+        assert_eq!(
+            a.storage::<ContractsAssets>().root(&id).unwrap(),
+            b.storage::<ContractsAssets>().root(&id).unwrap()
+        );
+    }
+}
+
+#[test]
+fn binary_round_trip() {
+    let storage = populated();
+    let snapshot = StorageSnapshot::from_storage(&storage);
+    assert_eq!(snapshot.contracts.len(), 2);
+
+    let bytes = snapshot.to_bytes();
+    let decoded = StorageSnapshot::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded, snapshot);
+    assert_same_state(&decoded.to_memory_storage(), &storage);
+
+    assert!(matches!(
+        StorageSnapshot::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(SnapshotError::Malformed)
+    ));
+
+    let mut future = bytes;
+    future[8..12].copy_from_slice(&2u32.to_be_bytes());
+    assert!(matches!(
+        StorageSnapshot::from_bytes(&future),
+        Err(SnapshotError::UnsupportedVersion(2))
+    ));
+}
+
+#[test]
+fn json_round_trip() {
+    let storage = populated();
+    let snapshot = StorageSnapshot::from_storage(&storage);
+
+    let json = snapshot.to_json().unwrap();
+    let decoded = StorageSnapshot::from_json(&json).unwrap();
+    assert_eq!(decoded, snapshot);
+    assert_same_state(&decoded.to_memory_storage(), &storage);
+
+    let future = json.replacen("\"version\": 1", "\"version\": 2", 1);
+    assert!(matches!(
+        StorageSnapshot::from_json(&future),
+        Err(SnapshotError::UnsupportedVersion(2))
+    ));
+}
+
+#[test]
+fn load_into_any_storage() {
+    let storage = populated();
+    let snapshot = StorageSnapshot::from_storage(&storage);
+
+    let mut target = MemoryStorage::new(BlockHeight::new(7), Address::from([0xcb; 32]));
+    let mut tx = StorageTransaction::new(&mut target);
+    snapshot.load_into(&mut tx).unwrap();
+    assert_eq!(
+        tx.merkle_contract_asset_id_balance(
+            &ContractId::from([2; 32]),
+            &AssetId::from([4; 32])
+        )
+        .unwrap(),
+        Some(5)
+    );
+    tx.commit().unwrap();
+    drop(tx);
+
+    assert_same_state(&target, &storage);
+}
@@ -0,0 +1,320 @@
+//! Storage wrapper buffering writes until they are committed.
+
+#[cfg(test)]
+mod tests;
+
+use std::borrow::Cow;
+
+use fuel_storage::{
+    MerkleRoot,
+    MerkleRootStorage,
+    StorageInspect,
+    StorageMutate,
+    StorageRead,
+    StorageSize,
+};
+use fuel_types::{
+    Address,
+    BlockHeight,
+    Bytes32,
+    ContractId,
+    Word,
+};
+
+use crate::storage::{
+    changes::{
+        insert_state_range,
+        remove_state_range,
+        state_range,
+    },
+    ChangeSet,
+    ChangeSetTable,
+    ContractsAssetsStorage,
+    InterpreterStorage,
+    ReadWriteSet,
+    RecordedChanges,
+};
+
+/// Storage wrapper buffering the writes to the contract tables over any
+/// [`InterpreterStorage`].
+///
+/// The writes are kept in layers, one per open savepoint, on top of the
+/// underlying storage. Rolling back or releasing a savepoint, and committing
+/// the transaction, costs the number of changed keys, not the size of the
+/// storage.
+///
+/// The merkle roots are the ones of the underlying storage and don't reflect
+/// the pending changes. Likewise, an underlying
+/// [`ReadWriteRecorder`](crate::storage::ReadWriteRecorder) or
+/// [`ChangeRecorder`](crate::storage::ChangeRecorder) only records the keys
+/// that reach it, i.e. the reads of unchanged keys and the commits.
+#[derive(Debug, Default, Clone)]
+pub struct StorageTransaction<S> {
+    storage: S,
+    /// Changes of the transaction, then of each savepoint. Never empty.
+    layers: Vec<ChangeSet>,
+}
+
+impl<S> StorageTransaction<S> {
+    /// Start a transaction over `storage`.
+    pub fn new(storage: S) -> Self {
+        Self {
+            storage,
+            layers: vec![ChangeSet::default()],
+        }
+    }
+
+    /// Open a nested savepoint. Returns the number of open savepoints.
+    pub fn savepoint(&mut self) -> usize {
+        self.layers.push(ChangeSet::default());
+        self.savepoints()
+    }
+
+    /// Number of open savepoints.
+    pub fn savepoints(&self) -> usize {
+        self.layers.len() - 1
+    }
+
+    /// Discard the changes made since the last savepoint, and close it.
+    ///
+    /// Returns the discarded changes, or `None` if there is no open savepoint.
+    pub fn rollback_to_savepoint(&mut self) -> Option<ChangeSet> {
+        if self.savepoints() == 0 {
+            return None
+        }
+        self.layers.pop()
+    }
+
+    /// Keep the changes made since the last savepoint in the enclosing one, and
+    /// close it. Returns `false` if there is no open savepoint.
+    pub fn release_savepoint(&mut self) -> bool {
+        if self.savepoints() == 0 {
+            return false
+        }
+        if let Some(changes) = self.layers.pop() {
+            self.top_mut().extend(changes);
+        }
+        true
+    }
+
+    /// Discard all the changes and close the savepoints.
+    pub fn rollback(&mut self) {
+        self.layers.truncate(1);
+        *self.top_mut() = ChangeSet::default();
+    }
+
+    /// Pending changes, including the ones of the open savepoints.
+    pub fn changes(&self) -> ChangeSet {
+        self.layers
+            .iter()
+            .cloned()
+            .fold(ChangeSet::default(), |mut changes, layer| {
+                changes.extend(layer);
+                changes
+            })
+    }
+
+    /// Underlying storage.
+    pub const fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// Remove the wrapper, discarding the pending changes.
+    pub fn into_inner(self) -> S {
+        self.storage
+    }
+
+    /// Latest change of `key`, if any.
+    fn change<Type: ChangeSetTable>(
+        &self,
+        key: &Type::Key,
+    ) -> Option<&Option<Type::OwnedValue>> {
+        self.layers
+            .iter()
+            .rev()
+            .find_map(|layer| Type::change(layer, key))
+    }
+
+    fn top_mut(&mut self) -> &mut ChangeSet {
+        self.layers
+            .last_mut()
+            .expect("The transaction layer is never removed")
+    }
+}
+
+impl<S> StorageTransaction<S>
+where
+    S: InterpreterStorage,
+{
+    /// Write the pending changes to the underlying storage, and close the
+    /// savepoints.
+    ///
+    /// The changes are applied key by key; if the underlying storage fails, the
+    /// ones already applied aren't reverted and the pending changes are kept.
+    pub fn commit(&mut self) -> Result<(), S::DataError> {
+        self.changes().apply(&mut self.storage)?;
+        self.rollback();
+        Ok(())
+    }
+}
+
+impl<Type: ChangeSetTable, S> StorageInspect<Type> for StorageTransaction<S>
+where
+    S: StorageInspect<Type>,
+{
+    type Error = S::Error;
+
+    fn get(
+        &self,
+        key: &Type::Key,
+    ) -> Result<Option<Cow<'_, Type::OwnedValue>>, Self::Error> {
+        match self.change::<Type>(key) {
+            Some(value) => Ok(value.as_ref().map(Cow::Borrowed)),
+            None => self.storage.get(key),
+        }
+    }
+
+    fn contains_key(&self, key: &Type::Key) -> Result<bool, Self::Error> {
+        match self.change::<Type>(key) {
+            Some(value) => Ok(value.is_some()),
+            None => self.storage.contains_key(key),
+        }
+    }
+}
+
+impl<Type: ChangeSetTable, S> StorageMutate<Type> for StorageTransaction<S>
+where
+    S: StorageInspect<Type>,
+    Type::Value: ToOwned,
+    Type::OwnedValue: From<<Type::Value as ToOwned>::Owned>,
+{
+    fn insert(
+        &mut self,
+        key: &Type::Key,
+        value: &Type::Value,
+    ) -> Result<Option<Type::OwnedValue>, Self::Error> {
+        let previous = self.get(key)?.map(Cow::into_owned);
+        Type::set_change(self.top_mut(), key, Some(value.to_owned().into()));
+        Ok(previous)
+    }
+
+    fn remove(
+        &mut self,
+        key: &Type::Key,
+    ) -> Result<Option<Type::OwnedValue>, Self::Error> {
+        let previous = self.get(key)?.map(Cow::into_owned);
+        Type::set_change(self.top_mut(), key, None);
+        Ok(previous)
+    }
+}
+
+impl<Type: ChangeSetTable, S> StorageSize<Type> for StorageTransaction<S>
+where
+    S: StorageSize<Type>,
+    Type::OwnedValue: AsRef<[u8]>,
+{
+    fn size_of_value(&self, key: &Type::Key) -> Result<Option<usize>, Self::Error> {
+        match self.change::<Type>(key) {
+            Some(value) => Ok(value.as_ref().map(|v| v.as_ref().len())),
+            None => self.storage.size_of_value(key),
+        }
+    }
+}
+
+impl<Type: ChangeSetTable, S> StorageRead<Type> for StorageTransaction<S>
+where
+    S: StorageRead<Type>,
+    Type::OwnedValue: AsRef<[u8]>,
+{
+    fn read(
+        &self,
+        key: &Type::Key,
+        buf: &mut [u8],
+    ) -> Result<Option<usize>, Self::Error> {
+        match self.change::<Type>(key) {
+            Some(value) => Ok(value.as_ref().map(|v| {
+                let bytes = v.as_ref();
+                let len = bytes.len().min(buf.len());
+                buf[..len].copy_from_slice(&bytes[..len]);
+                len
+            })),
+            None => self.storage.read(key, buf),
+        }
+    }
+
+    fn read_alloc(&self, key: &Type::Key) -> Result<Option<Vec<u8>>, Self::Error> {
+        match self.change::<Type>(key) {
+            Some(value) => Ok(value.as_ref().map(|v| v.as_ref().to_vec())),
+            None => self.storage.read_alloc(key),
+        }
+    }
+}
+
+impl<Key, Type: ChangeSetTable, S> MerkleRootStorage<Key, Type> for StorageTransaction<S>
+where
+    S: MerkleRootStorage<Key, Type>,
+{
+    fn root(&self, key: &Key) -> Result<MerkleRoot, Self::Error> {
+        self.storage.root(key)
+    }
+}
+
+impl<S: ContractsAssetsStorage> ContractsAssetsStorage for StorageTransaction<S> {}
+
+impl<S> InterpreterStorage for StorageTransaction<S>
+where
+    S: InterpreterStorage,
+{
+    type DataError = S::DataError;
+
+    fn block_height(&self) -> Result<BlockHeight, Self::DataError> {
+        self.storage.block_height()
+    }
+
+    fn timestamp(&self, height: BlockHeight) -> Result<Word, Self::DataError> {
+        self.storage.timestamp(height)
+    }
+
+    fn block_hash(&self, block_height: BlockHeight) -> Result<Bytes32, Self::DataError> {
+        self.storage.block_hash(block_height)
+    }
+
+    fn coinbase(&self) -> Result<Address, Self::DataError> {
+        self.storage.coinbase()
+    }
+
+    fn merkle_contract_state_range(
+        &self,
+        id: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> Result<Vec<Option<Cow<'_, Bytes32>>>, Self::DataError> {
+        state_range(self, id, start_key, range)
+    }
+
+    fn merkle_contract_state_insert_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        values: &[Bytes32],
+    ) -> Result<Option<()>, Self::DataError> {
+        insert_state_range(self, contract, start_key, values)
+    }
+
+    fn merkle_contract_state_remove_range(
+        &mut self,
+        contract: &ContractId,
+        start_key: &Bytes32,
+        range: Word,
+    ) -> Result<Option<()>, Self::DataError> {
+        remove_state_range(self, contract, start_key, range)
+    }
+
+    fn take_read_write_set(&mut self) -> Option<ReadWriteSet> {
+        self.storage.take_read_write_set()
+    }
+
+    fn take_changes(&mut self) -> Option<RecordedChanges> {
+        self.storage.take_changes()
+    }
+}
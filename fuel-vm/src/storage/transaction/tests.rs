@@ -0,0 +1,134 @@
+use fuel_tx::Contract;
+use fuel_types::{
+    AssetId,
+    Bytes32,
+    ContractId,
+    Salt,
+};
+
+use crate::storage::{
+    ContractsAssetsStorage,
+    InterpreterStorage,
+    MemoryStorage,
+    StorageTransaction,
+};
+
+fn slot(k: u8) -> Bytes32 {
+    Bytes32::from([k; 32])
+}
+
+fn state(
+    storage: &impl InterpreterStorage,
+    contract: &ContractId,
+    k: u8,
+) -> Option<Bytes32> {
+    storage
+        .merkle_contract_state(contract, &slot(k))
+        .unwrap()
+        .map(|value| value.into_owned())
+}
+
+#[test]
+fn nested_savepoints_rollback_and_release() {
+    let contract = ContractId::from([1; 32]);
+    let mut storage = MemoryStorage::default();
+    storage
+        .merkle_contract_state_insert(&contract, &slot(1), &slot(10))
+        .unwrap();
+
+    let mut tx = StorageTransaction::new(&mut storage);
+    tx.merkle_contract_state_insert(&contract, &slot(2), &slot(20))
+        .unwrap();
+
+    assert_eq!(tx.savepoint(), 1);
+    tx.merkle_contract_state_insert(&contract, &slot(1), &slot(11))
+        .unwrap();
+    tx.merkle_contract_state_remove(&contract, &slot(2))
+        .unwrap();
+    assert_eq!(state(&tx, &contract, 1), Some(slot(11)));
+    assert_eq!(state(&tx, &contract, 2), None);
+
+    assert_eq!(tx.savepoint(), 2);
+    tx.merkle_contract_state_insert(&contract, &slot(3), &slot(30))
+        .unwrap();
+    let discarded = tx.rollback_to_savepoint().expect("Expected a savepoint");
+    assert_eq!(discarded.len(), 1);
+    assert_eq!(state(&tx, &contract, 3), None);
+
+    let discarded = tx.rollback_to_savepoint().expect("Expected a savepoint");
+    assert_eq!(discarded.len(), 2);
+    assert_eq!(state(&tx, &contract, 1), Some(slot(10)));
+    assert_eq!(state(&tx, &contract, 2), Some(slot(20)));
+    assert_eq!(tx.rollback_to_savepoint(), None);
+
+    tx.savepoint();
+    tx.merkle_contract_state_remove(&contract, &slot(1))
+        .unwrap();
+    assert!(tx.release_savepoint());
+    assert!(!tx.release_savepoint());
+    assert_eq!(tx.savepoints(), 0);
+
+    let changes = tx.changes();
+    assert_eq!(changes.len(), 2);
+    assert_eq!(
+        changes.contracts_state().get(&(&contract, &slot(1)).into()),
+        Some(&None)
+    );
+    assert_eq!(
+        changes.contracts_state().get(&(&contract, &slot(2)).into()),
+        Some(&Some(slot(20)))
+    );
+
+    // Nothing reaches the underlying storage before the commit
+    assert_eq!(state(tx.storage(), &contract, 1), Some(slot(10)));
+    assert_eq!(state(tx.storage(), &contract, 2), None);
+
+    tx.commit().unwrap();
+    assert!(tx.changes().is_empty());
+    drop(tx);
+
+    assert_eq!(state(&storage, &contract, 1), None);
+    assert_eq!(state(&storage, &contract, 2), Some(slot(20)));
+}
+
+#[test]
+fn rollback_discards_all_tables() {
+    let contract = ContractId::from([1; 32]);
+    let code = Contract::from(vec![0x12; 16]);
+    let root = code.root();
+    let mut storage = MemoryStorage::default();
+
+    let mut tx = StorageTransaction::new(&mut storage);
+    tx.savepoint();
+    tx.deploy_contract_with_id(&Salt::zeroed(), &[], &code, &root, &contract)
+        .unwrap();
+    tx.merkle_contract_asset_id_balance_insert(&contract, &AssetId::zeroed(), 5)
+        .unwrap();
+
+    assert_eq!(tx.storage_contract_size(&contract).unwrap(), Some(16));
+    assert_eq!(
+        tx.storage_contract_root(&contract)
+            .unwrap()
+            .map(|info| info.into_owned()),
+        Some((Salt::zeroed(), root))
+    );
+    assert_eq!(tx.changes().len(), 3);
+
+    tx.rollback();
+    assert_eq!(tx.savepoints(), 0);
+    assert!(tx.changes().is_empty());
+    assert!(!tx.storage_contract_exists(&contract).unwrap());
+
+    tx.deploy_contract_with_id(&Salt::zeroed(), &[], &code, &root, &contract)
+        .unwrap();
+    tx.commit().unwrap();
+    drop(tx);
+
+    assert!(storage.storage_contract_exists(&contract).unwrap());
+    assert_eq!(
+        storage
+            .merkle_contract_asset_id_balance(&contract, &AssetId::zeroed())
+            .unwrap(),
+        None
+    );
+}
@@ -1,5 +1,6 @@
 use fuel_asm::{
     op,
+    Instruction,
     RegId,
 };
 use fuel_tx::{
@@ -110,3 +111,107 @@ fn code_coverage() {
         );
     }
 }
+
+#[test]
+fn code_coverage_source_map() {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+
+    let script_code = vec![
+        op::jnei(RegId::ZERO, RegId::ONE, 2), // main.sw:1
+        op::noop(),                           // main.sw:2, skipped
+        op::noop(),                           // main.sw:3
+        op::noop(),                           // main.sw:3
+        op::ret(RegId::ONE),                  // main.sw:4
+    ];
+
+    let tx_script = TransactionBuilder::script(script_code.into_iter().collect(), vec![])
+        .add_unsigned_coin_input(
+            rng.gen(),
+            rng.gen(),
+            1,
+            Default::default(),
+            rng.gen(),
+            Default::default(),
+        )
+        .gas_limit(1_000_000)
+        .finalize_checked(Default::default(), &GasCosts::default());
+
+    // Coverage is collected even without a profile receiver
+    let mut transactor: Transactor<_, Script> = Interpreter::with_memory_storage().into();
+    transactor.transact(tx_script);
+    assert!(!transactor.is_reverted());
+
+    // An unrelated contract that never runs
+    let contract: ContractId = rng.gen();
+    let lines = [1, 2, 3, 3, 4];
+    let source_map: SourceMap = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            (
+                InstructionLocation::new(None, i as u64 * Instruction::SIZE as u64),
+                SourceLocation::new("main.sw", *line, 5),
+            )
+        })
+        .chain([(
+            InstructionLocation::new(Some(contract), 0),
+            SourceLocation::new("lib.sw", 7, 1),
+        )])
+        .collect();
+
+    let json = serde_json::to_string(&source_map).expect("Serialization failed");
+    let source_map: SourceMap =
+        serde_json::from_str(&json).expect("Deserialization failed");
+    assert_eq!(source_map.len(), 6);
+
+    let coverage = transactor.interpreter().profiler().data().coverage();
+
+    assert_eq!(
+        coverage.lcov(&source_map),
+        "TN:\nSF:lib.sw\nDA:7,0\nLF:1\nLH:0\nend_of_record\n\
+         TN:\nSF:main.sw\nDA:1,1\nDA:2,0\nDA:3,1\nDA:4,1\nLF:4\nLH:3\nend_of_record\n"
+    );
+
+    let summary = coverage.summary(&source_map);
+    assert_eq!(
+        summary,
+        vec![
+            ContextCoverage {
+                contract: None,
+                instructions: 5,
+                executed: 4,
+                lines: 4,
+                lines_hit: 3,
+                uncovered: vec![SourceLocation::new("main.sw", 2, 5)],
+            },
+            ContextCoverage {
+                contract: Some(contract),
+                instructions: 1,
+                executed: 0,
+                lines: 1,
+                lines_hit: 0,
+                uncovered: vec![SourceLocation::new("lib.sw", 7, 1)],
+            },
+        ]
+    );
+    serde_json::to_string(&summary).expect("Serialization failed");
+}
+
+#[test]
+fn malformed_source_map_is_rejected() {
+    let contract = ContractId::from([1; 32]);
+    let source_map: SourceMap = [(
+        InstructionLocation::new(Some(contract), 12),
+        SourceLocation::new("main.sw", 1, 5),
+    )]
+    .into_iter()
+    .collect();
+    let json = serde_json::to_string(&source_map).expect("Serialization failed");
+    let key = format!("{contract}:12");
+    assert!(json.contains(&key));
+
+    for malformed in ["12x".to_owned(), format!("{contract}:"), "-1".to_owned()] {
+        let json = json.replace(&key, &malformed);
+        assert!(serde_json::from_str::<SourceMap>(&json).is_err());
+    }
+}
@@ -0,0 +1,126 @@
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::{
+    field::GasLimit,
+    Script,
+    TransactionFee,
+};
+use fuel_types::{
+    ContractId,
+    Immediate18,
+    Word,
+};
+
+use crate::{
+    checked_transaction::IntoChecked,
+    prelude::*,
+    script_with_data_offset,
+};
+
+const REQUIRED_GAS: Immediate18 = 50_000;
+
+/// Script calling `contract_id` with all its gas.
+fn call_script(test_context: &mut TestBuilder, contract_id: ContractId) -> Script {
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset as Immediate18),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.tx_offset()
+    );
+    let script_data: Vec<u8> = contract_id
+        .iter()
+        .copied()
+        .chain((0 as Word).to_be_bytes())
+        .chain((0 as Word).to_be_bytes())
+        .collect();
+    let max_gas = test_context.get_params().max_gas_per_tx;
+
+    test_context
+        .start_script(script, script_data)
+        .gas_limit(max_gas)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build()
+        .transaction()
+        .clone()
+}
+
+fn succeeds(test_context: &TestBuilder, tx: Script) -> bool {
+    let checked = tx
+        .into_checked_basic(test_context.get_block_height(), test_context.get_params())
+        .expect("Expected a valid transaction");
+    let mut transactor = Transactor::new(
+        test_context.get_storage().clone(),
+        *test_context.get_params(),
+        GasCosts::default(),
+    );
+    transactor.transact(checked);
+    !transactor
+        .state_transition()
+        .expect("Expected a state transition")
+        .should_revert()
+}
+
+#[test]
+fn estimate_gas_limit_accounts_for_forwarded_gas() {
+    let mut test_context = TestBuilder::new(2322u64);
+
+    // Revert unless the contract receives `REQUIRED_GAS`
+    let program = vec![
+        op::movi(0x10, REQUIRED_GAS),
+        op::lt(0x11, RegId::CGAS, 0x10),
+        op::jnzf(0x11, RegId::ZERO, 1),
+        op::ret(RegId::ONE),
+        op::rvrt(RegId::ONE),
+    ];
+    let contract_id = test_context.setup_contract(program, None, None).contract_id;
+
+    let mut tx = call_script(&mut test_context, contract_id);
+    let mut storage = test_context.get_storage().clone();
+    let estimate = Interpreter::estimate_gas_limit(
+        &mut tx,
+        &mut storage,
+        *test_context.get_params(),
+        GasCosts::default(),
+    )
+    .expect("Expected an estimate");
+
+    assert_eq!(*tx.gas_limit(), estimate.gas_limit());
+    assert!(estimate.gas_used() < REQUIRED_GAS as Word);
+    assert!(estimate.gas_limit() > REQUIRED_GAS as Word);
+    assert_eq!(
+        Some(*estimate.fee()),
+        TransactionFee::checked_from_tx(test_context.get_params(), &tx)
+    );
+
+    assert!(succeeds(&test_context, tx.clone()));
+    *tx.gas_limit_mut() -= 1;
+    assert!(!succeeds(&test_context, tx));
+}
+
+#[test]
+fn estimate_gas_limit_fails_for_reverting_script() {
+    let mut test_context = TestBuilder::new(2322u64);
+
+    let program = vec![op::rvrt(RegId::ONE)];
+    let contract_id = test_context.setup_contract(program, None, None).contract_id;
+
+    let mut tx = call_script(&mut test_context, contract_id);
+    let gas_limit = *tx.gas_limit();
+    let mut storage = test_context.get_storage().clone();
+    let result = Interpreter::estimate_gas_limit(
+        &mut tx,
+        &mut storage,
+        *test_context.get_params(),
+        GasCosts::default(),
+    );
+
+    assert_eq!(result, Err(InterpreterError::GasEstimationFailed));
+    assert_eq!(*tx.gas_limit(), gas_limit);
+}
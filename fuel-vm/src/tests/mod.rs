@@ -10,6 +10,7 @@ mod crypto;
 mod encoding;
 mod flow;
 mod gas_factor;
+mod gas_limit;
 mod jump_absolute;
 mod jump_relative;
 mod log;
@@ -18,7 +19,12 @@ mod metadata;
 mod outputs;
 mod predicate;
 mod profile_gas;
+mod read_write_set;
+mod schedule;
 mod serde_profile;
 mod spec;
+mod state_diff;
+mod state_overrides;
+mod trace;
 mod validation;
 mod wideint;
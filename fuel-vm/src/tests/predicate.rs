@@ -3,9 +3,11 @@ use fuel_asm::{
     GMArgs,
     GTFArgs,
     Instruction,
+    Opcode,
     RegId,
 };
 use fuel_tx::{
+    field::Inputs,
     ConsensusParameters,
     TransactionBuilder,
 };
@@ -18,6 +20,7 @@ use tokio_rayon::AsyncRayonHandle;
 
 use crate::{
     error::PredicateVerificationFailed,
+    interpreter::InitialBalances,
     prelude::*,
 };
 
@@ -725,3 +728,68 @@ async fn gas_used_by_predicates_more_than_limit() {
         CheckError::PredicateVerificationFailed
     );
 }
+
+#[tokio::test]
+async fn predicates_are_estimated_and_checked_in_parallel_with_the_policy() {
+    let predicate: Vec<u8> = vec![op::movi(0x10, 1), op::ret(0x10)].into_iter().collect();
+    let params = ConsensusParameters::default();
+    let owner = Input::predicate_owner(&predicate, &params.chain_id);
+    let mut tx = TransactionBuilder::script(vec![], vec![])
+        .gas_limit(1_000_000)
+        .add_input(Input::coin_predicate(
+            Default::default(),
+            owner,
+            1_000,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            0,
+            predicate,
+            vec![],
+        ))
+        .finalize();
+
+    let mut policy = OpcodePolicy::new();
+    policy.deny(PolicyScope::Predicate, Opcode::MOVI);
+    let gas_used = |tx: &Script| tx.inputs()[0].predicate_gas_used();
+
+    // The denied `movi` panics, so the `ret` isn't executed
+    let mut denied = tx.clone();
+    Interpreter::<PredicateStorage>::estimate_predicates_with_policy(
+        &mut denied,
+        InitialBalances::default(),
+        params,
+        GasCosts::default(),
+        policy,
+    )
+    .expect("Failed to estimate predicates");
+
+    let mut denied_async = tx.clone();
+    Interpreter::<PredicateStorage>::estimate_predicates_async_with_policy::<
+        _,
+        TokioWithRayon,
+    >(
+        &mut denied_async,
+        InitialBalances::default(),
+        params,
+        GasCosts::default(),
+        policy,
+    )
+    .await
+    .expect("Failed to estimate predicates");
+    assert_eq!(gas_used(&denied_async), gas_used(&denied));
+
+    tx.estimate_predicates(&params, &GasCosts::default())
+        .expect("Failed to estimate predicates");
+    assert!(gas_used(&denied) < gas_used(&tx));
+
+    let checked = tx
+        .into_checked_basic(Default::default(), &params)
+        .expect("failed to generate checked tx");
+    let checked = Interpreter::<PredicateStorage>::check_predicates_async_with_policy::<
+        _,
+        TokioWithRayon,
+    >(&checked, params, GasCosts::default(), policy)
+    .await;
+    assert!(checked.is_err());
+}
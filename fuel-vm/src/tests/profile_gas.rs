@@ -104,3 +104,95 @@ fn profile_gas() {
     assert!(items0[0] == items1[0] && items0[0] == items2[0]);
     assert!(items0[1] == items1[1] && items0[1] == items2[1]);
 }
+
+#[test]
+fn profile_gas_call_stacks() {
+    use fuel_types::Immediate18;
+    use fuel_vm::script_with_data_offset;
+
+    let mut test_context = TestBuilder::new(2322u64);
+
+    let contract = vec![
+        op::movi(0x10, 3),
+        op::subi(0x10, 0x10, 1),
+        op::jnei(RegId::ZERO, 0x10, 1),
+        op::ret(RegId::ONE),
+    ];
+    let contract_id = test_context
+        .setup_contract(contract, None, None)
+        .contract_id;
+
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset as Immediate18),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.tx_offset()
+    );
+    let script_data: Vec<u8> = contract_id
+        .iter()
+        .copied()
+        .chain((0 as Word).to_be_bytes())
+        .chain((0 as Word).to_be_bytes())
+        .collect();
+
+    let tx = test_context
+        .start_script(script, script_data)
+        .gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+
+    let mut vm = Interpreter::with_storage(
+        test_context.get_storage().clone(),
+        *test_context.get_params(),
+        GasCosts::default(),
+    );
+    vm.transact(tx).expect("failed to execute script");
+
+    let gas = vm.profiler().data().gas();
+
+    // Gas is attributed to the same instructions through both views
+    let stacks_total: u64 = gas.call_stacks().map(|(_, gas)| gas).sum();
+    assert_eq!(stacks_total, gas.values().sum::<u64>());
+    for (stack, used) in gas.call_stacks() {
+        assert!(used <= gas.get(stack.last().expect("Stacks are never empty")));
+    }
+
+    // Instructions of the contract are reached through the `call` of the script
+    let call = InstructionLocation::new(None, 4);
+    let decrement = InstructionLocation::new(Some(contract_id), 4);
+    let looped = gas.get_call_stack(&[call, decrement]);
+    assert!(looped > 0);
+    assert_eq!(looped, gas.get(&decrement));
+    assert!(gas.get_call_stack(&[call]) > 0);
+
+    let folded = gas.folded();
+    assert!(folded
+        .lines()
+        .any(|line| line == format!("script:4 {}", gas.get(&call))));
+    assert!(folded
+        .lines()
+        .any(|line| line == format!("script:4;{contract_id}:4 {looped}")));
+    assert_eq!(folded.lines().count(), gas.call_stacks().count());
+
+    // Only check the top level structure of the pprof profile
+    let pprof = gas.pprof();
+    let mut fields = vec![];
+    let mut cursor = pprof.as_slice();
+    while let Some((&key, rest)) = cursor.split_first() {
+        assert_eq!(key & 0b111, 2, "Every profile field is length-delimited");
+        let (&len, rest) = rest.split_first().expect("Missing field length");
+        assert!(len < 0x80, "Test fields fit in a single byte");
+        fields.push(key >> 3);
+        cursor = &rest[len as usize..];
+    }
+    let count = |field| fields.iter().filter(|f| **f == field).count();
+    assert_eq!(count(1), 1);
+    assert_eq!(count(2), gas.call_stacks().count());
+    assert_eq!(count(5), 2);
+    assert!(pprof.windows(3).any(|window| window == b"gas"));
+}
@@ -0,0 +1,210 @@
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::Script;
+use fuel_types::{
+    AssetId,
+    Bytes32,
+    ContractId,
+    Immediate18,
+    Word,
+};
+
+use crate::{
+    prelude::*,
+    script_with_data_offset,
+    storage::{
+        ReadWriteRecorder,
+        StateOverrides,
+        StorageTransaction,
+    },
+};
+
+/// Call `contract_id` from a script executed by `transactor`.
+fn call_contract<S>(
+    test_context: &mut TestBuilder,
+    transactor: &mut Transactor<S, Script>,
+    contract_id: ContractId,
+) -> StateTransition<Script>
+where
+    S: InterpreterStorage,
+{
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset as Immediate18),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.tx_offset()
+    );
+    let script_data: Vec<u8> = contract_id
+        .iter()
+        .copied()
+        .chain((0 as Word).to_be_bytes())
+        .chain((0 as Word).to_be_bytes())
+        .collect();
+
+    let tx = test_context
+        .start_script(script, script_data)
+        .gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+
+    transactor.transact(tx);
+    transactor
+        .to_owned_state_transition()
+        .expect("Expected a state transition")
+}
+
+#[test]
+fn read_write_set_records_contract_accesses() {
+    let mut test_context = TestBuilder::new(2322u64);
+
+    let program = vec![
+        op::movi(0x10, Bytes32::LEN as Immediate18),
+        op::aloc(0x10),
+        op::srw(0x11, 0x12, RegId::HP),
+        op::sww(RegId::HP, 0x12, RegId::ONE),
+        op::bal(0x13, RegId::HP, RegId::FP),
+        op::ret(RegId::ONE),
+    ];
+    let contract_id = test_context.setup_contract(program, None, None).contract_id;
+
+    let mut transactor = Transactor::new(
+        ReadWriteRecorder::new(test_context.get_storage().clone()),
+        *test_context.get_params(),
+        GasCosts::default(),
+    );
+    let state = call_contract(&mut test_context, &mut transactor, contract_id);
+    assert!(!state.should_revert());
+
+    let set = state.read_write_set().expect("Expected recorded accesses");
+    let slot = (&contract_id, &Bytes32::zeroed()).into();
+    let balance = (&contract_id, &AssetId::zeroed()).into();
+
+    assert!(set.contracts_raw_code().reads().contains(&contract_id));
+    assert!(set.contracts_raw_code().writes().is_empty());
+    assert!(set.contracts_info().is_empty());
+    assert_eq!(
+        set.contracts_state().reads().iter().collect::<Vec<_>>(),
+        vec![&slot]
+    );
+    assert_eq!(
+        set.contracts_state().writes().iter().collect::<Vec<_>>(),
+        vec![&slot]
+    );
+    assert!(set.contracts_assets().reads().contains(&balance));
+
+    let by_ref = transactor
+        .state_transition()
+        .and_then(|state| state.read_write_set().cloned());
+    assert_eq!(by_ref.as_ref(), Some(set));
+}
+
+#[test]
+fn read_write_set_is_reset_per_transaction() {
+    let mut test_context = TestBuilder::new(2322u64);
+
+    let reader = vec![
+        op::movi(0x10, Bytes32::LEN as Immediate18),
+        op::aloc(0x10),
+        op::srw(0x11, 0x12, RegId::HP),
+        op::ret(RegId::ONE),
+    ];
+    let writer = vec![
+        op::movi(0x10, Bytes32::LEN as Immediate18),
+        op::aloc(0x10),
+        op::sww(RegId::HP, 0x12, RegId::ONE),
+        op::ret(RegId::ONE),
+    ];
+    let reader = test_context.setup_contract(reader, None, None).contract_id;
+    let writer = test_context.setup_contract(writer, None, None).contract_id;
+
+    let mut transactor = Transactor::new(
+        ReadWriteRecorder::new(test_context.get_storage().clone()),
+        *test_context.get_params(),
+        GasCosts::default(),
+    );
+
+    let read = call_contract(&mut test_context, &mut transactor, reader);
+    let write = call_contract(&mut test_context, &mut transactor, writer);
+    let read_again = call_contract(&mut test_context, &mut transactor, reader);
+
+    let read = read.read_write_set().expect("Expected recorded accesses");
+    let write = write.read_write_set().expect("Expected recorded accesses");
+    let read_again = read_again
+        .read_write_set()
+        .expect("Expected recorded accesses");
+
+    assert!(!read.contracts_raw_code().reads().contains(&writer));
+    assert!(!write.contracts_raw_code().reads().contains(&reader));
+    assert_eq!(read, read_again);
+
+    // Different contracts never conflict, while the same slot does once written
+    assert!(!read.conflicts_with(write));
+    assert!(!read
+        .contracts_state()
+        .conflicts_with(read_again.contracts_state()));
+    assert!(write
+        .contracts_state()
+        .conflicts_with(write.contracts_state()));
+
+    // The forwarded coins are written to the balance of the called contract
+    assert!(read.conflicts_with(read_again));
+}
+
+#[test]
+fn read_write_set_requires_recorder() {
+    let mut test_context = TestBuilder::new(2322u64);
+
+    let program = vec![op::ret(RegId::ONE)];
+    let contract_id = test_context.setup_contract(program, None, None).contract_id;
+
+    let mut transactor = Transactor::new(
+        test_context.get_storage().clone(),
+        *test_context.get_params(),
+        GasCosts::default(),
+    );
+    let state = call_contract(&mut test_context, &mut transactor, contract_id);
+
+    assert!(state.read_write_set().is_none());
+}
+
+#[test]
+fn read_write_set_is_forwarded_by_wrappers() {
+    let mut test_context = TestBuilder::new(2322u64);
+
+    let program = vec![
+        op::movi(0x10, Bytes32::LEN as Immediate18),
+        op::aloc(0x10),
+        op::srw(0x11, 0x12, RegId::HP),
+        op::ret(RegId::ONE),
+    ];
+    let contract_id = test_context.setup_contract(program, None, None).contract_id;
+    let slot = (&contract_id, &Bytes32::zeroed()).into();
+
+    let recorder = ReadWriteRecorder::new(test_context.get_storage().clone());
+    let mut transactor = Transactor::new(
+        StateOverrides::new(recorder.clone()),
+        *test_context.get_params(),
+        GasCosts::default(),
+    );
+    let state = call_contract(&mut test_context, &mut transactor, contract_id);
+    let set = state.read_write_set().expect("Expected recorded accesses");
+    assert!(set.contracts_raw_code().reads().contains(&contract_id));
+    assert!(set.contracts_state().reads().contains(&slot));
+
+    let mut transactor = Transactor::new(
+        StorageTransaction::new(recorder),
+        *test_context.get_params(),
+        GasCosts::default(),
+    );
+    let state = call_contract(&mut test_context, &mut transactor, contract_id);
+    let set = state.read_write_set().expect("Expected recorded accesses");
+    assert!(set.contracts_raw_code().reads().contains(&contract_id));
+    assert!(set.contracts_state().reads().contains(&slot));
+}
@@ -0,0 +1,132 @@
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::{
+    ConsensusParameters,
+    Finalizable,
+    Receipt,
+    TransactionBuilder,
+};
+use fuel_types::{
+    BlockHeight,
+    ChainId,
+    Word,
+};
+
+use crate::{
+    checked_transaction::IntoChecked,
+    prelude::*,
+};
+
+fn schedule() -> Schedule {
+    let params = ConsensusParameters {
+        chain_id: ChainId::new(7),
+        ..ConsensusParameters::DEFAULT
+    };
+
+    let mut schedule =
+        Schedule::new(GasCostsValues::free(), ConsensusParameters::DEFAULT);
+    schedule.activate(10.into(), GasCostsValues::unit(), params);
+    schedule
+}
+
+/// Gas used by a script at `block_height`.
+fn script_gas_used(schedule: &Schedule, block_height: BlockHeight) -> Word {
+    let mut storage = MemoryStorage::default();
+    storage.set_block_height(block_height);
+    let mut client = MemoryClient::with_schedule(storage, schedule.clone());
+
+    let tx = TransactionBuilder::script(
+        vec![op::noop(), op::ret(RegId::ONE)].into_iter().collect(),
+        vec![],
+    )
+    .gas_limit(1_000_000)
+    .with_params(*schedule.at(block_height).params())
+    .add_random_fee_input()
+    .finalize()
+    .into_checked_with_schedule(block_height, schedule)
+    .expect("Expected a valid transaction");
+
+    match client.transact(tx).last() {
+        Some(Receipt::ScriptResult { gas_used, .. }) => *gas_used,
+        receipt => panic!("Expected a script result, got {receipt:?}"),
+    }
+}
+
+#[test]
+fn schedule_resolves_the_entry_in_force() {
+    let schedule = schedule();
+
+    assert_eq!(schedule.entries().len(), 2);
+    assert_eq!(schedule.at(0.into()).activation(), 0.into());
+    assert_eq!(schedule.at(9.into()).activation(), 0.into());
+    assert_eq!(schedule.at(10.into()).activation(), 10.into());
+    assert_eq!(schedule.at(u32::MAX.into()).activation(), 10.into());
+    assert_eq!(schedule.at(10.into()).params().chain_id, ChainId::new(7));
+}
+
+#[test]
+fn activation_replaces_the_entry_at_the_same_height() {
+    let mut schedule = schedule();
+    schedule.activate(
+        10.into(),
+        GasCostsValues::free(),
+        ConsensusParameters::DEFAULT,
+    );
+
+    assert_eq!(schedule.entries().len(), 2);
+    assert_eq!(
+        schedule.at(10.into()).gas_costs(),
+        &GasCosts::new(GasCostsValues::free())
+    );
+}
+
+#[test]
+fn transactions_use_the_schedule_of_the_block_height() {
+    let schedule = schedule();
+
+    assert_eq!(script_gas_used(&schedule, 1.into()), 0);
+    assert!(script_gas_used(&schedule, 10.into()) > 0);
+}
+
+#[test]
+fn predicates_are_checked_with_the_schedule_of_the_block_height() {
+    // The predicate is free before the activation at height 20
+    let mut schedule =
+        Schedule::new(GasCostsValues::free(), ConsensusParameters::DEFAULT);
+    schedule.activate(
+        20.into(),
+        GasCostsValues::unit(),
+        ConsensusParameters::DEFAULT,
+    );
+
+    let tx = || {
+        let predicate = vec![op::ret(RegId::ONE)].into_iter().collect::<Vec<u8>>();
+        let owner = Input::predicate_owner(&predicate, &ChainId::default());
+        TransactionBuilder::script(
+            vec![op::ret(RegId::ONE)].into_iter().collect(),
+            vec![],
+        )
+        .gas_limit(1_000_000)
+        .add_input(Input::coin_predicate(
+            Default::default(),
+            owner,
+            1_000,
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            0,
+            predicate,
+            vec![],
+        ))
+        .finalize()
+    };
+
+    assert!(tx()
+        .into_checked_with_schedule(19.into(), &schedule)
+        .is_ok());
+    assert!(tx()
+        .into_checked_with_schedule(20.into(), &schedule)
+        .is_err());
+}
@@ -0,0 +1,291 @@
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::{
+    field::Outputs,
+    Create,
+    Finalizable,
+    Output,
+    Script,
+    StorageSlot,
+    TransactionBuilder,
+    UtxoId,
+};
+use fuel_types::{
+    AssetId,
+    Bytes32,
+    ContractId,
+    Immediate18,
+    Word,
+};
+
+use crate::{
+    checked_transaction::IntoChecked,
+    prelude::*,
+    script_with_data_offset,
+    state::{
+        BalanceChange,
+        SlotChange,
+    },
+    storage::{
+        ChangeRecorder,
+        StateOverrides,
+        StorageTransaction,
+    },
+};
+
+/// Call `contract_id`, with a variable output, from a script executed by
+/// `transactor`.
+fn call_contract<S>(
+    test_context: &mut TestBuilder,
+    transactor: &mut Transactor<S, Script>,
+    contract_id: ContractId,
+    revert: bool,
+) -> StateTransition<Script>
+where
+    S: InterpreterStorage,
+{
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset as Immediate18),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            if revert {
+                op::rvrt(RegId::ONE)
+            } else {
+                op::ret(RegId::ONE)
+            },
+        ],
+        test_context.tx_offset()
+    );
+    let script_data: Vec<u8> = contract_id
+        .iter()
+        .copied()
+        .chain((0 as Word).to_be_bytes())
+        .chain((0 as Word).to_be_bytes())
+        .collect();
+
+    let tx = test_context
+        .start_script(script, script_data)
+        .gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .variable_output(AssetId::zeroed())
+        .build();
+
+    transactor.transact(tx);
+    transactor
+        .to_owned_state_transition()
+        .expect("Expected a state transition")
+}
+
+/// Contract writing 7 to the slot zero and transferring 10 of the base asset
+/// to the variable output.
+fn setup_contract(test_context: &mut TestBuilder) -> ContractId {
+    let program = vec![
+        op::movi(0x10, Bytes32::LEN as Immediate18),
+        op::aloc(0x10),
+        op::movi(0x11, 7),
+        op::sww(RegId::HP, 0x12, 0x11),
+        op::movi(0x13, 1),
+        op::movi(0x14, 10),
+        op::tro(RegId::HP, 0x13, 0x14, RegId::HP),
+        op::ret(RegId::ONE),
+    ];
+    test_context
+        .setup_contract(program, Some((AssetId::zeroed(), 100)), None)
+        .contract_id
+}
+
+fn word_slot(value: Word) -> Bytes32 {
+    let mut slot = Bytes32::zeroed();
+    slot[..8].copy_from_slice(&value.to_be_bytes());
+    slot
+}
+
+#[test]
+fn state_diff_lists_the_changes_of_a_call() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract_id = setup_contract(&mut test_context);
+
+    let mut transactor = Transactor::new(
+        ChangeRecorder::new(test_context.get_storage().clone()),
+        *test_context.get_params(),
+        GasCosts::default(),
+    );
+    let state = call_contract(&mut test_context, &mut transactor, contract_id, false);
+    assert!(!state.should_revert());
+
+    let diff = state.state_diff().expect("Expected a state diff");
+    assert!(diff.contracts_created.is_empty());
+    assert_eq!(
+        diff.state_changes,
+        vec![SlotChange {
+            contract_id,
+            key: Bytes32::zeroed(),
+            old: None,
+            new: Some(word_slot(7)),
+        }]
+    );
+    assert_eq!(
+        diff.balance_changes,
+        vec![BalanceChange {
+            contract_id,
+            asset_id: AssetId::zeroed(),
+            old: 100,
+            new: 90,
+        }]
+    );
+
+    let index = state
+        .tx()
+        .outputs()
+        .iter()
+        .position(|output| matches!(output, Output::Variable { .. }))
+        .expect("Expected a variable output") as u8;
+    assert_eq!(diff.variable_outputs.len(), 1);
+    assert_eq!(diff.variable_outputs[0].index, index);
+    assert_eq!(diff.variable_outputs[0].amount, 10);
+
+    let tx_id = state.tx().id(&test_context.get_params().chain_id);
+    assert!(diff
+        .coins
+        .iter()
+        .any(|coin| coin.utxo_id == UtxoId::new(tx_id, index) && coin.amount == 10));
+    assert!(diff.messages.is_empty());
+
+    let by_ref = transactor
+        .state_transition()
+        .and_then(|state| state.state_diff().cloned());
+    assert_eq!(by_ref.as_ref(), Some(diff));
+
+    let json = diff.to_json().expect("Failed to encode");
+    let decoded: StateDiff = serde_json::from_str(&json).expect("Failed to decode");
+    assert_eq!(&decoded, diff);
+}
+
+#[test]
+fn state_diff_omits_storage_changes_of_reverted_transaction() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract_id = setup_contract(&mut test_context);
+
+    let mut transactor = Transactor::new(
+        ChangeRecorder::new(test_context.get_storage().clone()),
+        *test_context.get_params(),
+        GasCosts::default(),
+    );
+    let state = call_contract(&mut test_context, &mut transactor, contract_id, true);
+    assert!(state.should_revert());
+
+    let diff = state.state_diff().expect("Expected a state diff");
+    assert!(diff.state_changes.is_empty());
+    assert!(diff.balance_changes.is_empty());
+    // The variable output is reset by the revert
+    assert_eq!(diff.variable_outputs.len(), 1);
+    assert_eq!(diff.variable_outputs[0].amount, 0);
+}
+
+#[test]
+fn state_diff_lists_created_contract() {
+    let test_context = TestBuilder::new(2322u64);
+    let params = *test_context.get_params();
+
+    let program: Vec<u8> = [op::ret(RegId::ONE)].into_iter().collect();
+    let slots = vec![StorageSlot::new(Bytes32::zeroed(), word_slot(1))];
+    let salt = Default::default();
+    let contract = Contract::from(program.as_slice());
+    let state_root = Contract::initial_state_root(slots.iter());
+    let contract_id = contract.id(&salt, &contract.root(), &state_root);
+
+    let tx = TransactionBuilder::create(program.into(), salt, slots)
+        .gas_limit(1_000_000)
+        .add_random_fee_input()
+        .add_output(Output::contract_created(contract_id, state_root))
+        .finalize()
+        .into_checked(Default::default(), &params, &GasCosts::default())
+        .expect("failed to check tx");
+
+    let mut transactor = Transactor::<_, Create>::new(
+        ChangeRecorder::new(MemoryStorage::default()),
+        params,
+        GasCosts::default(),
+    );
+    transactor.transact(tx);
+    let state = transactor
+        .to_owned_state_transition()
+        .expect("Expected a state transition");
+
+    let diff = state.state_diff().expect("Expected a state diff");
+    assert_eq!(diff.contracts_created, vec![contract_id]);
+    assert_eq!(
+        diff.state_changes,
+        vec![SlotChange {
+            contract_id,
+            key: Bytes32::zeroed(),
+            old: None,
+            new: Some(word_slot(1)),
+        }]
+    );
+}
+
+#[test]
+fn state_diff_requires_recorder() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract_id = setup_contract(&mut test_context);
+
+    let mut transactor = Transactor::new(
+        test_context.get_storage().clone(),
+        *test_context.get_params(),
+        GasCosts::default(),
+    );
+    let state = call_contract(&mut test_context, &mut transactor, contract_id, false);
+
+    assert!(state.state_diff().is_none());
+}
+
+#[test]
+fn state_diff_is_forwarded_by_wrappers() {
+    let mut test_context = TestBuilder::new(2322u64);
+    let contract_id = setup_contract(&mut test_context);
+    let recorder = ChangeRecorder::new(test_context.get_storage().clone());
+
+    // The writes are buffered by the wrappers and don't reach the recorder
+    let mut transactor = Transactor::new(
+        StateOverrides::new(recorder.clone()),
+        *test_context.get_params(),
+        GasCosts::default(),
+    );
+    let state = call_contract(&mut test_context, &mut transactor, contract_id, false);
+    let diff = state.state_diff().expect("Expected a state diff");
+    assert!(diff.state_changes.is_empty());
+    assert_eq!(diff.variable_outputs.len(), 1);
+    assert_eq!(diff.variable_outputs[0].amount, 10);
+
+    let mut transactor = Transactor::new(
+        StorageTransaction::new(recorder),
+        *test_context.get_params(),
+        GasCosts::default(),
+    );
+    let state = call_contract(&mut test_context, &mut transactor, contract_id, false);
+    let diff = state.state_diff().expect("Expected a state diff");
+    assert!(diff.balance_changes.is_empty());
+    assert_eq!(diff.variable_outputs.len(), 1);
+    assert_eq!(diff.variable_outputs[0].amount, 10);
+
+    // Once committed, the changes reach the recorder
+    let mut storage = transactor.as_mut().clone();
+    storage.commit().expect("Failed to commit");
+    let changes = storage
+        .into_inner()
+        .take_changes()
+        .expect("Expected recorded changes");
+    assert_eq!(
+        changes
+            .after()
+            .contracts_state()
+            .get(&(&contract_id, &Bytes32::zeroed()).into()),
+        Some(&Some(word_slot(7)))
+    );
+}
@@ -0,0 +1,212 @@
+use fuel_asm::{
+    op,
+    RegId,
+};
+use fuel_tx::{
+    Contract,
+    Receipt,
+    Script,
+};
+use fuel_types::{
+    AssetId,
+    BlockHeight,
+    Bytes32,
+    ContractId,
+    Immediate18,
+    Word,
+};
+
+use crate::{
+    prelude::*,
+    script_with_data_offset,
+    storage::{
+        ContractsState,
+        StateOverrides,
+    },
+};
+
+/// Call `contract_id` from a script executed by `transactor`.
+fn call_contract<S>(
+    test_context: &mut TestBuilder,
+    transactor: &mut Transactor<S, Script>,
+    contract_id: ContractId,
+) -> StateTransition<Script>
+where
+    S: InterpreterStorage,
+{
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset as Immediate18),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.tx_offset()
+    );
+    let script_data: Vec<u8> = contract_id
+        .iter()
+        .copied()
+        .chain((0 as Word).to_be_bytes())
+        .chain((0 as Word).to_be_bytes())
+        .collect();
+
+    let tx = test_context
+        .start_script(script, script_data)
+        .gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+
+    transactor.transact(tx);
+    transactor
+        .to_owned_state_transition()
+        .expect("Expected a state transition")
+}
+
+fn logs(state: &StateTransition<Script>) -> Vec<(Word, Word)> {
+    state
+        .receipts()
+        .iter()
+        .filter_map(|receipt| match receipt {
+            Receipt::Log { ra, rb, .. } => Some((*ra, *rb)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn state_overrides_replace_contract_state_and_balances() {
+    let mut test_context = TestBuilder::new(2322u64);
+
+    let program = vec![
+        op::movi(0x10, Bytes32::LEN as Immediate18),
+        op::aloc(0x10),
+        op::srw(0x11, 0x12, RegId::HP),
+        op::bal(0x13, RegId::HP, RegId::FP),
+        op::log(0x11, 0x13, 0x00, 0x00),
+        op::sww(RegId::HP, 0x12, RegId::ONE),
+        op::ret(RegId::ONE),
+    ];
+    let contract_id = test_context.setup_contract(program, None, None).contract_id;
+
+    let mut value = Bytes32::zeroed();
+    value[..8].copy_from_slice(&7u64.to_be_bytes());
+
+    let mut storage = test_context.get_storage().clone();
+    let mut overrides = StateOverrides::new(&mut storage);
+    overrides
+        .with_contract_state(&contract_id, &Bytes32::zeroed(), value)
+        .with_contract_balance(&contract_id, &AssetId::zeroed(), 100);
+
+    let mut transactor =
+        Transactor::new(overrides, *test_context.get_params(), GasCosts::default());
+    let state = call_contract(&mut test_context, &mut transactor, contract_id);
+    assert!(!state.should_revert());
+    assert_eq!(logs(&state), vec![(7, 100)]);
+
+    let slot = (&contract_id, &Bytes32::zeroed()).into();
+    let overrides: &StateOverrides<&mut MemoryStorage> = transactor.as_ref();
+    let written = StorageAsRef::storage::<ContractsState>(overrides)
+        .get(&slot)
+        .unwrap()
+        .map(|value| value.into_owned());
+    let mut expected = Bytes32::zeroed();
+    expected[..8].copy_from_slice(&1u64.to_be_bytes());
+    assert_eq!(written, Some(expected));
+
+    drop(transactor);
+    assert!(!storage
+        .storage::<ContractsState>()
+        .contains_key(&slot)
+        .unwrap());
+}
+
+#[test]
+fn state_overrides_replace_contract_code() {
+    let mut test_context = TestBuilder::new(2322u64);
+
+    let program = vec![op::log(RegId::ONE, 0x00, 0x00, 0x00), op::ret(RegId::ONE)];
+    let contract_id = test_context.setup_contract(program, None, None).contract_id;
+
+    let replacement: Contract = vec![
+        op::movi(0x10, 42),
+        op::log(0x10, 0x00, 0x00, 0x00),
+        op::ret(RegId::ONE),
+    ]
+    .into_iter()
+    .collect::<Vec<u8>>()
+    .into();
+
+    let mut overrides = StateOverrides::new(test_context.get_storage().clone());
+    overrides
+        .with_contract_code(&contract_id, replacement.clone())
+        .unwrap();
+    let mut transactor =
+        Transactor::new(overrides, *test_context.get_params(), GasCosts::default());
+    let state = call_contract(&mut test_context, &mut transactor, contract_id);
+    assert!(!state.should_revert());
+    assert_eq!(logs(&state), vec![(42, 0)]);
+
+    let overrides: &StateOverrides<MemoryStorage> = transactor.as_ref();
+    let root = overrides.storage_contract_root(&contract_id).unwrap();
+    assert_eq!(root.map(|root| root.1), Some(replacement.root()));
+
+    let mut transactor = Transactor::new(
+        test_context.get_storage().clone(),
+        *test_context.get_params(),
+        GasCosts::default(),
+    );
+    let state = call_contract(&mut test_context, &mut transactor, contract_id);
+    assert_eq!(logs(&state), vec![(1, 0)]);
+}
+
+#[test]
+fn state_overrides_replace_block_data() {
+    let mut test_context = TestBuilder::new(2322u64);
+
+    let height = BlockHeight::new(1000);
+    let previous = BlockHeight::new(999);
+    let coinbase = Address::from([0xcb; 32]);
+
+    let mut overrides = StateOverrides::new(test_context.get_storage().clone());
+    overrides
+        .with_block_height(height)
+        .with_timestamp(previous, 1234)
+        .with_block_hash(previous, Bytes32::from([0xab; 32]))
+        .with_coinbase(coinbase);
+
+    assert_eq!(overrides.block_height().unwrap(), height);
+    assert_eq!(overrides.timestamp(previous).unwrap(), 1234);
+    assert_eq!(overrides.block_hash(previous).unwrap(), [0xab; 32].into());
+    assert_eq!(overrides.coinbase().unwrap(), coinbase);
+
+    let underlying = test_context.get_storage();
+    assert_eq!(
+        overrides.timestamp(height).unwrap(),
+        underlying.timestamp(height).unwrap()
+    );
+
+    let script = vec![
+        op::bhei(0x10),
+        op::subi(0x11, 0x10, 1),
+        op::time(0x12, 0x11),
+        op::log(0x10, 0x12, 0x00, 0x00),
+        op::ret(RegId::ONE),
+    ]
+    .into_iter()
+    .collect();
+    let tx = test_context
+        .start_script(script, vec![])
+        .gas_limit(1_000_000)
+        .fee_input()
+        .build();
+
+    let mut transactor =
+        Transactor::new(overrides, *test_context.get_params(), GasCosts::default());
+    transactor.transact(tx);
+    let state = transactor
+        .to_owned_state_transition()
+        .expect("Expected a state transition");
+    assert_eq!(logs(&state), vec![(*height as Word, 1234)]);
+}
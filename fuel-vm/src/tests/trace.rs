@@ -0,0 +1,351 @@
+use core::iter;
+
+use fuel_asm::{
+    op,
+    Instruction,
+    PanicReason,
+    RawInstruction,
+    RegId,
+};
+use fuel_tx::{
+    ConsensusParameters,
+    TransactionBuilder,
+};
+use fuel_types::{
+    Bytes32,
+    Immediate18,
+    Word,
+};
+
+use crate::{
+    consts::*,
+    prelude::*,
+    script_with_data_offset,
+    tracer::{
+        StorageAccessKind,
+        StorageEntry,
+    },
+};
+
+fn trace_script(script: Vec<Instruction>) -> Interpreter<MemoryStorage, Script> {
+    trace_bytecode(script.into_iter().collect())
+}
+
+fn trace_bytecode(script: Vec<u8>) -> Interpreter<MemoryStorage, Script> {
+    let mut vm = Interpreter::with_memory_storage();
+    vm.set_tracing(true);
+
+    let tx = TransactionBuilder::script(script, vec![])
+        .gas_limit(1_000_000)
+        .add_random_fee_input()
+        .finalize()
+        .into_checked(
+            Default::default(),
+            &ConsensusParameters::default(),
+            vm.gas_costs(),
+        )
+        .expect("failed to generate checked tx");
+
+    vm.transact(tx).expect("failed to execute script");
+
+    vm
+}
+
+#[test]
+fn tracer_records_every_instruction() {
+    let script = vec![
+        op::movi(0x10, 8),
+        op::aloc(0x10),
+        op::sw(RegId::HP, RegId::ONE, 0),
+        op::ret(RegId::ONE),
+    ];
+    let vm = trace_script(script.clone());
+    let trace = vm.tracer().entries();
+
+    let instructions: Option<Vec<Instruction>> =
+        trace.iter().map(TraceEntry::instruction).collect();
+    assert_eq!(instructions, Some(script));
+
+    let offsets: Vec<_> = trace.iter().map(|e| e.location().offset()).collect();
+    assert_eq!(offsets, vec![0, 4, 8, 12]);
+    assert!(trace.iter().all(|e| e.location().context().is_none()));
+    assert!(trace.iter().all(|e| e.gas_before() > e.gas_after()));
+    assert!(trace.iter().all(|e| e.panic_reason().is_none()));
+
+    let movi = &trace[0];
+    assert!(movi.registers().contains(&RegisterChange::new(0x10, 0, 8)));
+    assert!(movi.memory_writes().is_empty());
+
+    let sw = &trace[2];
+    assert_eq!(
+        sw.memory_writes(),
+        &[MemoryWrite::new(
+            VM_MAX_RAM - 8,
+            1u64.to_be_bytes().to_vec()
+        )]
+    );
+}
+
+#[test]
+fn tracer_is_disabled_by_default() {
+    let mut vm = Interpreter::with_memory_storage();
+
+    let tx = TransactionBuilder::script(
+        [op::noop(), op::ret(RegId::ONE)].into_iter().collect(),
+        vec![],
+    )
+    .gas_limit(1_000_000)
+    .add_random_fee_input()
+    .finalize()
+    .into_checked(
+        Default::default(),
+        &ConsensusParameters::default(),
+        vm.gas_costs(),
+    )
+    .expect("failed to generate checked tx");
+
+    vm.transact(tx).expect("failed to execute script");
+
+    assert!(vm.tracer().entries().is_empty());
+}
+
+#[test]
+fn tracer_records_panic_reason() {
+    let vm = trace_script(vec![
+        op::movi(0x10, 1),
+        op::div(0x10, 0x10, RegId::ZERO),
+        op::ret(RegId::ONE),
+    ]);
+    let trace = vm.tracer().entries();
+
+    assert_eq!(trace.len(), 2);
+    assert_eq!(trace[1].panic_reason(), Some(PanicReason::ArithmeticError));
+}
+
+#[test]
+fn tracer_records_storage_accesses() {
+    let mut test_context = TestBuilder::new(2322u64);
+
+    let program = vec![
+        op::movi(0x10, Bytes32::LEN as Immediate18),
+        op::aloc(0x10),
+        op::movi(0x11, 42),
+        op::sww(RegId::HP, 0x12, 0x11),
+        op::srw(0x13, 0x14, RegId::HP),
+        op::ret(RegId::ONE),
+    ];
+    let contract_id = test_context.setup_contract(program, None, None).contract_id;
+
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset as Immediate18),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.tx_offset()
+    );
+    let script_data: Vec<u8> = contract_id
+        .iter()
+        .copied()
+        .chain((0 as Word).to_be_bytes())
+        .chain((0 as Word).to_be_bytes())
+        .collect();
+
+    let tx = test_context
+        .start_script(script, script_data)
+        .gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+
+    let mut vm = Interpreter::with_storage(
+        test_context.get_storage().clone(),
+        *test_context.get_params(),
+        GasCosts::default(),
+    );
+    vm.set_tracing(true);
+    vm.transact(tx).expect("failed to execute script");
+
+    let mut value = Bytes32::zeroed();
+    value[..WORD_SIZE].copy_from_slice(&42u64.to_be_bytes());
+
+    let accesses: Vec<_> = vm
+        .tracer()
+        .entries()
+        .iter()
+        .flat_map(|e| e.storage().iter().copied())
+        .filter(|access| matches!(access.entry(), StorageEntry::State { .. }))
+        .collect();
+    assert_eq!(
+        accesses,
+        vec![
+            StorageAccess::new(
+                StorageAccessKind::Write,
+                contract_id,
+                StorageEntry::State {
+                    key: Bytes32::zeroed(),
+                    value: Some(value)
+                }
+            ),
+            StorageAccess::new(
+                StorageAccessKind::Read,
+                contract_id,
+                StorageEntry::State {
+                    key: Bytes32::zeroed(),
+                    value: Some(value)
+                }
+            ),
+        ]
+    );
+
+    let contract_entries = vm
+        .tracer()
+        .entries()
+        .iter()
+        .filter(|e| e.location().context() == Some(contract_id))
+        .count();
+    assert_eq!(contract_entries, 6);
+}
+
+#[test]
+fn tracer_records_balance_and_code_accesses() {
+    let mut test_context = TestBuilder::new(2322u64);
+
+    let program = vec![
+        op::movi(0x10, AssetId::LEN as Immediate18),
+        op::aloc(0x10),
+        op::bal(0x11, RegId::HP, RegId::FP),
+        op::csiz(0x12, RegId::FP),
+        op::ret(RegId::ONE),
+    ];
+    let contract_id = test_context
+        .setup_contract(program, Some((AssetId::zeroed(), 100)), None)
+        .contract_id;
+    let code_len = test_context
+        .get_storage()
+        .storage_contract_size(&contract_id)
+        .expect("failed to read the contract")
+        .map(|len| len as Word);
+
+    let (script, _) = script_with_data_offset!(
+        data_offset,
+        vec![
+            op::movi(0x10, data_offset as Immediate18),
+            op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+            op::ret(RegId::ONE),
+        ],
+        test_context.tx_offset()
+    );
+    let script_data: Vec<u8> = contract_id
+        .iter()
+        .copied()
+        .chain((0 as Word).to_be_bytes())
+        .chain((0 as Word).to_be_bytes())
+        .collect();
+
+    let tx = test_context
+        .start_script(script, script_data)
+        .gas_limit(1_000_000)
+        .contract_input(contract_id)
+        .fee_input()
+        .contract_output(&contract_id)
+        .build();
+
+    let mut vm = Interpreter::with_storage(
+        test_context.get_storage().clone(),
+        *test_context.get_params(),
+        GasCosts::default(),
+    );
+    vm.set_tracing(true);
+    vm.transact(tx).expect("failed to execute script");
+
+    let call = vm
+        .tracer()
+        .entries()
+        .iter()
+        .find(|e| matches!(e.instruction(), Some(Instruction::CALL(_))))
+        .expect("expected a call entry");
+    assert!(call.storage().contains(&StorageAccess::new(
+        StorageAccessKind::Read,
+        contract_id,
+        StorageEntry::Code { len: code_len }
+    )));
+    assert!(call.storage().iter().any(|access| {
+        access.kind() == StorageAccessKind::Write
+            && access.contract() == &contract_id
+            && matches!(access.entry(), StorageEntry::Balance { .. })
+    }));
+
+    let accesses: Vec<_> = vm
+        .tracer()
+        .entries()
+        .iter()
+        .filter(|e| e.location().context() == Some(contract_id))
+        .flat_map(|e| e.storage().iter().copied())
+        .collect();
+    assert_eq!(
+        accesses,
+        vec![
+            StorageAccess::new(
+                StorageAccessKind::Read,
+                contract_id,
+                StorageEntry::Balance {
+                    asset_id: AssetId::zeroed(),
+                    value: Some(100)
+                }
+            ),
+            StorageAccess::new(
+                StorageAccessKind::Read,
+                contract_id,
+                StorageEntry::Code { len: code_len }
+            ),
+        ]
+    );
+}
+
+#[test]
+fn tracer_records_invalid_instructions() {
+    let invalid: RawInstruction = 0xff_00_00_00;
+    assert!(Instruction::try_from(invalid).is_err());
+
+    let script: Vec<u8> = iter::once(op::movi(0x10, 8))
+        .flat_map(Instruction::to_bytes)
+        .chain(invalid.to_be_bytes())
+        .collect();
+    let vm = trace_bytecode(script);
+    let trace = vm.tracer().entries();
+    assert_eq!(trace.len(), 2);
+
+    let entry = &trace[1];
+    assert_eq!(entry.location().offset(), 4);
+    assert_eq!(entry.raw_instruction(), invalid);
+    assert_eq!(entry.instruction(), None);
+    assert_eq!(entry.panic_reason(), Some(PanicReason::ErrorFlag));
+    assert!(entry.gas_before() > 0);
+    assert!(entry.storage().is_empty());
+}
+
+#[test]
+fn tracer_exports_json_lines() {
+    let vm = trace_script(vec![
+        op::movi(0x10, 8),
+        op::aloc(0x10),
+        op::sw(RegId::HP, RegId::ONE, 0),
+        op::ret(RegId::ONE),
+    ]);
+
+    let mut buffer = Vec::new();
+    vm.tracer()
+        .write_json_lines(&mut buffer)
+        .expect("failed to export trace");
+
+    let decoded: Vec<TraceEntry> = String::from_utf8(buffer)
+        .expect("trace is not valid utf-8")
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("invalid trace entry"))
+        .collect();
+
+    assert_eq!(decoded, vm.tracer().entries());
+}
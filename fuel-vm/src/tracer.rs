@@ -0,0 +1,300 @@
+//! Execution tracer, records every instruction executed by the VM
+
+use std::io;
+
+use fuel_asm::{
+    Instruction,
+    PanicReason,
+    RawInstruction,
+    RegisterId,
+};
+use fuel_types::{
+    AssetId,
+    Bytes32,
+    ContractId,
+    Word,
+};
+
+use crate::constraints::InstructionLocation;
+
+/// A register modified by an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegisterChange {
+    register: RegisterId,
+    before: Word,
+    after: Word,
+}
+
+impl RegisterChange {
+    /// Create a new register change.
+    pub const fn new(register: RegisterId, before: Word, after: Word) -> Self {
+        Self {
+            register,
+            before,
+            after,
+        }
+    }
+
+    /// Modified register.
+    pub const fn register(&self) -> RegisterId {
+        self.register
+    }
+
+    /// Value of the register before the instruction.
+    pub const fn before(&self) -> Word {
+        self.before
+    }
+
+    /// Value of the register after the instruction.
+    pub const fn after(&self) -> Word {
+        self.after
+    }
+}
+
+/// A memory range written by an instruction.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryWrite {
+    address: Word,
+    bytes: Vec<u8>,
+}
+
+impl MemoryWrite {
+    /// Create a new memory write.
+    pub const fn new(address: Word, bytes: Vec<u8>) -> Self {
+        Self { address, bytes }
+    }
+
+    /// Start address of the write.
+    pub const fn address(&self) -> Word {
+        self.address
+    }
+
+    /// Contents of the range after the instruction.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+/// Kind of access performed on a storage entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StorageAccessKind {
+    /// The entry was read.
+    Read,
+    /// The entry was written or cleared.
+    Write,
+}
+
+/// A contract storage entry, with its value after the instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StorageEntry {
+    /// State slot, `None` if it is unset.
+    State {
+        /// Key of the slot.
+        key: Bytes32,
+        /// Value of the slot.
+        value: Option<Bytes32>,
+    },
+    /// Balance of an asset, `None` if it is unset.
+    Balance {
+        /// Asset of the balance.
+        asset_id: AssetId,
+        /// Amount of the asset.
+        value: Option<Word>,
+    },
+    /// Bytecode, `None` if the contract doesn't exist.
+    Code {
+        /// Length of the bytecode, in bytes.
+        len: Option<Word>,
+    },
+    /// Code root, `None` if the contract doesn't exist.
+    CodeRoot {
+        /// Merkle root of the bytecode.
+        root: Option<Bytes32>,
+    },
+}
+
+/// A contract storage entry accessed by an instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StorageAccess {
+    kind: StorageAccessKind,
+    contract: ContractId,
+    entry: StorageEntry,
+}
+
+impl StorageAccess {
+    /// Create a new storage access.
+    pub const fn new(
+        kind: StorageAccessKind,
+        contract: ContractId,
+        entry: StorageEntry,
+    ) -> Self {
+        Self {
+            kind,
+            contract,
+            entry,
+        }
+    }
+
+    /// Kind of the access.
+    pub const fn kind(&self) -> StorageAccessKind {
+        self.kind
+    }
+
+    /// Contract owning the entry.
+    pub const fn contract(&self) -> &ContractId {
+        &self.contract
+    }
+
+    /// Accessed entry.
+    pub const fn entry(&self) -> &StorageEntry {
+        &self.entry
+    }
+}
+
+/// A single executed instruction, with its effects on the VM state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TraceEntry {
+    location: InstructionLocation,
+    raw_instruction: RawInstruction,
+    instruction: Option<Instruction>,
+    registers: Vec<RegisterChange>,
+    memory_writes: Vec<MemoryWrite>,
+    gas_before: Word,
+    gas_after: Word,
+    storage: Vec<StorageAccess>,
+    panic_reason: Option<PanicReason>,
+}
+
+impl TraceEntry {
+    /// Create a new trace entry, decoding `raw_instruction`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        location: InstructionLocation,
+        raw_instruction: RawInstruction,
+        registers: Vec<RegisterChange>,
+        memory_writes: Vec<MemoryWrite>,
+        gas_before: Word,
+        gas_after: Word,
+        storage: Vec<StorageAccess>,
+        panic_reason: Option<PanicReason>,
+    ) -> Self {
+        Self {
+            location,
+            raw_instruction,
+            instruction: Instruction::try_from(raw_instruction).ok(),
+            registers,
+            memory_writes,
+            gas_before,
+            gas_after,
+            storage,
+            panic_reason,
+        }
+    }
+
+    /// Location of the instruction.
+    pub const fn location(&self) -> InstructionLocation {
+        self.location
+    }
+
+    /// Raw instruction, as read from memory.
+    pub const fn raw_instruction(&self) -> RawInstruction {
+        self.raw_instruction
+    }
+
+    /// Decoded instruction, `None` if the raw instruction is invalid.
+    pub const fn instruction(&self) -> Option<Instruction> {
+        self.instruction
+    }
+
+    /// Registers changed by the instruction.
+    pub fn registers(&self) -> &[RegisterChange] {
+        &self.registers
+    }
+
+    /// Memory written by the instruction, in write order.
+    pub fn memory_writes(&self) -> &[MemoryWrite] {
+        &self.memory_writes
+    }
+
+    /// Remaining global gas before the instruction.
+    pub const fn gas_before(&self) -> Word {
+        self.gas_before
+    }
+
+    /// Remaining global gas after the instruction.
+    pub const fn gas_after(&self) -> Word {
+        self.gas_after
+    }
+
+    /// Contract storage entries accessed by the instruction: state slots,
+    /// balances and code.
+    pub fn storage(&self) -> &[StorageAccess] {
+        &self.storage
+    }
+
+    /// Panic reason, if the instruction panicked.
+    pub const fn panic_reason(&self) -> Option<PanicReason> {
+        self.panic_reason
+    }
+}
+
+/// Execution tracer.
+///
+/// When enabled, the interpreter appends a [`TraceEntry`] for every executed
+/// instruction. The trace is reset when a new transaction is initialized.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Tracer {
+    enabled: bool,
+    entries: Vec<TraceEntry>,
+}
+
+impl Tracer {
+    /// Check if tracing is enabled.
+    pub const fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enable or disable tracing.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Recorded entries, in execution order.
+    pub fn entries(&self) -> &[TraceEntry] {
+        &self.entries
+    }
+
+    /// Take the recorded entries, leaving the trace empty.
+    pub fn take_entries(&mut self) -> Vec<TraceEntry> {
+        core::mem::take(&mut self.entries)
+    }
+
+    /// Discard the recorded entries.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Append an entry to the trace.
+    pub fn push(&mut self, entry: TraceEntry) {
+        self.entries.push(entry);
+    }
+
+    /// Write the trace as JSON lines, one entry per line.
+    pub fn write_json_lines<W>(&self, mut writer: W) -> io::Result<()>
+    where
+        W: io::Write,
+    {
+        for entry in &self.entries {
+            serde_json::to_writer(&mut writer, entry)?;
+            writer.write_all(b"\n")?;
+        }
+
+        Ok(())
+    }
+}
@@ -7,11 +7,15 @@ use crate::{
         IntoChecked,
     },
     error::InterpreterError,
-    gas::GasCosts,
+    gas::{
+        GasCosts,
+        Schedule,
+    },
     interpreter::{
         CheckedMetadata,
         ExecutableTransaction,
         Interpreter,
+        ReceiptSink,
     },
     state::{
         ProgramState,
@@ -51,6 +55,21 @@ where
         Interpreter::with_storage(storage, params, gas_costs).into()
     }
 
+    /// Transactor following the gas costs and consensus parameters of
+    /// `schedule` at the block height of the storage.
+    pub fn with_schedule(storage: S, schedule: Schedule) -> Self {
+        Interpreter::with_schedule(storage, schedule).into()
+    }
+
+    /// Set the sink notified of the receipts while they are appended.
+    pub fn with_receipt_sink<R>(&mut self, sink: R) -> &mut Self
+    where
+        R: ReceiptSink + Send + Sync + 'static,
+    {
+        self.interpreter.with_receipt_sink(sink);
+        self
+    }
+
     /// State transition representation after the execution of a transaction.
     ///
     /// Will be `None` if the last transaction resulted in a VM panic, or if no
@@ -62,6 +81,8 @@ where
                 self.interpreter.transaction(),
                 self.interpreter.receipts(),
             )
+            .with_read_write_set(self.interpreter.read_write_set())
+            .with_state_diff(self.interpreter.state_diff())
         })
     }
 
@@ -76,6 +97,8 @@ where
                 self.interpreter.transaction().clone(),
                 self.interpreter.receipts().to_vec(),
             )
+            .with_read_write_set(self.interpreter.read_write_set().cloned())
+            .with_state_diff(self.interpreter.state_diff().cloned())
         })
     }
 